@@ -67,6 +67,12 @@
                     nativeBuildInputs = with pkgs; [
                         (rust-bin.stable.latest.default.override {
                             extensions = [ "rust-src" ];
+
+                            # Used to check no_std builds of garden-protocol.
+                            targets = [
+                                "thumbv7em-none-eabihf"
+                                "wasm32-unknown-unknown"
+                            ];
                         })
 
                         gcc
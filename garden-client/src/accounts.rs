@@ -49,28 +49,10 @@ impl Account {
         signing_key: impl Into<SigningKey>,
         password: &[u8]
     ) -> anyhow::Result<Self> {
-        let password = blake3::derive_key(Self::CONTEXT, password);
-        let nonce = Nonce::from_slice(&Self::NONCE);
-
-        let mut encryptor = ChaCha20Poly1305::new_from_slice(&password)
-            .map_err(|err| {
-                anyhow::anyhow!("failed to create chacha20poly1305 encryptor")
-                    .context(err)
-            })?;
-
-        let signing_key: SigningKey = signing_key.into();
-        let signing_key = signing_key.to_bytes();
-
-        let signing_key = encryptor.encrypt(nonce, signing_key.as_slice())
-            .map_err(|err| {
-                anyhow::anyhow!("failed to encrypt account signing key")
-                    .context(err)
-            })?;
-
         Ok(Self {
             name: name.to_string(),
             created_at: UtcDateTime::now(),
-            signing_key: base64::encode(signing_key)
+            signing_key: encrypt_signing_key(&signing_key.into(), Self::CONTEXT, password)?
         })
     }
 
@@ -85,42 +67,9 @@ impl Account {
     }
 
     /// Try to decrypt account signing key using provided password.
+    #[inline]
     pub fn signing_key(&self, password: &[u8]) -> anyhow::Result<SigningKey> {
-        let password = blake3::derive_key(Self::CONTEXT, password);
-        let nonce = Nonce::from_slice(&Self::NONCE);
-
-        let mut decryptor = ChaCha20Poly1305::new_from_slice(&password)
-            .map_err(|err| {
-                anyhow::anyhow!("failed to create chacha20poly1305 decryptor")
-                    .context(err)
-            })?;
-
-        let signing_key = base64::decode(&self.signing_key)
-            .map_err(|err| {
-                anyhow::anyhow!("failed to decode account signing key from base64")
-                    .context(err)
-            })?;
-
-        let signing_key = decryptor.decrypt(nonce, signing_key.as_slice())
-            .map_err(|err| {
-                anyhow::anyhow!("failed to decrypt account signing key")
-                    .context(err)
-            })?;
-
-        if signing_key.len() != SigningKey::SIZE {
-            anyhow::bail!("invalid signing key size");
-        }
-
-        let mut buf = [0; SigningKey::SIZE];
-
-        buf.copy_from_slice(&signing_key);
-
-        let signing_key = SigningKey::from_bytes(&buf)
-            .ok_or_else(|| {
-                anyhow::anyhow!("failed to decode account signing key from decrypted binary data")
-            })?;
-
-        Ok(signing_key)
+        decrypt_signing_key(&self.signing_key, Self::CONTEXT, password)
     }
 
     pub fn to_json(&self) -> Json {
@@ -150,6 +99,78 @@ impl Account {
     }
 }
 
+/// Encrypt signing key with chacha20poly1305 using a key derived from
+/// provided context and secret, and encode it with base64.
+///
+/// The same nonce is used for all the keys, so every encrypted signing key
+/// must use its own context or secret.
+pub fn encrypt_signing_key(
+    signing_key: &SigningKey,
+    context: &str,
+    secret: &[u8]
+) -> anyhow::Result<String> {
+    let secret = blake3::derive_key(context, secret);
+    let nonce = Nonce::from_slice(&Account::NONCE);
+
+    let mut encryptor = ChaCha20Poly1305::new_from_slice(&secret)
+        .map_err(|err| {
+            anyhow::anyhow!("failed to create chacha20poly1305 encryptor")
+                .context(err)
+        })?;
+
+    let signing_key = encryptor.encrypt(nonce, signing_key.to_bytes().as_slice())
+        .map_err(|err| {
+            anyhow::anyhow!("failed to encrypt signing key")
+                .context(err)
+        })?;
+
+    Ok(base64::encode(signing_key))
+}
+
+/// Try to decrypt signing key produced by the `encrypt_signing_key` function
+/// with the same context and secret.
+pub fn decrypt_signing_key(
+    signing_key: &str,
+    context: &str,
+    secret: &[u8]
+) -> anyhow::Result<SigningKey> {
+    let secret = blake3::derive_key(context, secret);
+    let nonce = Nonce::from_slice(&Account::NONCE);
+
+    let mut decryptor = ChaCha20Poly1305::new_from_slice(&secret)
+        .map_err(|err| {
+            anyhow::anyhow!("failed to create chacha20poly1305 decryptor")
+                .context(err)
+        })?;
+
+    let signing_key = base64::decode(signing_key)
+        .map_err(|err| {
+            anyhow::anyhow!("failed to decode signing key from base64")
+                .context(err)
+        })?;
+
+    let signing_key = decryptor.decrypt(nonce, signing_key.as_slice())
+        .map_err(|err| {
+            anyhow::anyhow!("failed to decrypt signing key")
+                .context(err)
+        })?;
+
+    if signing_key.len() != SigningKey::SIZE {
+        anyhow::bail!("invalid signing key size");
+    }
+
+    let mut buf = [0; SigningKey::SIZE];
+
+    buf.copy_from_slice(&signing_key);
+
+    let signing_key = SigningKey::from_bytes(&buf)
+        .ok_or_else(|| {
+            anyhow::anyhow!("failed to decode signing key from decrypted binary data")
+        })?;
+
+    Ok(signing_key)
+}
+
 /// Try to read accounts file.
 pub fn read() -> anyhow::Result<Box<[Account]>> {
     if !crate::ACCOUNTS_FILE_PATH.is_file() {
@@ -22,6 +22,11 @@ use anyhow::Context;
 use serde_json::{json, Value as Json};
 
 use flowerpot::address::Address;
+use flowerpot::crypto::sign::SigningKey;
+
+use garden_protocol::StampPolicy;
+
+use crate::moderation::Policy;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Config {
@@ -31,8 +36,170 @@ pub struct Config {
     /// List of bootstrap flowerpot nodes addresses.
     pub node_bootstrap: Vec<String>,
 
+    /// List of garden protocol blockchains (communities) the node
+    /// participates in. Always contains at least one chain.
+    pub chains: Vec<ChainConfig>,
+
+    /// Local content moderation policy.
+    pub moderation: Policy,
+
+    /// List of saved feed queries shown as custom feeds.
+    pub feeds: Vec<SavedFeed>
+}
+
+/// Garden protocol blockchain configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainConfig {
+    /// Name of the chain shown in the UI.
+    pub name: String,
+
     /// Garden protocol blockchain address.
-    pub blockchain_address: Address
+    pub address: Address,
+
+    /// Minimal proof of work stamps difficulty of indexed events. Stamps of
+    /// this difficulty are minted for sent events.
+    pub stamps: StampPolicy,
+
+    /// Base64 encoded chacha20poly1305 encrypted root signer of the chain if
+    /// it was created by this client. It's encrypted using signing key of the
+    /// account which created the chain, and the node seals garden events into
+    /// new blocks of the chain using it when this account is logged in.
+    pub validator: Option<String>
+}
+
+impl Default for ChainConfig {
+    fn default() -> Self {
+        Self {
+            name: String::from("Public garden"),
+            address: Address::from_base64("AwVwKRoob1NIyRhn5vXtTD6H3yxpDO5Y7JRMruE8g25U5nbZGQ==").unwrap(),
+            stamps: StampPolicy::default(),
+            validator: None
+        }
+    }
+}
+
+impl ChainConfig {
+    pub const VALIDATOR_CONTEXT: &str = "garden client chain validator encryption key context";
+
+    /// Secret used to encrypt the chain's root signer. The chain address is
+    /// a part of the secret so root signers of different chains are never
+    /// encrypted with the same key.
+    fn validator_secret(&self, account_key: &SigningKey) -> Vec<u8> {
+        let mut secret = account_key.to_bytes().to_vec();
+
+        secret.extend_from_slice(self.address.to_base64().as_bytes());
+
+        secret
+    }
+
+    /// Encrypt provided root signer of the chain using signing key of the
+    /// logged in account and store it as the chain's validator.
+    pub fn set_validator(
+        &mut self,
+        root_signer: &SigningKey,
+        account_key: &SigningKey
+    ) -> anyhow::Result<()> {
+        let secret = self.validator_secret(account_key);
+
+        self.validator = Some(crate::accounts::encrypt_signing_key(
+            root_signer,
+            Self::VALIDATOR_CONTEXT,
+            &secret
+        )?);
+
+        Ok(())
+    }
+
+    /// Try to decrypt the chain's root signer using signing key of the logged
+    /// in account. Return `None` if the chain has no validator.
+    pub fn validator(
+        &self,
+        account_key: &SigningKey
+    ) -> Option<anyhow::Result<SigningKey>> {
+        let validator = self.validator.as_deref()?;
+
+        Some(crate::accounts::decrypt_signing_key(
+            validator,
+            Self::VALIDATOR_CONTEXT,
+            &self.validator_secret(account_key)
+        ))
+    }
+
+    pub fn to_json(&self) -> Json {
+        let mut value = json!({
+            "name": self.name,
+            "address": self.address.to_base64(),
+            "stamps": {
+                "post": self.stamps.post,
+                "comment": self.stamps.comment,
+                "reaction": self.stamps.reaction,
+                "delegation": self.stamps.delegation,
+                "rotation": self.stamps.rotation,
+                "report": self.stamps.report,
+                "group": self.stamps.group
+            }
+        });
+
+        if let Some(validator) = &self.validator {
+            value["validator"] = Json::String(validator.clone());
+        }
+
+        value
+    }
+
+    /// Try to read chain config. Return `None` if the chain address is
+    /// missing or invalid.
+    pub fn from_json(value: &Json) -> Option<Self> {
+        let default = Self::default();
+
+        let address = value.get("address")
+            .and_then(Json::as_str)
+            .and_then(Address::from_base64)?;
+
+        Some(Self {
+            name: value.get("name")
+                .and_then(Json::as_str)
+                .map(String::from)
+                .unwrap_or(default.name),
+
+            address,
+
+            stamps: value.get("stamps")
+                .map(|stamps| {
+                    let difficulty = |name: &str| {
+                        stamps.get(name)
+                            .and_then(Json::as_u64)
+                            .and_then(|difficulty| u32::try_from(difficulty).ok())
+                            .unwrap_or(0)
+                    };
+
+                    StampPolicy {
+                        post: difficulty("post"),
+                        comment: difficulty("comment"),
+                        reaction: difficulty("reaction"),
+                        delegation: difficulty("delegation"),
+                        rotation: difficulty("rotation"),
+                        report: difficulty("report"),
+                        group: difficulty("group")
+                    }
+                })
+                .unwrap_or(default.stamps),
+
+            validator: value.get("validator")
+                .and_then(Json::as_str)
+                .map(String::from)
+        })
+    }
+}
+
+/// Feed query saved by the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SavedFeed {
+    /// Name of the feed shown in the UI.
+    pub name: String,
+
+    /// Feed query (see `garden_protocol::feed::FeedQuery`).
+    pub query: String
 }
 
 impl Default for Config {
@@ -40,7 +207,9 @@ impl Default for Config {
         Self {
             node_address: SocketAddr::new(Ipv6Addr::UNSPECIFIED.into(), 13400),
             node_bootstrap: Vec::new(),
-            blockchain_address: Address::from_base64("AwVwKRoob1NIyRhn5vXtTD6H3yxpDO5Y7JRMruE8g25U5nbZGQ==").unwrap()
+            chains: vec![ChainConfig::default()],
+            moderation: Policy::default(),
+            feeds: Vec::new()
         }
     }
 }
@@ -52,9 +221,20 @@ impl Config {
                 "address": self.node_address.to_string(),
                 "bootstrap": self.node_bootstrap
             },
-            "blockchain": {
-                "address": self.blockchain_address.to_base64()
-            }
+            "chains": self.chains.iter()
+                .map(ChainConfig::to_json)
+                .collect::<Vec<_>>(),
+
+            "moderation": self.moderation.to_json(),
+
+            "feeds": self.feeds.iter()
+                .map(|feed| {
+                    json!({
+                        "name": feed.name,
+                        "query": feed.query
+                    })
+                })
+                .collect::<Vec<_>>()
         })
     }
 
@@ -85,14 +265,40 @@ impl Config {
             })
             .unwrap_or(default.node_bootstrap),
 
-            blockchain_address: value.get("blockchain")
-                .map(|blockchain| {
-                    blockchain.get("address")
-                        .and_then(Json::as_str)
-                        .and_then(Address::from_base64)
-                        .unwrap_or(default.blockchain_address.clone())
+            // Single "blockchain" section is read for compatibility with
+            // configs made before multiple chains were supported.
+            chains: value.get("chains")
+                .and_then(Json::as_array)
+                .map(|chains| {
+                    chains.iter()
+                        .flat_map(ChainConfig::from_json)
+                        .collect::<Vec<_>>()
+                })
+                .or_else(|| {
+                    value.get("blockchain")
+                        .and_then(ChainConfig::from_json)
+                        .map(|chain| vec![chain])
+                })
+                .filter(|chains| !chains.is_empty())
+                .unwrap_or(default.chains),
+
+            moderation: value.get("moderation")
+                .map(Policy::from_json)
+                .unwrap_or(default.moderation),
+
+            feeds: value.get("feeds")
+                .and_then(Json::as_array)
+                .map(|feeds| {
+                    feeds.iter()
+                        .flat_map(|feed| {
+                            Some(SavedFeed {
+                                name: feed.get("name")?.as_str()?.to_string(),
+                                query: feed.get("query")?.as_str()?.to_string()
+                            })
+                        })
+                        .collect()
                 })
-                .unwrap_or(default.blockchain_address)
+                .unwrap_or(default.feeds)
         }
     }
 }
@@ -25,6 +25,7 @@ use anyhow::Context;
 pub mod config;
 pub mod node;
 pub mod accounts;
+pub mod moderation;
 pub mod ui;
 
 lazy_static::lazy_static! {
@@ -123,6 +124,8 @@ fn main() -> anyhow::Result<()> {
     // Run the app.
     let app = RelmApp::new("com.github.krypt0nn.garden");
 
+    app.set_global_css(include_str!("../assets/style.css"));
+
     app.run::<ui::login_window::LoginWindow>(accounts::read()?.to_vec());
 
     Ok(())
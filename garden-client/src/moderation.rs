@@ -0,0 +1,605 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-client
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use serde_json::{json, Value as Json};
+
+use flowerpot::crypto::sign::VerifyingKey;
+
+use garden_protocol::ReportReason;
+use garden_protocol::index::Index;
+use garden_protocol::index::post::PostInfo;
+use garden_protocol::index::report::ReportIndex;
+
+/// Action applied to the content by the moderation policy. Actions are
+/// ordered by their severity.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Action {
+    /// Show content as is.
+    #[default]
+    Show,
+
+    /// Collapse content so it's visible only when expanded.
+    Collapse,
+
+    /// Blur content until clicked on.
+    Blur,
+
+    /// Don't show content at all.
+    Hide
+}
+
+impl Action {
+    pub const fn to_name(&self) -> &'static str {
+        match self {
+            Self::Show     => "show",
+            Self::Collapse => "collapse",
+            Self::Blur     => "blur",
+            Self::Hide     => "hide"
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "show"     => Some(Self::Show),
+            "collapse" => Some(Self::Collapse),
+            "blur"     => Some(Self::Blur),
+            "hide"     => Some(Self::Hide),
+
+            _ => None
+        }
+    }
+
+    /// Past tense verb used to explain decisions to the user.
+    pub const fn to_verb(&self) -> &'static str {
+        match self {
+            Self::Show     => "shown",
+            Self::Collapse => "collapsed",
+            Self::Blur     => "blurred",
+            Self::Hide     => "hidden"
+        }
+    }
+}
+
+/// Moderation policy decision with the list of reasons explaining it.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Decision {
+    /// The most severe action of all the applied rules.
+    pub action: Action,
+
+    /// Human readable reasons of the decision.
+    pub reasons: Vec<String>
+}
+
+impl Decision {
+    fn apply(&mut self, action: Action, reason: String) {
+        self.action = self.action.max(action);
+        self.reasons.push(reason);
+    }
+
+    /// Human readable explanation of the decision, e.g. "hidden because author
+    /// is blocked".
+    pub fn explain(&self) -> Option<String> {
+        if self.action == Action::Show || self.reasons.is_empty() {
+            return None;
+        }
+
+        Some(format!("{} because {}", self.action.to_verb(), self.reasons.join(", ")))
+    }
+}
+
+/// Content containing provided keyword will be moderated using provided
+/// action. Rules with blank keywords are ignored because they would match
+/// any content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeywordRule {
+    pub keyword: String,
+    pub action: Action
+}
+
+/// Local moderation policy. It's never shared with other users and only
+/// affects the content shown in the current client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Policy {
+    /// Keys whose reports are trusted.
+    pub trusted_reporters: Vec<VerifyingKey>,
+
+    /// Amount of unique trusted reporters needed to moderate the content.
+    pub reports_threshold: usize,
+
+    /// Action applied to the content reported by trusted reporters.
+    pub reports_action: Action,
+
+    /// Keyword rules.
+    pub keyword_rules: Vec<KeywordRule>,
+
+    /// Authors whose content is always hidden.
+    pub blocked_authors: Vec<VerifyingKey>,
+
+    /// Show posts with `nsfw` tag without blurring them.
+    pub show_nsfw: bool
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self {
+            trusted_reporters: Vec::new(),
+            reports_threshold: 1,
+            reports_action: Action::Collapse,
+            keyword_rules: Vec::new(),
+            blocked_authors: Vec::new(),
+            show_nsfw: false
+        }
+    }
+}
+
+impl Policy {
+    /// Decide what to do with the post using the list of its reports.
+    ///
+    /// Index is used to resolve identities of the reporters, so reports made
+    /// from delegated device keys or after identity key rotations are still
+    /// counted as reports of the trusted users.
+    pub fn decide<'a>(
+        &self,
+        index: &Index,
+        post: &PostInfo,
+        reports: impl IntoIterator<Item = &'a ReportIndex>
+    ) -> Decision {
+        let mut decision = Decision::default();
+
+        // Blocked authors.
+        if self.blocked_authors.contains(&post.identity)
+            || self.blocked_authors.contains(&post.author)
+        {
+            decision.apply(Action::Hide, String::from("author is blocked"));
+        }
+
+        // Special tags.
+        if post.is_nsfw() && !self.show_nsfw {
+            decision.apply(Action::Blur, String::from("it is not safe for work"));
+        }
+
+        if post.is_spoiler() {
+            decision.apply(Action::Blur, String::from("it contains spoilers"));
+        }
+
+        // Keyword rules.
+        let content = post.content.to_lowercase();
+
+        for rule in &self.keyword_rules {
+            if rule.keyword.trim().is_empty() {
+                continue;
+            }
+
+            let keyword = rule.keyword.to_lowercase();
+
+            let matches = content.contains(&keyword)
+                || post.tags.iter().any(|tag| tag.as_str() == keyword);
+
+            if matches {
+                decision.apply(rule.action, format!("it contains \"{}\"", rule.keyword));
+            }
+        }
+
+        // Trusted reports.
+        let mut reporters = Vec::new();
+        let mut reasons = Vec::<ReportReason>::new();
+
+        for report in reports {
+            let identity = index.identity(report.author(), report.timestamp());
+
+            let trusted = self.trusted_reporters.iter()
+                .any(|key| index.latest_key(key) == identity);
+
+            if trusted && !reporters.contains(&identity) {
+                reporters.push(identity);
+
+                if !reasons.contains(report.reason()) {
+                    reasons.push(*report.reason());
+                }
+            }
+        }
+
+        if !reporters.is_empty() && reporters.len() >= self.reports_threshold {
+            let reasons = reasons.iter()
+                .map(ReportReason::to_name)
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            decision.apply(self.reports_action, format!(
+                "{} trusted user(s) reported it as {reasons}",
+                reporters.len()
+            ));
+        }
+
+        decision
+    }
+
+    pub fn to_json(&self) -> Json {
+        json!({
+            "reports": {
+                "trusted": self.trusted_reporters.iter()
+                    .map(VerifyingKey::to_base64)
+                    .collect::<Vec<_>>(),
+
+                "threshold": self.reports_threshold,
+                "action": self.reports_action.to_name()
+            },
+
+            "keywords": self.keyword_rules.iter()
+                .map(|rule| {
+                    json!({
+                        "keyword": rule.keyword,
+                        "action": rule.action.to_name()
+                    })
+                })
+                .collect::<Vec<_>>(),
+
+            "blocked": self.blocked_authors.iter()
+                .map(VerifyingKey::to_base64)
+                .collect::<Vec<_>>(),
+
+            "show_nsfw": self.show_nsfw
+        })
+    }
+
+    pub fn from_json(value: &Json) -> Self {
+        fn keys(value: Option<&Json>) -> Vec<VerifyingKey> {
+            value.and_then(Json::as_array)
+                .map(|keys| {
+                    keys.iter()
+                        .flat_map(Json::as_str)
+                        .flat_map(VerifyingKey::from_base64)
+                        .collect()
+                })
+                .unwrap_or_default()
+        }
+
+        let default = Self::default();
+
+        let reports = value.get("reports");
+
+        Self {
+            trusted_reporters: keys(reports.and_then(|reports| reports.get("trusted"))),
+
+            reports_threshold: reports.and_then(|reports| reports.get("threshold"))
+                .and_then(Json::as_u64)
+                .map(|threshold| threshold as usize)
+                .unwrap_or(default.reports_threshold),
+
+            reports_action: reports.and_then(|reports| reports.get("action"))
+                .and_then(Json::as_str)
+                .and_then(Action::from_name)
+                .unwrap_or(default.reports_action),
+
+            keyword_rules: value.get("keywords")
+                .and_then(Json::as_array)
+                .map(|rules| {
+                    rules.iter()
+                        .flat_map(|rule| {
+                            Some(KeywordRule {
+                                keyword: rule.get("keyword")
+                                    .and_then(Json::as_str)
+                                    .filter(|keyword| !keyword.trim().is_empty())
+                                    .map(String::from)?,
+
+                                action: rule.get("action")
+                                    .and_then(Json::as_str)
+                                    .and_then(Action::from_name)?
+                            })
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+
+            blocked_authors: keys(value.get("blocked")),
+
+            show_nsfw: value.get("show_nsfw")
+                .and_then(Json::as_bool)
+                .unwrap_or(default.show_nsfw)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use flowerpot::crypto::hash::Hash;
+    use flowerpot::crypto::sign::SigningKey;
+
+    use garden_protocol::*;
+    use garden_protocol::testing::*;
+
+    use super::*;
+
+    fn post(content: &str, tags: &[&str]) -> PostEvent {
+        PostEvent::new(
+            Content::new(content).unwrap(),
+            tags.iter().map(|tag| Tag::new(tag).unwrap())
+        ).unwrap()
+    }
+
+    fn report(post: Hash, reason: ReportReason) -> ReportEvent {
+        ReportEvent::new(post, reason, Content::new("").unwrap())
+    }
+
+    /// Build a chain with a post and reports made by provided keys and return
+    /// the index of this chain with the indexed post.
+    fn index(
+        chain: &mut ChainBuilder,
+        author: &SigningKey,
+        reporters: &[(&SigningKey, ReportReason)]
+    ) -> (Index, PostInfo) {
+        let post_hash = chain.event(author, post("Hello, World!", &["test"])).unwrap();
+
+        chain.block().unwrap();
+
+        for (reporter, reason) in reporters {
+            chain.event(reporter, report(post_hash, *reason)).unwrap();
+        }
+
+        chain.block().unwrap();
+
+        let storage = chain.storage();
+
+        let mut index = Index::default();
+
+        index.update(&storage).unwrap();
+
+        let post = index.posts()
+            .find(|post| post.message_hash() == &post_hash)
+            .unwrap()
+            .read(&index, &storage)
+            .unwrap();
+
+        (index, post)
+    }
+
+    #[test]
+    fn decide() {
+        let author = signing_key(1);
+        let trusted = signing_key(2);
+        let stranger = signing_key(3);
+
+        let mut chain = ChainBuilder::new(signing_key(0));
+
+        let (index, post) = index(&mut chain, &author, &[
+            (&trusted, ReportReason::Spam),
+            (&trusted, ReportReason::Spam),
+            (&stranger, ReportReason::Illegal)
+        ]);
+
+        let decision = Policy::default()
+            .decide(&index, &post, index.reports_of(&post.message_hash));
+
+        assert_eq!(decision, Decision::default());
+
+        let policy = Policy {
+            trusted_reporters: vec![trusted.verifying_key()],
+            ..Policy::default()
+        };
+
+        let decision = policy.decide(&index, &post, index.reports_of(&post.message_hash));
+
+        assert_eq!(decision.action, Action::Collapse);
+        assert_eq!(decision.reasons, ["1 trusted user(s) reported it as spam"]);
+
+        let policy = Policy {
+            trusted_reporters: vec![trusted.verifying_key()],
+            reports_threshold: 2,
+            ..Policy::default()
+        };
+
+        let decision = policy.decide(&index, &post, index.reports_of(&post.message_hash));
+
+        assert_eq!(decision.action, Action::Show);
+
+        let policy = Policy {
+            keyword_rules: vec![
+                KeywordRule {
+                    keyword: String::from("WORLD"),
+                    action: Action::Collapse
+                },
+                KeywordRule {
+                    keyword: String::from("test"),
+                    action: Action::Blur
+                }
+            ],
+            blocked_authors: vec![author.verifying_key()],
+            ..Policy::default()
+        };
+
+        let decision = policy.decide(&index, &post, []);
+
+        assert_eq!(decision.action, Action::Hide);
+        assert_eq!(decision.reasons, [
+            "author is blocked",
+            "it contains \"WORLD\"",
+            "it contains \"test\""
+        ]);
+    }
+
+    #[test]
+    fn decide_blank_keywords() {
+        let mut chain = ChainBuilder::new(signing_key(0));
+
+        let (index, post) = index(&mut chain, &signing_key(1), &[]);
+
+        let policy = Policy {
+            keyword_rules: vec![
+                KeywordRule {
+                    keyword: String::new(),
+                    action: Action::Hide
+                },
+                KeywordRule {
+                    keyword: String::from(" "),
+                    action: Action::Hide
+                }
+            ],
+            ..Policy::default()
+        };
+
+        assert_eq!(policy.decide(&index, &post, []), Decision::default());
+    }
+
+    #[test]
+    fn decide_special_tags() {
+        let author = signing_key(1);
+
+        let mut chain = ChainBuilder::new(signing_key(0));
+
+        let post_hash = chain.event(&author, post("Hello, World!", &["nsfw"])).unwrap();
+
+        chain.block().unwrap();
+
+        let storage = chain.storage();
+
+        let mut index = Index::default();
+
+        index.update(&storage).unwrap();
+
+        let post = index.posts()
+            .find(|post| post.message_hash() == &post_hash)
+            .unwrap()
+            .read(&index, &storage)
+            .unwrap();
+
+        let decision = Policy::default().decide(&index, &post, []);
+
+        assert_eq!(decision.action, Action::Blur);
+        assert_eq!(decision.reasons, ["it is not safe for work"]);
+
+        let policy = Policy {
+            show_nsfw: true,
+            ..Policy::default()
+        };
+
+        assert_eq!(policy.decide(&index, &post, []).action, Action::Show);
+    }
+
+    #[test]
+    fn decide_delegated_reporter() {
+        let author = signing_key(1);
+        let trusted = signing_key(2);
+        let device = signing_key(3);
+
+        let mut chain = ChainBuilder::new(signing_key(0));
+
+        chain.event(&trusted, DelegationEvent::authorize(device.verifying_key())).unwrap();
+        chain.event(&device, DelegationEvent::accept(trusted.verifying_key())).unwrap();
+        chain.block().unwrap();
+
+        // Both reports belong to the same identity and are counted once.
+        let (index, post) = index(&mut chain, &author, &[
+            (&trusted, ReportReason::Spam),
+            (&device, ReportReason::Harassment)
+        ]);
+
+        let policy = Policy {
+            trusted_reporters: vec![trusted.verifying_key()],
+            ..Policy::default()
+        };
+
+        let decision = policy.decide(&index, &post, index.reports_of(&post.message_hash));
+
+        assert_eq!(decision.action, Action::Collapse);
+        assert_eq!(decision.reasons, ["1 trusted user(s) reported it as spam, harassment"]);
+
+        let policy = Policy {
+            trusted_reporters: vec![trusted.verifying_key()],
+            reports_threshold: 2,
+            ..Policy::default()
+        };
+
+        let decision = policy.decide(&index, &post, index.reports_of(&post.message_hash));
+
+        assert_eq!(decision.action, Action::Show);
+    }
+
+    #[test]
+    fn explain() {
+        assert_eq!(Decision::default().explain(), None);
+
+        let decision = Decision {
+            action: Action::Show,
+            reasons: vec![String::from("author is blocked")]
+        };
+
+        assert_eq!(decision.explain(), None);
+
+        let decision = Decision {
+            action: Action::Hide,
+            reasons: vec![
+                String::from("author is blocked"),
+                String::from("it contains spoilers")
+            ]
+        };
+
+        assert_eq!(
+            decision.explain().as_deref(),
+            Some("hidden because author is blocked, it contains spoilers")
+        );
+    }
+
+    #[test]
+    fn json() {
+        let policy = Policy {
+            trusted_reporters: vec![signing_key(1).verifying_key()],
+            reports_threshold: 3,
+            reports_action: Action::Hide,
+            keyword_rules: vec![
+                KeywordRule {
+                    keyword: String::from("spoiler"),
+                    action: Action::Blur
+                }
+            ],
+            blocked_authors: vec![
+                signing_key(2).verifying_key(),
+                signing_key(3).verifying_key()
+            ],
+            show_nsfw: true
+        };
+
+        assert_eq!(Policy::from_json(&policy.to_json()), policy);
+        assert_eq!(Policy::from_json(&Policy::default().to_json()), Policy::default());
+
+        // Missing and malformed values are replaced by defaults.
+        assert_eq!(Policy::from_json(&json!({})), Policy::default());
+
+        let policy = Policy::from_json(&json!({
+            "reports": {
+                "trusted": ["invalid key"],
+                "threshold": "invalid threshold",
+                "action": "invalid action"
+            },
+            "keywords": [
+                { "keyword": "spoiler", "action": "invalid action" },
+                { "keyword": "", "action": "hide" },
+                { "keyword": "nsfw", "action": "blur" }
+            ]
+        }));
+
+        assert_eq!(policy, Policy {
+            keyword_rules: vec![
+                KeywordRule {
+                    keyword: String::from("nsfw"),
+                    action: Action::Blur
+                }
+            ],
+            ..Policy::default()
+        });
+    }
+}
@@ -18,20 +18,73 @@
 
 use std::net::{SocketAddr, TcpListener};
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
 
 use rand_chacha::ChaCha20Rng;
 use rand_chacha::rand_core::{SeedableRng, RngCore};
 
 use anyhow::Context;
 
+use flowerpot::address::Address;
+use flowerpot::crypto::hash::Hash;
 use flowerpot::crypto::key_exchange::SecretKey;
+use flowerpot::crypto::sign::{SigningKey, VerifyingKey};
+use flowerpot::block::Block;
+use flowerpot::message::Message;
+use flowerpot::storage::{Storage, StorageError};
 use flowerpot::storage::sqlite_storage::SqliteStorage;
-use flowerpot::protocol::network::{
-    PacketStream, PacketStreamOptions, PacketStreamEncryption
-};
+use flowerpot::protocol::network::{PacketStreamOptions, PacketStreamEncryption};
 use flowerpot::node::{Node, NodeOptions, NodeHandler};
 
-use crate::config::Config;
+use garden_protocol::StampPolicy;
+use garden_protocol::handler::{Handler, listener};
+use garden_protocol::handler::chain::{self, Invite};
+
+use crate::config::{Config, ChainConfig};
+
+/// Proof of work stamps policies of the garden chains used by the node's
+/// messages filter.
+static STAMP_POLICIES: OnceLock<Vec<(Address, StampPolicy)>> = OnceLock::new();
+
+/// Addresses of the garden chains validated by this node.
+static VALIDATED_CHAINS: OnceLock<Vec<Address>> = OnceLock::new();
+
+/// Garden events received by the node which should be sealed into new blocks
+/// of the validated chains.
+static PENDING_MESSAGES: Mutex<Vec<(Address, Message)>> = Mutex::new(Vec::new());
+
+/// Interval between blocks sealed by the validator of a garden chain.
+const BLOCK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Filter out flowerpot messages which are not garden events or don't have
+/// proof of work stamps required by the config of their chain.
+fn messages_filter(
+    address: &Address,
+    message: &Message,
+    author: &VerifyingKey
+) -> bool {
+    let policy = STAMP_POLICIES.get()
+        .and_then(|policies| {
+            policies.iter()
+                .find(|(chain, _)| chain == address)
+                .map(|(_, policy)| *policy)
+        })
+        .unwrap_or_default();
+
+    if !garden_protocol::messages_filter_with(&policy, address, message, author) {
+        return false;
+    }
+
+    let is_validated = VALIDATED_CHAINS.get()
+        .is_some_and(|chains| chains.contains(address));
+
+    if is_validated && let Ok(mut pending) = PENDING_MESSAGES.lock() {
+        pending.push((address.clone(), message.clone()));
+    }
+
+    true
+}
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Progress {
@@ -51,30 +104,110 @@ pub enum Progress {
     StartListener(SocketAddr)
 }
 
+/// Flowerpot blockchain storage wrapper which calls provided callback every
+/// time a new block is written to the storage.
+struct WatchedStorage<S> {
+    storage: S,
+    on_block: Box<dyn Fn() + Send + Sync>
+}
+
+impl<S: Storage> Storage for WatchedStorage<S> {
+    #[inline]
+    fn root_block(&self) -> Result<Option<Hash>, StorageError> {
+        self.storage.root_block()
+    }
+
+    #[inline]
+    fn tail_block(&self) -> Result<Option<Hash>, StorageError> {
+        self.storage.tail_block()
+    }
+
+    #[inline]
+    fn has_block(&self, hash: &Hash) -> Result<bool, StorageError> {
+        self.storage.has_block(hash)
+    }
+
+    #[inline]
+    fn next_block(&self, hash: &Hash) -> Result<Option<Hash>, StorageError> {
+        self.storage.next_block(hash)
+    }
+
+    #[inline]
+    fn prev_block(&self, hash: &Hash) -> Result<Option<Hash>, StorageError> {
+        self.storage.prev_block(hash)
+    }
+
+    #[inline]
+    fn read_block(&self, hash: &Hash) -> Result<Option<Block>, StorageError> {
+        self.storage.read_block(hash)
+    }
+
+    fn write_block(&self, block: &Block) -> Result<bool, StorageError> {
+        let written = self.storage.write_block(block)?;
+
+        if written {
+            (self.on_block)();
+        }
+
+        Ok(written)
+    }
+
+    #[inline]
+    fn has_message(&self, hash: &Hash) -> Result<bool, StorageError> {
+        self.storage.has_message(hash)
+    }
+
+    #[inline]
+    fn find_message(&self, hash: &Hash) -> Result<Option<Hash>, StorageError> {
+        self.storage.find_message(hash)
+    }
+
+    #[inline]
+    fn read_message(&self, hash: &Hash) -> Result<Option<Message>, StorageError> {
+        self.storage.read_message(hash)
+    }
+}
+
 /// Try to start flowerpot node.
 ///
 /// This method will establish packet streams with bootstrap nodes listed in the
 /// config file, synchronize blockchain, start background thread to listen to
 /// incoming stream connections and return started node handler.
 ///
+/// Provided `on_block` callback is called with the chain address every time
+/// the node writes a new block to any of the chains' storages.
+///
 /// It's recommended to use this function in a separate thread.
 pub fn start(
     config: &Config,
-    mut progress: impl FnMut(Progress)
+    mut progress: impl FnMut(Progress),
+    on_block: impl Fn(&Address) + Send + Sync + 'static
 ) -> anyhow::Result<NodeHandler> {
     // Create the node.
     let mut node = Node::default();
 
-    // Attach blockchain storage.
-    let storage_path = crate::STORAGES_FOLDER_PATH
-        .join(format!("{}.db", config.blockchain_address.to_base64()));
+    let on_block = Arc::new(on_block);
 
-    progress(Progress::CreateTracker(storage_path.clone()));
+    // Attach storages of all the garden chains.
+    for chain in &config.chains {
+        let storage_path = crate::STORAGES_FOLDER_PATH
+            .join(format!("{}.db", chain.address.to_base64()));
 
-    let storage = SqliteStorage::open(storage_path)
-        .context("failed to open flowerpot blockchain storage")?;
+        progress(Progress::CreateTracker(storage_path.clone()));
 
-    node = node.add_storage(config.blockchain_address.clone(), storage);
+        let storage = SqliteStorage::open(storage_path)
+            .with_context(|| format!("failed to open flowerpot blockchain storage of '{}' chain", chain.name))?;
+
+        let on_block = on_block.clone();
+        let address = chain.address.clone();
+
+        let storage = WatchedStorage {
+            storage,
+            on_block: Box::new(move || on_block(&address))
+        };
+
+        node = node.add_storage(chain.address.clone(), storage);
+    }
 
     // Generate ECDH secret key.
     let mut rng = ChaCha20Rng::from_entropy();
@@ -116,8 +249,21 @@ pub fn start(
     // Start the node.
     progress(Progress::StartNode);
 
+    let _ = STAMP_POLICIES.set({
+        config.chains.iter()
+            .map(|chain| (chain.address.clone(), chain.stamps))
+            .collect()
+    });
+
+    let _ = VALIDATED_CHAINS.set({
+        config.chains.iter()
+            .filter(|chain| chain.validator.is_some())
+            .map(|chain| chain.address.clone())
+            .collect()
+    });
+
     let handler = node.start(NodeOptions {
-        messages_filter: Some(garden_protocol::messages_filter),
+        messages_filter: Some(messages_filter),
 
         ..NodeOptions::default()
     });
@@ -131,26 +277,143 @@ pub fn start(
     progress(Progress::StartListener(config.node_address));
 
     if let Ok(listener) = TcpListener::bind(config.node_address) {
-        let handler = handler.clone();
-
-        std::thread::spawn(move || {
-            loop {
-                let Ok((stream, _)) = listener.accept() else {
-                    continue;
-                };
-
-                let Ok(stream) = PacketStream::init(
-                    &secret_key,
-                    &options,
-                    stream
-                ) else {
-                    continue;
-                };
-
-                handler.add_stream(stream);
-            }
-        });
+        listener::listen(listener, handler.clone(), secret_key, options);
     }
 
     Ok(handler)
 }
+
+/// Start background thread which periodically seals garden events sent by
+/// provided handler or received by the node into new blocks of the handler's
+/// chain using provided validator (root signer) key.
+pub fn start_validator(handler: Handler, validator: SigningKey) {
+    std::thread::spawn(move || {
+        loop {
+            std::thread::sleep(BLOCK_INTERVAL);
+
+            let messages = match PENDING_MESSAGES.lock() {
+                Ok(mut pending) => {
+                    let (messages, rest) = std::mem::take(&mut *pending)
+                        .into_iter()
+                        .partition::<Vec<_>, _>(|(address, _)| {
+                            address == handler.address().as_ref()
+                        });
+
+                    *pending = rest;
+
+                    messages.into_iter()
+                        .map(|(_, message)| message)
+                        .collect::<Vec<_>>()
+                }
+
+                Err(_) => Vec::new()
+            };
+
+            match handler.seal_block(&validator, messages) {
+                Ok(Some(hash)) => tracing::debug!(
+                    address = handler.address().to_base64(),
+                    block = ?hash,
+                    "sealed new block"
+                ),
+
+                Ok(None) => (),
+
+                Err(err) => tracing::error!(
+                    ?err,
+                    address = handler.address().to_base64(),
+                    "failed to seal new block"
+                )
+            }
+        }
+    });
+}
+
+/// Create a new garden chain with provided root signer, initialize its
+/// blockchain storage and add it to the config file.
+///
+/// The root signer is encrypted using signing key of the logged in account
+/// and stored in the chain's config as its validator, so the node seals events
+/// of the created chain into new blocks while this account is logged in.
+///
+/// Return invite into the created chain which contains provided bootstrap
+/// nodes addresses. The chain is available after the node is restarted.
+pub fn create_chain(
+    name: impl ToString,
+    root_signer: &SigningKey,
+    account_key: &SigningKey,
+    bootstrap: Vec<String>
+) -> anyhow::Result<Invite> {
+    let mut config = crate::config::read()?;
+
+    // Choose random chain id so the same signer could create many chains.
+    let chain_id = ChaCha20Rng::from_entropy().next_u32();
+
+    let mut chain = ChainConfig {
+        name: name.to_string(),
+        address: chain::address(root_signer, chain_id),
+        stamps: StampPolicy::default(),
+        validator: None
+    };
+
+    chain.set_validator(root_signer, account_key)
+        .context("failed to encrypt root signer of the garden")?;
+
+    let storage_path = crate::STORAGES_FOLDER_PATH
+        .join(format!("{}.db", chain.address.to_base64()));
+
+    let storage = SqliteStorage::open(storage_path)
+        .context("failed to open flowerpot blockchain storage")?;
+
+    Handler::create_chain(root_signer, chain_id, &storage)
+        .context("failed to create garden blockchain")?;
+
+    let invite = Invite {
+        name: chain.name.clone(),
+        address: chain.address.clone(),
+        stamps: chain.stamps,
+        bootstrap
+    };
+
+    config.chains.push(chain);
+
+    crate::config::write(&config)?;
+
+    tracing::info!(
+        name = invite.name,
+        address = invite.address.to_base64(),
+        bootstrap = ?invite.bootstrap,
+        invite = invite.to_base64(),
+        "created new garden chain"
+    );
+
+    Ok(invite)
+}
+
+/// Add garden chain from provided invite and its bootstrap nodes to the
+/// config file. Return `false` if the chain is already in the config.
+///
+/// The chain is available after the node is restarted.
+pub fn join_chain(invite: Invite) -> anyhow::Result<bool> {
+    let mut config = crate::config::read()?;
+
+    if config.chains.iter().any(|chain| chain.address == invite.address) {
+        return Ok(false);
+    }
+
+    for address in invite.bootstrap {
+        if !config.node_bootstrap.contains(&address) {
+            config.node_bootstrap.push(address);
+        }
+    }
+
+    config.chains.push(ChainConfig {
+        name: invite.name,
+        address: invite.address,
+        stamps: invite.stamps,
+        validator: None
+    });
+
+    crate::config::write(&config)?;
+
+    Ok(true)
+}
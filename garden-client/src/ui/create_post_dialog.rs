@@ -19,7 +19,7 @@
 use adw::prelude::*;
 use relm4::prelude::*;
 
-use garden_protocol::{Content, PostEvent};
+use garden_protocol::{Content, Tag, PostEvent, ValidationError};
 
 #[derive(Debug, Clone)]
 pub enum CreatePostDialogMsg {
@@ -31,8 +31,31 @@ pub enum CreatePostDialogMsg {
 pub struct CreatePostDialog {
     window: adw::Dialog,
     text_view: gtk::TextView,
+    tags_entry: gtk::Entry,
 
-    is_content_valid: bool
+    error: Option<ValidationError>
+}
+
+impl CreatePostDialog {
+    /// Try to build post event from the dialog's input fields.
+    fn build_event(&self) -> Result<PostEvent, ValidationError> {
+        let buffer = self.text_view.buffer();
+
+        let content = buffer.text(
+            &buffer.start_iter(),
+            &buffer.end_iter(),
+            true
+        );
+
+        let tags = self.tags_entry.text();
+
+        let tags = tags.split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|tag| !tag.is_empty())
+            .map(Tag::new)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        PostEvent::new(Content::new(content)?, tags)
+    }
 }
 
 #[relm4::component(pub)]
@@ -56,14 +79,14 @@ impl SimpleComponent for CreatePostDialog {
 
                     pack_end = &gtk::Button {
                         #[watch]
-                        set_css_classes: if model.is_content_valid {
+                        set_css_classes: if model.error.is_none() {
                             &["suggested-action"]
                         } else {
                             &[]
                         },
 
                         #[watch]
-                        set_sensitive: model.is_content_valid,
+                        set_sensitive: model.error.is_none(),
 
                         adw::ButtonContent {
                             set_label: "Publish",
@@ -115,6 +138,52 @@ impl SimpleComponent for CreatePostDialog {
                                         connect_changed => CreatePostDialogMsg::VerifyContent
                                     }
                                 }
+                            },
+
+                            gtk::Label {
+                                set_halign: gtk::Align::Start,
+
+                                set_margin_top: 16,
+
+                                add_css_class: "heading",
+
+                                set_text: "Tags"
+                            },
+
+                            #[local_ref]
+                            tags_entry -> gtk::Entry {
+                                set_margin_top: 8,
+
+                                set_placeholder_text: Some("sport, news"),
+
+                                connect_changed => CreatePostDialogMsg::VerifyContent
+                            },
+
+                            gtk::Label {
+                                set_halign: gtk::Align::Start,
+
+                                set_margin_top: 8,
+
+                                set_wrap: true,
+                                set_wrap_mode: gtk::pango::WrapMode::WordChar,
+
+                                add_css_class: "error",
+
+                                #[watch]
+                                set_visible: model.error.is_some(),
+
+                                #[watch]
+                                set_text: &model.error.as_ref()
+                                    .map(|err| {
+                                        let mut err = err.to_string();
+
+                                        if let Some(first) = err.get_mut(..1) {
+                                            first.make_ascii_uppercase();
+                                        }
+
+                                        err
+                                    })
+                                    .unwrap_or_default()
                             }
                         }
                     }
@@ -131,11 +200,13 @@ impl SimpleComponent for CreatePostDialog {
         let model = Self {
             window: root.clone(),
             text_view: gtk::TextView::new(),
+            tags_entry: gtk::Entry::new(),
 
-            is_content_valid: true
+            error: None
         };
 
         let text_view = &model.text_view;
+        let tags_entry = &model.tags_entry;
 
         let widgets = view_output!();
 
@@ -150,35 +221,24 @@ impl SimpleComponent for CreatePostDialog {
         match message {
             CreatePostDialogMsg::Reset => {
                 self.text_view.buffer().set_text("");
+                self.tags_entry.set_text("");
 
-                self.is_content_valid = true;
+                self.error = None;
             }
 
             CreatePostDialogMsg::VerifyContent => {
-                let content = self.text_view.buffer().text(
-                    &self.text_view.buffer().start_iter(),
-                    &self.text_view.buffer().end_iter(),
-                    true
-                );
-
-                self.is_content_valid = Content::new(content).is_some();
+                self.error = self.build_event().err();
             }
 
             CreatePostDialogMsg::Publish => {
-                let content = self.text_view.buffer().text(
-                    &self.text_view.buffer().start_iter(),
-                    &self.text_view.buffer().end_iter(),
-                    true
-                );
+                match self.build_event() {
+                    Ok(event) => {
+                        let _ = sender.output(event);
 
-                let Some(content) = Content::new(content) else {
-                    return;
-                };
-
-                if let Some(event) = PostEvent::new(content, []) {
-                    let _ = sender.output(event);
+                        self.window.close();
+                    }
 
-                    self.window.close();
+                    Err(err) => self.error = Some(err)
                 }
             }
         }
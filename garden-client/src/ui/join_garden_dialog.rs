@@ -0,0 +1,200 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-client
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use adw::prelude::*;
+use relm4::prelude::*;
+
+use garden_protocol::handler::chain::Invite;
+
+#[derive(Debug, Clone)]
+pub enum JoinGardenDialogMsg {
+    Reset,
+    VerifyInvite,
+    Join
+}
+
+pub struct JoinGardenDialog {
+    invite_row: adw::EntryRow,
+
+    /// Invite decoded from the entry row.
+    invite: Option<Invite>,
+
+    /// Result of joining the garden.
+    status: Option<Result<String, String>>
+}
+
+#[relm4::component(pub)]
+impl SimpleComponent for JoinGardenDialog {
+    type Init = ();
+    type Input = JoinGardenDialogMsg;
+    type Output = ();
+
+    view! {
+        adw::Dialog {
+            set_title: "Join garden",
+
+            set_size_request: (600, 300),
+
+            #[wrap(Some)]
+            set_child = &gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+
+                adw::HeaderBar {
+                    add_css_class: "flat"
+                },
+
+                adw::PreferencesPage {
+                    set_vexpand: true,
+                    set_valign: gtk::Align::Center,
+
+                    adw::PreferencesGroup {
+                        set_title: "Join garden",
+
+                        #[watch]
+                        set_description: model.invite.as_ref()
+                            .map(|invite| format!("Garden: {}", invite.name))
+                            .as_deref(),
+
+                        #[local_ref]
+                        invite_row -> adw::EntryRow {
+                            set_title: "Invite",
+
+                            set_show_apply_button: false,
+
+                            #[watch]
+                            set_css_classes: if model.invite.is_some() || model.invite_row.text().is_empty() {
+                                &[]
+                            } else {
+                                &["error"]
+                            },
+
+                            connect_changed => JoinGardenDialogMsg::VerifyInvite
+                        }
+                    },
+
+                    adw::PreferencesGroup {
+                        gtk::Button {
+                            set_hexpand: false,
+                            set_halign: gtk::Align::Start,
+
+                            #[watch]
+                            set_css_classes: if model.invite.is_some() {
+                                &["suggested-action", "pill"]
+                            } else {
+                                &["pill"]
+                            },
+
+                            #[watch]
+                            set_sensitive: model.invite.is_some(),
+
+                            adw::ButtonContent {
+                                set_icon_name: "network-workgroup-symbolic",
+                                set_label: "Join"
+                            },
+
+                            connect_clicked => JoinGardenDialogMsg::Join
+                        },
+
+                        gtk::Label {
+                            set_halign: gtk::Align::Start,
+
+                            set_margin_top: 8,
+
+                            set_wrap: true,
+                            set_wrap_mode: gtk::pango::WrapMode::WordChar,
+
+                            #[watch]
+                            set_css_classes: if matches!(model.status, Some(Err(_))) {
+                                &["error"]
+                            } else {
+                                &[]
+                            },
+
+                            #[watch]
+                            set_visible: model.status.is_some(),
+
+                            #[watch]
+                            set_text: match &model.status {
+                                Some(Ok(status) | Err(status)) => status,
+                                None => ""
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn init(
+        _init: Self::Init,
+        root: Self::Root,
+        _sender: ComponentSender<Self>
+    ) -> ComponentParts<Self> {
+        let model = Self {
+            invite_row: adw::EntryRow::new(),
+
+            invite: None,
+            status: None
+        };
+
+        let invite_row = &model.invite_row;
+
+        let widgets = view_output!();
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(
+        &mut self,
+        message: Self::Input,
+        _sender: ComponentSender<Self>
+    ) {
+        match message {
+            JoinGardenDialogMsg::Reset => {
+                self.invite_row.set_text("");
+
+                self.invite = None;
+                self.status = None;
+            }
+
+            JoinGardenDialogMsg::VerifyInvite => {
+                self.invite = Invite::from_base64(self.invite_row.text());
+                self.status = None;
+            }
+
+            JoinGardenDialogMsg::Join => {
+                let Some(invite) = self.invite.clone() else {
+                    return;
+                };
+
+                let name = invite.name.clone();
+
+                self.status = match crate::node::join_chain(invite) {
+                    Ok(true) => Some(Ok(format!("Garden \"{name}\" was added. Restart the application to connect to it"))),
+                    Ok(false) => Some(Ok(format!("Garden \"{name}\" is already added"))),
+
+                    Err(err) => {
+                        tracing::error!(?err, "failed to join garden");
+
+                        Some(Err(format!("Failed to join garden: {err:#}")))
+                    }
+                };
+            }
+        }
+    }
+}
@@ -16,39 +16,99 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use std::path::PathBuf;
+
+use anyhow::Context;
 use adw::prelude::*;
 use relm4::prelude::*;
 use relm4::{Worker, WorkerController};
 
 use flowerpot::crypto::hash::Hash;
-use flowerpot::crypto::sign::SigningKey;
+use flowerpot::crypto::sign::{SigningKey, VerifyingKey};
 
 use garden_protocol::PostEvent;
 use garden_protocol::index::post::PostInfo;
+use garden_protocol::index::notification::Notification;
 use garden_protocol::handler::Handler;
+use garden_protocol::handler::query::{Query, QueryError, Cursor};
+use garden_protocol::handler::outbox::DeliveryStatus;
+use garden_protocol::handler::send::{SendError, CancelHandle};
+use garden_protocol::feed::{FeedQuery, Plan};
+use garden_protocol::feed::ranking::{self, RankItem, Strategy, Top, Hot};
+use garden_protocol::archive::Archive;
 
 use crate::node::Progress as StartNodeProgress;
+use crate::moderation::{Policy, Decision, Action as ModerationAction};
 
 use crate::ui::create_post_dialog::CreatePostDialog;
+use crate::ui::new_garden_dialog::{NewGardenDialog, NewGardenDialogMsg};
+use crate::ui::join_garden_dialog::{JoinGardenDialog, JoinGardenDialogMsg};
 
 #[derive(Debug, Clone)]
 enum MainWindowHandlerWorkerInput {
-    /// Set garden protocol handler.
-    SetHandler(Handler),
+    /// Set garden protocol handlers of all the chains from the config.
+    SetHandlers(Vec<Handler>),
+
+    /// Set signing key of the logged in account used to decrypt root signers
+    /// of the chains created by this account.
+    SetAccountKey(SigningKey),
+
+    /// Request worker to update the garden index of the chain.
+    Update {
+        chain: usize
+    },
 
-    /// Request worker to update the garden index.
-    Update,
+    /// Garden index of the chain has new posts or was reset.
+    ChainUpdated {
+        chain: usize,
+        reset: bool
+    },
+
+    /// Show posts of the chain with provided index.
+    SelectChain(usize),
 
-    /// Send post to the network.
+    /// Send post to the selected chain's network.
     PublishPost {
         signing_key: SigningKey,
         event: PostEvent
     },
 
-    /// Query posts since provided message hash.
+    /// Post was sent to the chain's network with provided message hash.
+    PostSent {
+        chain: usize,
+        hash: Hash,
+        signing_key: SigningKey,
+        event: PostEvent
+    },
+
+    /// Cancel proof of work stamp minting of the post being published.
+    CancelPublish,
+
+    /// Send post with provided message hash to the network again.
+    ResendPost(Hash),
+
+    /// Query new posts since the last queried one. If `reset` is true then
+    /// all the previously queried posts are removed first.
     QueryPosts {
-        since_message: Option<Hash>
-    }
+        reset: bool
+    },
+
+    /// Show only posts matching provided custom feed plan, or all the posts
+    /// if `None`.
+    SetFeed(Option<Plan>),
+
+    /// Order posts using provided ranking strategy.
+    SetRanking(Strategy),
+
+    /// Export events of the author from the selected chain into an archive
+    /// file.
+    ExportArchive {
+        author: VerifyingKey,
+        path: PathBuf
+    },
+
+    /// Republish events from an archive file to the selected chain.
+    ImportArchive(PathBuf)
 }
 
 #[derive(Debug, Clone)]
@@ -56,12 +116,107 @@ enum MainWindowHandlerWorkerOutput {
     /// Update main window status.
     UpdateStatus(MainWindowStatus),
 
-    /// Queried post info.
-    Post(PostInfo)
+    /// Queried post info with its moderation decision.
+    Post(PostInfo, Decision),
+
+    /// Sent post which is not stored in the blockchain yet.
+    PendingPost(PostInfo),
+
+    /// Proof of work stamp minting progress of the post being published, or
+    /// `None` if minting is finished.
+    MintingProgress(Option<f64>),
+
+    /// Delivery status of the sent post with provided message hash was
+    /// changed.
+    PostDelivery(Hash, DeliveryStatus),
+
+    /// Remove all the queried posts (the index was reset).
+    ClearPosts,
+
+    /// Remove all the shown posts including pending ones (another chain was
+    /// selected).
+    ResetPosts
+}
+
+/// Post sent by the user which is not stored in the blockchain yet.
+#[derive(Debug, Clone)]
+struct SentPost {
+    /// Index of the chain where the post was sent.
+    chain: usize,
+
+    /// Info of the sent post.
+    post: PostInfo,
+
+    /// Current delivery status of the post.
+    status: DeliveryStatus
 }
 
 struct MainWindowHandlerWorker {
-    handler: Option<Handler>
+    /// Garden protocol handlers of all the chains from the config.
+    handlers: Vec<Handler>,
+
+    /// Index of the selected chain.
+    chain: usize,
+
+    policy: Policy,
+
+    /// Cursor of the last queried post.
+    cursor: Option<Cursor>,
+
+    /// Plan of the selected custom feed.
+    feed: Option<Plan>,
+
+    /// Ranking strategy of the shown posts.
+    ranking: Strategy,
+
+    /// Sent posts which are not stored in the blockchain yet.
+    sent_posts: Vec<SentPost>,
+
+    /// Cancel handle of the post being published.
+    publishing: Option<CancelHandle>,
+
+    /// Signing key of the logged in account.
+    account_key: Option<SigningKey>,
+
+    /// Validators of the chains created by the logged in account are
+    /// started.
+    validators_started: bool
+}
+
+impl MainWindowHandlerWorker {
+    /// Seal blocks of the chains created by the logged in account. Validators
+    /// are started once both the handlers and the account key are set.
+    fn start_validators(&mut self) {
+        let Some(account_key) = &self.account_key else {
+            return;
+        };
+
+        if self.validators_started || self.handlers.is_empty() {
+            return;
+        }
+
+        self.validators_started = true;
+
+        // TODO: error handling.
+
+        let config = crate::config::read()
+            .expect("failed to read config");
+
+        for (chain, handler) in config.chains.iter().zip(&self.handlers) {
+            match chain.validator(account_key) {
+                Some(Ok(validator)) => {
+                    crate::node::start_validator(handler.clone(), validator);
+                }
+
+                // The chain was created by another account.
+                Some(Err(err)) => {
+                    tracing::warn!(?err, name = chain.name, "failed to decrypt root signer of the chain");
+                }
+
+                None => ()
+            }
+        }
+    }
 }
 
 impl Worker for MainWindowHandlerWorker {
@@ -78,8 +233,12 @@ impl Worker for MainWindowHandlerWorker {
         let config = crate::config::read()
             .expect("failed to read config");
 
+        let policy = config.moderation.clone();
+
         std::thread::spawn(move || {
-            let address = config.blockchain_address.clone();
+            let chains = config.chains.clone();
+
+            let input = sender.input_sender().clone();
 
             let handle = crate::node::start(&config, |progress| {
                 let _ = sender.output(
@@ -87,13 +246,36 @@ impl Worker for MainWindowHandlerWorker {
                         MainWindowStatus::Starting(progress)
                     )
                 );
+            }, move |address| {
+                let chain = chains.iter()
+                    .position(|chain| &chain.address == address);
+
+                if let Some(chain) = chain {
+                    input.emit(MainWindowHandlerWorkerInput::Update { chain });
+                }
             });
 
-            let handler = handle.expect("failed to start flowerpot node");
+            let node = handle.expect("failed to start flowerpot node");
+
+            let handlers = config.chains.iter()
+                .map(|chain| {
+                    Handler::with_stamp_policy(
+                        chain.address.clone(),
+                        node.clone(),
+                        chain.stamps
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            let subscriptions = handlers.iter()
+                .map(Handler::subscribe)
+                .collect::<Vec<_>>();
 
-            let handler = Handler::new(address, handler);
+            sender.input(MainWindowHandlerWorkerInput::SetHandlers(handlers));
 
-            sender.input(MainWindowHandlerWorkerInput::SetHandler(handler));
+            for chain in 0..subscriptions.len() {
+                sender.input(MainWindowHandlerWorkerInput::Update { chain });
+            }
 
             let _ = sender.output(
                 MainWindowHandlerWorkerOutput::UpdateStatus(
@@ -101,15 +283,48 @@ impl Worker for MainWindowHandlerWorker {
                 )
             );
 
-            loop {
-                sender.input(MainWindowHandlerWorkerInput::Update);
+            // Listen to notifications of every chain on a separate thread.
+            for (chain, notifications) in subscriptions.into_iter().enumerate() {
+                let sender = sender.clone();
+
+                std::thread::spawn(move || {
+                    while let Ok(notification) = notifications.recv() {
+                        let mut has_posts = false;
+                        let mut reset = false;
+
+                        // Process all the pending notifications at once to
+                        // query new posts only once.
+                        for notification in std::iter::once(notification).chain(notifications.try_iter()) {
+                            match notification {
+                                Notification::Post(_) => has_posts = true,
+                                Notification::Reset => reset = true,
+
+                                _ => ()
+                            }
+                        }
 
-                std::thread::sleep(std::time::Duration::from_secs(5));
+                        if has_posts || reset {
+                            sender.input(MainWindowHandlerWorkerInput::ChainUpdated {
+                                chain,
+                                reset
+                            });
+                        }
+                    }
+                });
             }
         });
 
         Self {
-            handler: None
+            handlers: Vec::new(),
+            chain: 0,
+            policy,
+            cursor: None,
+            feed: None,
+            ranking: Strategy::default(),
+            sent_posts: Vec::new(),
+            publishing: None,
+            account_key: None,
+            validators_started: false
         }
     }
 
@@ -119,75 +334,404 @@ impl Worker for MainWindowHandlerWorker {
         sender: ComponentSender<Self>
     ) {
         match message {
-            MainWindowHandlerWorkerInput::SetHandler(handler) => {
-                self.handler = Some(handler);
+            MainWindowHandlerWorkerInput::SetHandlers(handlers) => {
+                self.handlers = handlers;
+
+                self.start_validators();
+            }
+
+            MainWindowHandlerWorkerInput::SetAccountKey(account_key) => {
+                self.account_key = Some(account_key);
+
+                self.start_validators();
+            }
+
+            MainWindowHandlerWorkerInput::Update { chain } => {
+                let Some(handler) = self.handlers.get(chain) else {
+                    return;
+                };
+
+                if let Err(err) = handler.update() {
+                    tracing::error!(?err, chain, "failed to update garden handler");
+                }
+
+                // Report changed delivery statuses of the sent posts.
+                self.sent_posts.retain_mut(|sent| {
+                    if sent.chain != chain {
+                        return true;
+                    }
+
+                    let hash = &sent.post.message_hash;
+
+                    let Some(new_status) = handler.outbox().status(hash).copied() else {
+                        return false;
+                    };
+
+                    // Included posts will be queried from the index.
+                    if let DeliveryStatus::Included { .. } = new_status {
+                        handler.forget(hash);
+
+                        return false;
+                    }
+
+                    if sent.status != new_status {
+                        sent.status = new_status;
+
+                        if chain == self.chain {
+                            let _ = sender.output(MainWindowHandlerWorkerOutput::PostDelivery(*hash, new_status));
+                        }
+                    }
+
+                    true
+                });
             }
 
-            MainWindowHandlerWorkerInput::Update => {
-                #[allow(clippy::collapsible_if)]
-                if let Some(handler) = &self.handler {
-                    if let Err(err) = handler.update() {
-                        tracing::error!(?err, "failed to update garden handler");
+            MainWindowHandlerWorkerInput::ChainUpdated { chain, reset } => {
+                if chain == self.chain {
+                    sender.input(MainWindowHandlerWorkerInput::QueryPosts { reset });
+                }
+            }
+
+            MainWindowHandlerWorkerInput::SelectChain(chain) => {
+                if chain == self.chain || chain >= self.handlers.len() {
+                    return;
+                }
+
+                self.chain = chain;
+                self.cursor = None;
+
+                let _ = sender.output(MainWindowHandlerWorkerOutput::ResetPosts);
+
+                // Show pending posts sent to the selected chain.
+                for sent in &self.sent_posts {
+                    if sent.chain != chain {
+                        continue;
+                    }
+
+                    let _ = sender.output(MainWindowHandlerWorkerOutput::PendingPost(sent.post.clone()));
+
+                    if sent.status != DeliveryStatus::Pending {
+                        let _ = sender.output(MainWindowHandlerWorkerOutput::PostDelivery(sent.post.message_hash, sent.status));
                     }
                 }
+
+                sender.input(MainWindowHandlerWorkerInput::QueryPosts {
+                    reset: true
+                });
             }
 
             MainWindowHandlerWorkerInput::PublishPost {
                 signing_key,
                 event
             } => {
-                if let Some(handler) = &self.handler {
-                    handler.send_post(&signing_key, event)
-                        .expect("failed to send post to the flowerpot network");
+                let chain = self.chain;
+
+                let Some(handler) = self.handlers.get(chain) else {
+                    return;
+                };
+
+                let task = handler.send_post(&signing_key, event.clone());
+
+                self.publishing = Some(task.cancel_handle());
+
+                let sender = sender.clone();
+
+                std::thread::spawn(move || {
+                    let result = loop {
+                        if let Some(result) = task.try_wait() {
+                            break result;
+                        }
+
+                        if task.difficulty() > 0 {
+                            let _ = sender.output(MainWindowHandlerWorkerOutput::MintingProgress(Some(task.progress())));
+                        }
+
+                        std::thread::sleep(std::time::Duration::from_millis(100));
+                    };
+
+                    let _ = sender.output(MainWindowHandlerWorkerOutput::MintingProgress(None));
+
+                    match result {
+                        Ok(hash) => sender.input(MainWindowHandlerWorkerInput::PostSent {
+                            chain,
+                            hash,
+                            signing_key,
+                            event
+                        }),
+
+                        Err(SendError::Cancelled) => (),
+
+                        Err(err) => {
+                            // TODO: error handling.
+
+                            tracing::error!(?err, "failed to send post to the flowerpot network");
+                        }
+                    }
+                });
+            }
+
+            MainWindowHandlerWorkerInput::PostSent {
+                chain,
+                hash,
+                signing_key,
+                event
+            } => {
+                let Some(handler) = self.handlers.get(chain) else {
+                    return;
+                };
+
+                self.publishing = None;
+
+                let author = signing_key.verifying_key();
+                let timestamp = time::UtcDateTime::now();
+
+                let identity = handler.index()
+                    .identity(&author, &timestamp)
+                    .clone();
+
+                let post = PostInfo {
+                    block_hash: Hash::ZERO,
+                    message_hash: hash,
+                    author,
+                    identity,
+                    signed_by_revoked_key: false,
+                    timestamp,
+                    content: event.content().clone(),
+                    tags: event.tags().to_vec().into_boxed_slice()
+                };
+
+                self.sent_posts.push(SentPost {
+                    chain,
+                    post: post.clone(),
+                    status: DeliveryStatus::Pending
+                });
+
+                if chain == self.chain {
+                    let _ = sender.output(MainWindowHandlerWorkerOutput::PendingPost(post));
                 }
             }
 
-            MainWindowHandlerWorkerInput::QueryPosts { since_message } => {
-                if let Some(handler) = &self.handler {
-                    let posts = handler.index()
-                        .posts()
-                        .skip_while(|post| {
-                            match &since_message {
-                                Some(since_message) => post.message_hash() != since_message,
-                                None => false
-                            }
-                        })
-                        .skip(if since_message.is_some() { 1 } else { 0 })
-                        .cloned()
-                        .collect::<Vec<_>>();
+            MainWindowHandlerWorkerInput::CancelPublish => {
+                if let Some(publishing) = self.publishing.take() {
+                    publishing.cancel();
+                }
+            }
 
-                    for post in posts {
-                        if let Some(post) = handler.read_post(&post) {
-                            match post {
-                                Ok(post) => {
-                                    let _ = sender.output(MainWindowHandlerWorkerOutput::Post(post));
-                                }
+            MainWindowHandlerWorkerInput::ResendPost(hash) => {
+                let sent = self.sent_posts.iter_mut()
+                    .find(|sent| sent.post.message_hash == hash);
 
-                                Err(err) => {
-                                    // TODO: error handling.
+                let Some(sent) = sent else {
+                    return;
+                };
 
-                                    tracing::error!(?err, "failed to read post info");
-                                }
-                            }
+                if let Some(handler) = self.handlers.get(sent.chain)
+                    && handler.resubmit(&hash)
+                {
+                    sent.status = DeliveryStatus::Pending;
+
+                    let _ = sender.output(MainWindowHandlerWorkerOutput::PostDelivery(hash, DeliveryStatus::Pending));
+                }
+            }
+
+            MainWindowHandlerWorkerInput::ExportArchive { author, path } => {
+                let Some(handler) = self.handlers.get(self.chain) else {
+                    return;
+                };
+
+                let export = || -> anyhow::Result<usize> {
+                    let archive = handler.export_archive(&author)
+                        .context("failed to export events")?;
+
+                    let bytes = archive.to_bytes()
+                        .context("failed to encode archive")?;
+
+                    std::fs::write(&path, bytes)
+                        .context("failed to write archive file")?;
+
+                    Ok(archive.messages().len())
+                };
+
+                match export() {
+                    Ok(events) => tracing::info!(?path, events, "exported garden archive"),
+                    Err(err) => tracing::error!(?err, ?path, "failed to export garden archive")
+                }
+            }
+
+            MainWindowHandlerWorkerInput::ImportArchive(path) => {
+                let Some(handler) = self.handlers.get(self.chain) else {
+                    return;
+                };
+
+                let import = || -> anyhow::Result<(usize, usize)> {
+                    let bytes = std::fs::read(&path)
+                        .context("failed to read archive file")?;
+
+                    let archive = Archive::from_bytes(bytes)
+                        .context("failed to decode archive")?;
+
+                    let import = handler.import_archive(&archive)
+                        .context("failed to republish archived events")?;
+
+                    Ok((import.sent.len(), import.unstamped.len()))
+                };
+
+                match import() {
+                    Ok((sent, 0)) => tracing::info!(?path, sent, "imported garden archive"),
+
+                    Ok((sent, unstamped)) => tracing::warn!(
+                        ?path,
+                        sent,
+                        unstamped,
+                        "imported garden archive without events which require proof of work stamps"
+                    ),
+
+                    Err(err) => tracing::error!(?err, ?path, "failed to import garden archive")
+                }
+            }
+
+            MainWindowHandlerWorkerInput::QueryPosts { reset } => {
+                let Some(handler) = self.handlers.get(self.chain) else {
+                    return;
+                };
+
+                // Ranked posts can change their order so all the posts must be
+                // queried and ranked again.
+                let ranked = !matches!(self.ranking, Strategy::Chronological(_));
+
+                if reset || ranked {
+                    self.cursor = None;
+
+                    let _ = sender.output(MainWindowHandlerWorkerOutput::ClearPosts);
+                }
+
+                let mut posts = Vec::new();
+
+                loop {
+                    let query = Query {
+                        after: self.cursor,
+                        ..Query::default()
+                    };
+
+                    let page = match handler.query_posts(&query) {
+                        Ok(page) => page,
+
+                        // Queried posts are not indexed anymore, start over.
+                        Err(QueryError::InvalidCursor) => {
+                            self.cursor = None;
+
+                            let _ = sender.output(MainWindowHandlerWorkerOutput::ClearPosts);
+
+                            continue;
                         }
+
+                        Err(err) => {
+                            // TODO: error handling.
+
+                            tracing::error!(?err, "failed to query posts");
+
+                            break;
+                        }
+                    };
+
+                    posts.extend(page.items.into_iter().filter(|post| {
+                        self.feed.as_ref()
+                            .is_none_or(|feed| feed.matches_post(&handler.index(), post))
+                    }));
+
+                    self.cursor = page.next;
+
+                    if !page.has_more {
+                        break;
                     }
                 }
+
+                if ranked {
+                    let index = handler.index();
+
+                    let items = posts.into_iter()
+                        .map(|post| {
+                            let item = RankItem::from_index(
+                                &index,
+                                post.message_hash,
+                                post.timestamp
+                            );
+
+                            (item, post)
+                        })
+                        .collect::<Vec<_>>();
+
+                    posts = ranking::rank(&self.ranking, items, time::UtcDateTime::now());
+
+                    // Posts are added to the top of the list.
+                    posts.reverse();
+                }
+
+                for post in posts {
+                    let index = handler.index();
+
+                    let decision = self.policy.decide(
+                        &index,
+                        &post,
+                        index.reports_of(&post.message_hash)
+                    );
+
+                    let _ = sender.output(MainWindowHandlerWorkerOutput::Post(post, decision));
+                }
+            }
+
+            MainWindowHandlerWorkerInput::SetFeed(feed) => {
+                self.feed = feed;
+
+                sender.input(MainWindowHandlerWorkerInput::QueryPosts {
+                    reset: true
+                });
+            }
+
+            MainWindowHandlerWorkerInput::SetRanking(ranking) => {
+                self.ranking = ranking;
+
+                sender.input(MainWindowHandlerWorkerInput::QueryPosts {
+                    reset: true
+                });
             }
         }
     }
 }
 
+#[derive(Debug, Clone)]
+enum MainWindowPostFactoryMsg {
+    /// Reveal blurred post content.
+    Reveal,
+
+    /// Update delivery status of the sent post.
+    SetDelivery(DeliveryStatus)
+}
+
+#[derive(Debug, Clone)]
+enum MainWindowPostFactoryOutput {
+    /// Send post with provided message hash to the network again.
+    Resend(Hash)
+}
+
 #[derive(Debug)]
 struct MainWindowPostFactory {
     post: PostInfo,
+    decision: Decision,
+    is_revealed: bool,
+
+    /// Delivery status of the sent post, or `None` if the post is stored in
+    /// the blockchain.
+    delivery: Option<DeliveryStatus>,
+
     index: DynamicIndex
 }
 
 #[relm4::factory]
 impl FactoryComponent for MainWindowPostFactory {
-    type Init = PostInfo;
-    type Input = ();
-    type Output = ();
+    type Init = (PostInfo, Decision, Option<DeliveryStatus>);
+    type Input = MainWindowPostFactoryMsg;
+    type Output = MainWindowPostFactoryOutput;
     type CommandOutput = ();
     type ParentWidget = gtk::ListBox;
 
@@ -206,7 +750,55 @@ impl FactoryComponent for MainWindowPostFactory {
                         set_hexpand: true,
                         set_halign: gtk::Align::Start,
 
-                        set_label: &format!("@{}", self.post.author.to_base64())
+                        set_label: &format!("@{}", self.post.identity.to_base64())
+                    },
+
+                    gtk::Label {
+                        set_halign: gtk::Align::Start,
+
+                        add_css_class: "warning",
+
+                        set_visible: self.post.signed_by_revoked_key,
+                        set_tooltip_text: Some("This post was signed by a key which was revoked by its owner"),
+
+                        set_label: "Revoked key"
+                    },
+
+                    gtk::Label {
+                        set_halign: gtk::Align::Start,
+
+                        add_css_class: "dim-label",
+
+                        #[watch]
+                        set_visible: self.delivery == Some(DeliveryStatus::Pending),
+
+                        set_label: "Sending…"
+                    },
+
+                    gtk::Label {
+                        set_halign: gtk::Align::Start,
+
+                        add_css_class: "error",
+
+                        #[watch]
+                        set_visible: self.delivery == Some(DeliveryStatus::Dropped),
+
+                        set_label: "Not delivered"
+                    },
+
+                    gtk::Button {
+                        set_halign: gtk::Align::Start,
+
+                        add_css_class: "flat",
+
+                        #[watch]
+                        set_visible: self.delivery == Some(DeliveryStatus::Dropped),
+
+                        set_label: "Resend",
+
+                        connect_clicked[sender, hash = self.post.message_hash] => move |_| {
+                            let _ = sender.output(MainWindowPostFactoryOutput::Resend(hash));
+                        }
                     },
 
                     gtk::Label {
@@ -217,6 +809,65 @@ impl FactoryComponent for MainWindowPostFactory {
                     },
                 },
 
+                #[name = "tags_box"]
+                gtk::FlowBox {
+                    set_margin_top: 4,
+                    set_row_spacing: 4,
+                    set_column_spacing: 4,
+
+                    set_selection_mode: gtk::SelectionMode::None,
+
+                    set_visible: !self.post.tags.is_empty()
+                },
+
+                gtk::Label {
+                    set_halign: gtk::Align::Start,
+
+                    set_margin_top: 4,
+
+                    add_css_class: "dim-label",
+                    add_css_class: "caption",
+
+                    set_wrap: true,
+
+                    set_visible: self.decision.explain().is_some(),
+                    set_label: &self.decision.explain().unwrap_or_default()
+                },
+
+                gtk::Expander {
+                    set_margin_top: 4,
+
+                    set_label: Some("Show post"),
+
+                    set_visible: self.decision.action == ModerationAction::Collapse,
+
+                    #[wrap(Some)]
+                    set_child = &gtk::Label {
+                        set_hexpand: true,
+                        set_halign: gtk::Align::Start,
+                        set_justify: gtk::Justification::Fill,
+
+                        set_wrap: true,
+                        set_wrap_mode: gtk::pango::WrapMode::WordChar,
+
+                        set_label: &self.post.content
+                    }
+                },
+
+                gtk::Button {
+                    set_margin_top: 4,
+                    set_halign: gtk::Align::Start,
+
+                    add_css_class: "flat",
+
+                    set_label: "Click to reveal",
+
+                    #[watch]
+                    set_visible: self.decision.action == ModerationAction::Blur && !self.is_revealed,
+
+                    connect_clicked => MainWindowPostFactoryMsg::Reveal
+                },
+
                 gtk::Label {
                     set_hexpand: true,
                     set_halign: gtk::Align::Start,
@@ -225,6 +876,21 @@ impl FactoryComponent for MainWindowPostFactory {
                     set_wrap: true,
                     set_wrap_mode: gtk::pango::WrapMode::WordChar,
 
+                    set_visible: matches!(
+                        self.decision.action,
+                        ModerationAction::Show | ModerationAction::Blur
+                    ),
+
+                    #[watch]
+                    set_css_classes: if self.decision.action == ModerationAction::Blur && !self.is_revealed {
+                        &["blurred"]
+                    } else {
+                        &[]
+                    },
+
+                    #[watch]
+                    set_selectable: self.decision.action != ModerationAction::Blur || self.is_revealed,
+
                     set_label: &self.post.content
                 },
             }
@@ -233,15 +899,59 @@ impl FactoryComponent for MainWindowPostFactory {
 
     #[inline]
     fn init_model(
-        init: Self::Init,
+        (post, decision, delivery): Self::Init,
         index: &DynamicIndex,
         _sender: FactorySender<Self>
     ) -> Self {
         Self {
-            post: init,
+            post,
+            decision,
+            is_revealed: false,
+            delivery,
             index: index.clone()
         }
     }
+
+    fn init_widgets(
+        &mut self,
+        _index: &DynamicIndex,
+        root: Self::Root,
+        _returned_widget: &gtk::ListBoxRow,
+        sender: FactorySender<Self>
+    ) -> Self::Widgets {
+        let widgets = view_output!();
+
+        // Render post tags as chips.
+        for tag in &self.post.tags {
+            let chip = gtk::Label::new(Some(&format!("#{}", tag.as_str())));
+
+            chip.add_css_class("tag-chip");
+
+            if tag.is_nsfw() {
+                chip.add_css_class("error");
+            } else if tag.is_spoiler() {
+                chip.add_css_class("warning");
+            }
+
+            widgets.tags_box.append(&chip);
+        }
+
+        widgets
+    }
+
+    fn update(
+        &mut self,
+        message: Self::Input,
+        _sender: FactorySender<Self>
+    ) {
+        match message {
+            MainWindowPostFactoryMsg::Reveal => self.is_revealed = true,
+
+            MainWindowPostFactoryMsg::SetDelivery(delivery) => {
+                self.delivery = Some(delivery);
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -260,21 +970,47 @@ pub enum MainWindowStatus {
 pub enum MainWindowMsg {
     SetStatus(MainWindowStatus),
     SetSigningKey(SigningKey),
-    Update,
     OpenCreatePostDialog,
+    OpenNewGardenDialog,
+    OpenJoinGardenDialog,
+    OpenExportArchiveDialog,
+    OpenImportArchiveDialog,
+    ExportArchive(PathBuf),
+    ImportArchive(PathBuf),
     PublishPost(PostEvent),
-    AddPost(PostInfo)
+    AddPost(PostInfo, Decision),
+    AddPendingPost(PostInfo),
+    SetMintingProgress(Option<f64>),
+    CancelPublish,
+    SetPostDelivery(Hash, DeliveryStatus),
+    ResendPost(Hash),
+    SelectChain(usize),
+    SelectFeed(usize),
+    SelectRanking(usize),
+    ClearPosts,
+    ResetPosts
 }
 
 pub struct MainWindow {
     status: MainWindowStatus,
     signing_key: Option<SigningKey>,
 
+    /// Proof of work stamp minting progress of the post being published.
+    minting_progress: Option<f64>,
+
+    /// Names of the garden chains from the config.
+    chains: Vec<String>,
+
+    /// Custom feeds saved in the config.
+    feeds: Vec<(String, Plan)>,
+
     handler_worker: WorkerController<MainWindowHandlerWorker>,
 
     window: adw::ApplicationWindow,
     posts_factory: FactoryVecDeque<MainWindowPostFactory>,
-    create_post_dialog: Controller<CreatePostDialog>
+    create_post_dialog: Controller<CreatePostDialog>,
+    new_garden_dialog: Controller<NewGardenDialog>,
+    join_garden_dialog: Controller<JoinGardenDialog>
 }
 
 #[relm4::component(pub)]
@@ -352,6 +1088,35 @@ impl SimpleComponent for MainWindow {
                 set_visible: model.status == MainWindowStatus::Running,
 
                 adw::HeaderBar {
+                    pack_start = &gtk::DropDown {
+                        set_model: Some(&chains_list),
+                        set_tooltip_text: Some("Garden"),
+
+                        set_visible: model.chains.len() > 1,
+
+                        connect_selected_notify[sender] => move |dropdown| {
+                            sender.input(MainWindowMsg::SelectChain(dropdown.selected() as usize));
+                        }
+                    },
+
+                    pack_start = &gtk::DropDown {
+                        set_model: Some(&feeds_list),
+
+                        set_visible: !model.feeds.is_empty(),
+
+                        connect_selected_notify[sender] => move |dropdown| {
+                            sender.input(MainWindowMsg::SelectFeed(dropdown.selected() as usize));
+                        }
+                    },
+
+                    pack_start = &gtk::DropDown::from_strings(&["Latest", "Top", "Hot"]) {
+                        set_tooltip_text: Some("Sort posts"),
+
+                        connect_selected_notify[sender] => move |dropdown| {
+                            sender.input(MainWindowMsg::SelectRanking(dropdown.selected() as usize));
+                        }
+                    },
+
                     pack_end = &gtk::Button {
                         adw::ButtonContent {
                             set_label: "Create post",
@@ -359,6 +1124,80 @@ impl SimpleComponent for MainWindow {
                         },
 
                         connect_clicked => MainWindowMsg::OpenCreatePostDialog
+                    },
+
+                    pack_end = &gtk::Button {
+                        set_tooltip_text: Some("Import activity"),
+
+                        adw::ButtonContent {
+                            set_icon_name: "document-open-symbolic"
+                        },
+
+                        connect_clicked => MainWindowMsg::OpenImportArchiveDialog
+                    },
+
+                    pack_end = &gtk::Button {
+                        set_tooltip_text: Some("Export activity"),
+
+                        #[watch]
+                        set_sensitive: model.signing_key.is_some(),
+
+                        adw::ButtonContent {
+                            set_icon_name: "document-save-symbolic"
+                        },
+
+                        connect_clicked => MainWindowMsg::OpenExportArchiveDialog
+                    },
+
+                    pack_end = &gtk::Button {
+                        set_tooltip_text: Some("Join garden"),
+
+                        adw::ButtonContent {
+                            set_icon_name: "network-workgroup-symbolic"
+                        },
+
+                        connect_clicked => MainWindowMsg::OpenJoinGardenDialog
+                    },
+
+                    pack_end = &gtk::Button {
+                        set_tooltip_text: Some("New garden"),
+
+                        adw::ButtonContent {
+                            set_icon_name: "list-add-symbolic"
+                        },
+
+                        connect_clicked => MainWindowMsg::OpenNewGardenDialog
+                    }
+                },
+
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Horizontal,
+                    set_spacing: 8,
+
+                    set_margin_top: 8,
+                    set_margin_start: 16,
+                    set_margin_end: 16,
+
+                    #[watch]
+                    set_visible: model.minting_progress.is_some(),
+
+                    gtk::ProgressBar {
+                        set_hexpand: true,
+                        set_valign: gtk::Align::Center,
+
+                        set_show_text: true,
+                        set_text: Some("Minting proof of work stamp"),
+
+                        #[watch]
+                        set_fraction: model.minting_progress.unwrap_or_default()
+                    },
+
+                    gtk::Button {
+                        add_css_class: "flat",
+
+                        set_label: "Cancel",
+
+                        connect_clicked => MainWindowMsg::CancelPublish
                     }
                 },
 
@@ -387,9 +1226,34 @@ impl SimpleComponent for MainWindow {
         root: Self::Root,
         sender: ComponentSender<Self>
     ) -> ComponentParts<Self> {
+        let config = crate::config::read()
+            .inspect_err(|err| tracing::error!(?err, "failed to read config"))
+            .unwrap_or_default();
+
+        let chains = config.chains.into_iter()
+            .map(|chain| chain.name)
+            .collect();
+
+        let feeds = config.feeds.into_iter()
+            .filter_map(|feed| {
+                match FeedQuery::parse(&feed.query) {
+                    Ok(query) => Some((feed.name, query.plan())),
+
+                    Err(err) => {
+                        tracing::error!(?err, name = %feed.name, "invalid saved feed query");
+
+                        None
+                    }
+                }
+            })
+            .collect();
+
         let model = Self {
             status: MainWindowStatus::None,
             signing_key: None,
+            minting_progress: None,
+            chains,
+            feeds,
 
             handler_worker: MainWindowHandlerWorker::builder()
                 .detach_worker(())
@@ -398,8 +1262,23 @@ impl SimpleComponent for MainWindow {
                         MainWindowHandlerWorkerOutput::UpdateStatus(status) =>
                             MainWindowMsg::SetStatus(status),
 
-                        MainWindowHandlerWorkerOutput::Post(post)
-                            => MainWindowMsg::AddPost(post)
+                        MainWindowHandlerWorkerOutput::Post(post, decision)
+                            => MainWindowMsg::AddPost(post, decision),
+
+                        MainWindowHandlerWorkerOutput::PendingPost(post)
+                            => MainWindowMsg::AddPendingPost(post),
+
+                        MainWindowHandlerWorkerOutput::MintingProgress(progress)
+                            => MainWindowMsg::SetMintingProgress(progress),
+
+                        MainWindowHandlerWorkerOutput::PostDelivery(hash, status)
+                            => MainWindowMsg::SetPostDelivery(hash, status),
+
+                        MainWindowHandlerWorkerOutput::ClearPosts
+                            => MainWindowMsg::ClearPosts,
+
+                        MainWindowHandlerWorkerOutput::ResetPosts
+                            => MainWindowMsg::ResetPosts
                     }
                 }),
 
@@ -407,24 +1286,41 @@ impl SimpleComponent for MainWindow {
 
             posts_factory: FactoryVecDeque::builder()
                 .launch_default()
-                .detach(),
+                .forward(sender.input_sender(), |message| {
+                    match message {
+                        MainWindowPostFactoryOutput::Resend(hash)
+                            => MainWindowMsg::ResendPost(hash)
+                    }
+                }),
 
             create_post_dialog: CreatePostDialog::builder()
                 .launch(())
-                .forward(sender.input_sender(), MainWindowMsg::PublishPost)
+                .forward(sender.input_sender(), MainWindowMsg::PublishPost),
+
+            new_garden_dialog: NewGardenDialog::builder()
+                .launch(())
+                .detach(),
+
+            join_garden_dialog: JoinGardenDialog::builder()
+                .launch(())
+                .detach()
         };
 
         let posts_factory = model.posts_factory.widget();
 
-        let widgets = view_output!();
+        let chains_list = gtk::StringList::new(&[]);
 
-        std::thread::spawn(move || {
-            loop {
-                sender.input(MainWindowMsg::Update);
+        for name in &model.chains {
+            chains_list.append(name);
+        }
 
-                std::thread::sleep(std::time::Duration::from_secs(5));
-            }
-        });
+        let feeds_list = gtk::StringList::new(&["All posts"]);
+
+        for (name, _) in &model.feeds {
+            feeds_list.append(name);
+        }
+
+        let widgets = view_output!();
 
         ComponentParts { model, widgets }
     }
@@ -432,7 +1328,7 @@ impl SimpleComponent for MainWindow {
     fn update(
         &mut self,
         message: Self::Input,
-        _sender: ComponentSender<Self>
+        sender: ComponentSender<Self>
     ) {
         match message {
             MainWindowMsg::SetStatus(status) => {
@@ -440,24 +1336,71 @@ impl SimpleComponent for MainWindow {
             }
 
             MainWindowMsg::SetSigningKey(signing_key) => {
+                self.new_garden_dialog.emit(NewGardenDialogMsg::SetSigningKey(signing_key.clone()));
+                self.handler_worker.emit(MainWindowHandlerWorkerInput::SetAccountKey(signing_key.clone()));
+
                 self.signing_key = Some(signing_key);
             }
 
-            MainWindowMsg::Update => {
-                let last_post = self.posts_factory.guard()
-                    .get(0)
-                    .map(|post| post.post.message_hash);
+            MainWindowMsg::OpenCreatePostDialog => {
+                self.create_post_dialog.widget()
+                    .present(Some(&self.window));
+            }
+
+            MainWindowMsg::OpenNewGardenDialog => {
+                self.new_garden_dialog.emit(NewGardenDialogMsg::Reset);
 
-                self.handler_worker.emit(MainWindowHandlerWorkerInput::QueryPosts {
-                    since_message: last_post
-                });
+                self.new_garden_dialog.widget()
+                    .present(Some(&self.window));
             }
 
-            MainWindowMsg::OpenCreatePostDialog => {
-                self.create_post_dialog.widget()
+            MainWindowMsg::OpenJoinGardenDialog => {
+                self.join_garden_dialog.emit(JoinGardenDialogMsg::Reset);
+
+                self.join_garden_dialog.widget()
                     .present(Some(&self.window));
             }
 
+            MainWindowMsg::OpenExportArchiveDialog => {
+                let dialog = gtk::FileDialog::builder()
+                    .title("Export activity")
+                    .initial_name("garden-archive.bin")
+                    .modal(true)
+                    .build();
+
+                dialog.save(Some(&self.window), None::<&gtk::gio::Cancellable>, move |file| {
+                    if let Some(path) = file.ok().and_then(|file| file.path()) {
+                        sender.input(MainWindowMsg::ExportArchive(path));
+                    }
+                });
+            }
+
+            MainWindowMsg::OpenImportArchiveDialog => {
+                let dialog = gtk::FileDialog::builder()
+                    .title("Import activity")
+                    .modal(true)
+                    .build();
+
+                dialog.open(Some(&self.window), None::<&gtk::gio::Cancellable>, move |file| {
+                    if let Some(path) = file.ok().and_then(|file| file.path()) {
+                        sender.input(MainWindowMsg::ImportArchive(path));
+                    }
+                });
+            }
+
+            MainWindowMsg::ExportArchive(path) => {
+                if let Some(signing_key) = &self.signing_key {
+                    self.handler_worker.emit(MainWindowHandlerWorkerInput::ExportArchive {
+                        author: signing_key.verifying_key(),
+                        path
+                    });
+                }
+            }
+
+            MainWindowMsg::ImportArchive(path) => {
+                self.handler_worker.emit(MainWindowHandlerWorkerInput::ImportArchive(path));
+            }
+
             MainWindowMsg::PublishPost(event) => {
                 if let Some(signing_key) = self.signing_key.clone() {
                     self.handler_worker.emit(MainWindowHandlerWorkerInput::PublishPost {
@@ -467,9 +1410,86 @@ impl SimpleComponent for MainWindow {
                 }
             }
 
-            MainWindowMsg::AddPost(post) => {
+            MainWindowMsg::AddPost(post, decision) => {
+                let mut guard = self.posts_factory.guard();
+
+                // Replace pending post when it's stored in the blockchain.
+                let pending = guard.iter().position(|pending| {
+                    pending.delivery.is_some()
+                        && pending.post.message_hash == post.message_hash
+                });
+
+                if let Some(index) = pending {
+                    guard.remove(index);
+                }
+
+                guard.push_front((post, decision, None));
+            }
+
+            MainWindowMsg::AddPendingPost(post) => {
                 self.posts_factory.guard()
-                    .push_front(post);
+                    .push_front((post, Decision::default(), Some(DeliveryStatus::Pending)));
+            }
+
+            MainWindowMsg::SetPostDelivery(hash, status) => {
+                let index = self.posts_factory.iter().position(|pending| {
+                    pending.post.message_hash == hash
+                });
+
+                if let Some(index) = index {
+                    self.posts_factory.send(index, MainWindowPostFactoryMsg::SetDelivery(status));
+                }
+            }
+
+            MainWindowMsg::SetMintingProgress(progress) => {
+                self.minting_progress = progress;
+            }
+
+            MainWindowMsg::CancelPublish => {
+                self.handler_worker.emit(MainWindowHandlerWorkerInput::CancelPublish);
+            }
+
+            MainWindowMsg::ResendPost(hash) => {
+                self.handler_worker.emit(MainWindowHandlerWorkerInput::ResendPost(hash));
+            }
+
+            MainWindowMsg::SelectChain(index) => {
+                self.handler_worker.emit(MainWindowHandlerWorkerInput::SelectChain(index));
+            }
+
+            MainWindowMsg::SelectFeed(index) => {
+                // The first item is the feed of all posts.
+                let feed = index.checked_sub(1)
+                    .and_then(|index| self.feeds.get(index))
+                    .map(|(_, plan)| plan.clone());
+
+                self.handler_worker.emit(MainWindowHandlerWorkerInput::SetFeed(feed));
+            }
+
+            MainWindowMsg::SelectRanking(index) => {
+                let ranking = match index {
+                    1 => Strategy::Top(Top::default()),
+                    2 => Strategy::Hot(Hot::default()),
+
+                    _ => Strategy::default()
+                };
+
+                self.handler_worker.emit(MainWindowHandlerWorkerInput::SetRanking(ranking));
+            }
+
+            MainWindowMsg::ClearPosts => {
+                let mut guard = self.posts_factory.guard();
+
+                // Keep pending posts since they're not stored in the index.
+                for index in (0..guard.len()).rev() {
+                    if guard.get(index).is_some_and(|post| post.delivery.is_none()) {
+                        guard.remove(index);
+                    }
+                }
+            }
+
+            MainWindowMsg::ResetPosts => {
+                self.posts_factory.guard().clear();
             }
         }
     }
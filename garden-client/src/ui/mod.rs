@@ -22,3 +22,5 @@ pub mod login_account_dialog;
 
 pub mod main_window;
 pub mod create_post_dialog;
+pub mod new_garden_dialog;
+pub mod join_garden_dialog;
@@ -0,0 +1,344 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-client
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use adw::prelude::*;
+use relm4::prelude::*;
+
+use flowerpot::crypto::sign::SigningKey;
+
+use rand_chacha::ChaCha20Rng;
+use rand_chacha::rand_core::{RngCore, SeedableRng};
+
+use time::UtcDateTime;
+
+use garden_protocol::handler::chain::Invite;
+
+#[derive(Debug, Clone)]
+pub enum NewGardenDialogMsg {
+    /// Set signing key of the logged in account used to encrypt root signers
+    /// of new gardens.
+    SetSigningKey(SigningKey),
+
+    Reset,
+    RandSigningKey,
+    VerifySigningKey,
+    Create
+}
+
+pub struct NewGardenDialog {
+    rng: ChaCha20Rng,
+
+    /// Signing key of the logged in account.
+    account_key: Option<SigningKey>,
+
+    name_row: adw::EntryRow,
+    signing_key_row: adw::EntryRow,
+    bootstrap_row: adw::EntryRow,
+
+    is_signing_key_valid: bool,
+
+    /// Invite into the created garden.
+    invite: Option<Invite>,
+
+    error: Option<String>
+}
+
+impl NewGardenDialog {
+    /// Get address of the local node if it can be used by other users to
+    /// connect to it.
+    fn default_bootstrap() -> String {
+        crate::config::read()
+            .ok()
+            .map(|config| config.node_address)
+            .filter(|address| !address.ip().is_unspecified())
+            .map(|address| address.to_string())
+            .unwrap_or_default()
+    }
+}
+
+#[relm4::component(pub)]
+impl SimpleComponent for NewGardenDialog {
+    type Init = ();
+    type Input = NewGardenDialogMsg;
+    type Output = ();
+
+    view! {
+        adw::Dialog {
+            set_title: "New garden",
+
+            set_size_request: (600, 500),
+
+            #[wrap(Some)]
+            set_child = &gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+
+                adw::HeaderBar {
+                    add_css_class: "flat"
+                },
+
+                adw::PreferencesPage {
+                    set_vexpand: true,
+
+                    adw::PreferencesGroup {
+                        set_title: "New garden",
+                        set_description: Some("Create a new blockchain for a private garden"),
+
+                        #[local_ref]
+                        name_row -> adw::EntryRow {
+                            set_title: "Name",
+                            set_text: "New garden",
+
+                            set_show_apply_button: false
+                        },
+
+                        #[local_ref]
+                        signing_key_row -> adw::EntryRow {
+                            set_title: "Root signer",
+
+                            set_show_apply_button: false,
+
+                            #[watch]
+                            set_css_classes: if model.is_signing_key_valid {
+                                &[]
+                            } else {
+                                &["error"]
+                            },
+
+                            add_suffix = &gtk::Button {
+                                set_vexpand: false,
+                                set_valign: gtk::Align::Center,
+
+                                add_css_class: "flat",
+
+                                adw::ButtonContent {
+                                    set_icon_name: "dice3-symbolic"
+                                },
+
+                                connect_clicked => NewGardenDialogMsg::RandSigningKey
+                            },
+
+                            connect_changed => NewGardenDialogMsg::VerifySigningKey
+                        },
+
+                        #[local_ref]
+                        bootstrap_row -> adw::EntryRow {
+                            set_title: "Public addresses of the node",
+
+                            set_show_apply_button: false
+                        }
+                    },
+
+                    adw::PreferencesGroup {
+                        gtk::Button {
+                            set_hexpand: false,
+                            set_halign: gtk::Align::Start,
+
+                            #[watch]
+                            set_css_classes: if model.is_signing_key_valid && model.invite.is_none() {
+                                &["suggested-action", "pill"]
+                            } else {
+                                &["pill"]
+                            },
+
+                            #[watch]
+                            set_sensitive: model.is_signing_key_valid && model.invite.is_none(),
+
+                            adw::ButtonContent {
+                                set_icon_name: "list-add-symbolic",
+                                set_label: "Create"
+                            },
+
+                            connect_clicked => NewGardenDialogMsg::Create
+                        },
+
+                        gtk::Label {
+                            set_halign: gtk::Align::Start,
+
+                            set_margin_top: 8,
+
+                            set_wrap: true,
+                            set_wrap_mode: gtk::pango::WrapMode::WordChar,
+
+                            add_css_class: "error",
+
+                            #[watch]
+                            set_visible: model.error.is_some(),
+
+                            #[watch]
+                            set_text: model.error.as_deref().unwrap_or_default()
+                        }
+                    },
+
+                    adw::PreferencesGroup {
+                        set_title: "Invite",
+                        set_description: Some("Share this invite with other users so they could join the garden. Restart the application to connect to the new garden"),
+
+                        #[watch]
+                        set_visible: model.invite.is_some(),
+
+                        adw::ActionRow {
+                            set_title: "Address",
+
+                            add_css_class: "property",
+
+                            #[watch]
+                            set_subtitle: &model.invite.as_ref()
+                                .map(|invite| invite.address.to_base64())
+                                .unwrap_or_default(),
+
+                            set_subtitle_selectable: true
+                        },
+
+                        adw::ActionRow {
+                            set_title: "Bootstrap nodes",
+
+                            add_css_class: "property",
+
+                            #[watch]
+                            set_subtitle: &model.invite.as_ref()
+                                .map(|invite| invite.bootstrap.join(", "))
+                                .unwrap_or_default(),
+
+                            set_subtitle_selectable: true
+                        },
+
+                        adw::ActionRow {
+                            set_title: "Invite",
+
+                            add_css_class: "property",
+
+                            #[watch]
+                            set_subtitle: &model.invite.as_ref()
+                                .map(Invite::to_base64)
+                                .unwrap_or_default(),
+
+                            set_subtitle_selectable: true
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn init(
+        _init: Self::Init,
+        root: Self::Root,
+        _sender: ComponentSender<Self>
+    ) -> ComponentParts<Self> {
+        let mut rng = ChaCha20Rng::from_entropy();
+
+        let rng = ChaCha20Rng::seed_from_u64(
+            rng.next_u64() ^
+            UtcDateTime::now().unix_timestamp() as u64
+        );
+
+        let model = Self {
+            rng,
+            account_key: None,
+
+            name_row: adw::EntryRow::new(),
+            signing_key_row: adw::EntryRow::new(),
+            bootstrap_row: adw::EntryRow::new(),
+
+            is_signing_key_valid: false,
+            invite: None,
+            error: None
+        };
+
+        let name_row = &model.name_row;
+        let signing_key_row = &model.signing_key_row;
+        let bootstrap_row = &model.bootstrap_row;
+
+        let widgets = view_output!();
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(
+        &mut self,
+        message: Self::Input,
+        _sender: ComponentSender<Self>
+    ) {
+        match message {
+            NewGardenDialogMsg::SetSigningKey(signing_key) => {
+                self.account_key = Some(signing_key);
+            }
+
+            NewGardenDialogMsg::Reset => {
+                // Root signer is stored in the config, so the account's key
+                // is never used as the default one.
+                let signing_key = SigningKey::random(&mut self.rng);
+
+                self.signing_key_row.set_text(signing_key.to_base64().as_str());
+
+                self.name_row.set_text("New garden");
+                self.bootstrap_row.set_text(&Self::default_bootstrap());
+
+                self.is_signing_key_valid = true;
+                self.invite = None;
+                self.error = None;
+            }
+
+            NewGardenDialogMsg::RandSigningKey => {
+                let signing_key = SigningKey::random(&mut self.rng);
+
+                self.signing_key_row.set_text(signing_key.to_base64().as_str());
+            }
+
+            NewGardenDialogMsg::VerifySigningKey => {
+                let signing_key = self.signing_key_row.text();
+
+                self.is_signing_key_valid = SigningKey::from_base64(signing_key).is_some();
+            }
+
+            NewGardenDialogMsg::Create => {
+                let name = self.name_row.text();
+                let signing_key = self.signing_key_row.text();
+                let bootstrap = self.bootstrap_row.text();
+
+                let Some(signing_key) = SigningKey::from_base64(signing_key) else {
+                    return;
+                };
+
+                let Some(account_key) = &self.account_key else {
+                    self.error = Some(String::from("Log in to an account to create a garden"));
+
+                    return;
+                };
+
+                let bootstrap = bootstrap.split(|c: char| c.is_whitespace() || c == ',')
+                    .filter(|address| !address.is_empty())
+                    .map(String::from)
+                    .collect();
+
+                match crate::node::create_chain(name.trim(), &signing_key, account_key, bootstrap) {
+                    Ok(invite) => {
+                        self.invite = Some(invite);
+                        self.error = None;
+                    }
+
+                    Err(err) => {
+                        tracing::error!(?err, "failed to create new garden");
+
+                        self.error = Some(format!("Failed to create garden: {err:#}"));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,347 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::alloc::{GlobalAlloc, System, Layout};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+use flowerpot::address::Address;
+use flowerpot::crypto::hash::Hash;
+use flowerpot::crypto::sign::SigningKey;
+use flowerpot::storage::Storage;
+
+use garden_protocol::*;
+use garden_protocol::index::Index;
+use garden_protocol::testing::*;
+
+/// Global allocator which counts allocated bytes to measure memory use of
+/// the index.
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Read amount of chain items from the environment variable.
+fn size(name: &str, default: usize) -> usize {
+    std::env::var(name).ok()
+        .and_then(|size| size.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Amount of messages stored in a single block.
+const BLOCK_SIZE: usize = 1000;
+
+/// Amount of authors of the generated events.
+const AUTHORS: usize = 64;
+
+struct Chain {
+    storage: MemoryStorage,
+    index: Index,
+
+    /// Message hashes of all the posts.
+    posts: Vec<Hash>,
+
+    /// Message hash of the root post of the deep thread.
+    thread: Hash
+}
+
+fn authors() -> Vec<SigningKey> {
+    (0..AUTHORS)
+        .map(|i| signing_key(i as u8 + 1))
+        .collect()
+}
+
+fn post(i: usize) -> PostEvent {
+    PostEvent::new(
+        Content::new(format!("synthetic post #{i}")).unwrap(),
+        [Tag::new(["sport", "news", "music"][i % 3]).unwrap()]
+    ).unwrap()
+}
+
+fn seal(chain: &mut ChainBuilder) {
+    if chain.pending() >= BLOCK_SIZE {
+        chain.block().unwrap();
+    }
+}
+
+/// Chain with `GARDEN_BENCH_POSTS` posts (100k by default).
+fn posts_chain() -> &'static Chain {
+    static CHAIN: OnceLock<Chain> = OnceLock::new();
+
+    CHAIN.get_or_init(|| {
+        let authors = authors();
+
+        let mut chain = ChainBuilder::new(signing_key(0));
+        let mut posts = Vec::new();
+
+        for i in 0..size("GARDEN_BENCH_POSTS", 100_000) {
+            posts.push(chain.event(&authors[i % AUTHORS], post(i)).unwrap());
+
+            seal(&mut chain);
+        }
+
+        chain.block().unwrap();
+
+        let storage = chain.storage();
+
+        let mut index = Index::default();
+
+        index.update(&storage).unwrap();
+
+        Chain {
+            storage,
+            index,
+            thread: posts[0],
+            posts
+        }
+    })
+}
+
+/// Chain with `GARDEN_BENCH_THREAD_POSTS` posts (1k by default) with
+/// `GARDEN_BENCH_COMMENTS` comments (1M by default) spread over them, and a
+/// thread of `GARDEN_BENCH_THREAD_DEPTH` nested comments (10k by default).
+fn comments_chain() -> &'static Chain {
+    static CHAIN: OnceLock<Chain> = OnceLock::new();
+
+    CHAIN.get_or_init(|| {
+        let authors = authors();
+
+        let mut chain = ChainBuilder::new(signing_key(0));
+        let mut posts = Vec::new();
+
+        for i in 0..size("GARDEN_BENCH_THREAD_POSTS", 1_000) {
+            posts.push(chain.event(&authors[i % AUTHORS], post(i)).unwrap());
+
+            seal(&mut chain);
+        }
+
+        for i in 0..size("GARDEN_BENCH_COMMENTS", 1_000_000) {
+            let comment = CommentEvent::new(
+                posts[i % posts.len()],
+                Content::new(format!("synthetic comment #{i}")).unwrap()
+            );
+
+            chain.event(&authors[i % AUTHORS], comment).unwrap();
+
+            seal(&mut chain);
+        }
+
+        let thread = chain.event(&authors[0], post(posts.len())).unwrap();
+
+        let mut parent = thread;
+
+        for i in 0..size("GARDEN_BENCH_THREAD_DEPTH", 10_000) {
+            let comment = CommentEvent::new(
+                parent,
+                Content::new(format!("synthetic reply #{i}")).unwrap()
+            );
+
+            parent = chain.event(&authors[i % AUTHORS], comment).unwrap();
+
+            seal(&mut chain);
+        }
+
+        chain.block().unwrap();
+
+        let storage = chain.storage();
+
+        let mut index = Index::default();
+
+        index.update(&storage).unwrap();
+
+        Chain {
+            storage,
+            index,
+            posts,
+            thread
+        }
+    })
+}
+
+fn index_update(c: &mut Criterion) {
+    let chain = posts_chain();
+
+    let mut group = c.benchmark_group("index");
+
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(chain.posts.len() as u64));
+
+    group.bench_function("update", |b| {
+        b.iter(|| {
+            let mut index = Index::default();
+
+            index.update(&chain.storage).unwrap();
+
+            index
+        });
+    });
+
+    group.bench_function("update_parallel", |b| {
+        b.iter(|| {
+            let mut index = Index::default();
+
+            index.update_parallel(&chain.storage).unwrap();
+
+            index
+        });
+    });
+
+    group.finish();
+}
+
+fn index_memory(_c: &mut Criterion) {
+    for (name, chain) in [("posts", posts_chain()), ("comments", comments_chain())] {
+        let before = ALLOCATED.load(Ordering::Relaxed);
+
+        let mut index = Index::default();
+
+        index.update(&chain.storage).unwrap();
+
+        let used = ALLOCATED.load(Ordering::Relaxed).saturating_sub(before);
+
+        let posts = index.posts().len();
+        let comments = index.comments().len();
+
+        println!(
+            "index memory use ({name} chain): {used} bytes, {posts} posts, {comments} comments, {:.1} bytes per event",
+            used as f64 / (posts + comments).max(1) as f64
+        );
+
+        drop(black_box(index));
+    }
+}
+
+fn post_comments(c: &mut Criterion) {
+    let chain = comments_chain();
+
+    let posts = chain.index.posts()
+        .take(100)
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("post");
+
+    group.throughput(Throughput::Elements(chain.index.comments().len() as u64));
+
+    group.bench_function("comments", |b| {
+        let mut i = 0;
+
+        b.iter(|| {
+            i = (i + 1) % posts.len();
+
+            posts[i].comments(&chain.index).count()
+        });
+    });
+
+    group.bench_function("read", |b| {
+        let mut i = 0;
+
+        b.iter(|| {
+            i = (i + 1) % posts.len();
+
+            posts[i].read(&chain.index, &chain.storage).unwrap()
+        });
+    });
+
+    group.finish();
+}
+
+fn deep_thread(c: &mut Criterion) {
+    let chain = comments_chain();
+
+    let mut group = c.benchmark_group("thread");
+
+    group.sample_size(10);
+
+    group.bench_function("walk", |b| {
+        b.iter(|| {
+            let mut depth = 0;
+            let mut parent = chain.thread;
+
+            while let Some(comment) = chain.index.comments_of(&parent).next() {
+                parent = *comment.message_hash();
+                depth += 1;
+            }
+
+            depth
+        });
+    });
+
+    group.finish();
+}
+
+fn filter(c: &mut Criterion) {
+    let chain = posts_chain();
+
+    let validator = signing_key(0);
+    let address = Address::new(validator.verifying_key(), 0);
+
+    let messages = chain.storage.read_block(&chain.storage.root_block().unwrap().unwrap())
+        .unwrap()
+        .unwrap()
+        .inline_messages()
+        .to_vec();
+
+    let authors = messages.iter()
+        .map(|message| message.verify().unwrap().1)
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("messages");
+
+    group.throughput(Throughput::Elements(messages.len() as u64));
+
+    group.bench_function("filter", |b| {
+        b.iter(|| {
+            messages.iter()
+                .zip(&authors)
+                .filter(|(message, author)| messages_filter(&address, message, author))
+                .count()
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    index_update,
+    index_memory,
+    post_comments,
+    deep_thread,
+    filter
+);
+
+criterion_main!(benches);
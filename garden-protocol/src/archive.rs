@@ -0,0 +1,482 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+//! Portable archives of an account's activity. Archive contains raw signed
+//! flowerpot messages of the account's events and a JSON manifest describing
+//! them, so it can be verified offline and republished to another chain.
+
+use flowerpot::crypto::hash::Hash;
+use flowerpot::crypto::sign::VerifyingKey;
+use flowerpot::message::Message;
+use flowerpot::storage::{Storage, StorageError};
+
+use time::UtcDateTime;
+
+use crate::{Events, DelegationAction};
+use crate::index::Index;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ArchiveError {
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+
+    #[error("failed to encode or decode archive manifest: {0}")]
+    Manifest(#[from] serde_json::Error),
+
+    #[error("provided bytes are not a valid garden archive")]
+    InvalidFormat,
+
+    #[error("unsupported garden archive version: {0}")]
+    UnsupportedVersion(u8),
+
+    #[error("archive message #{0} doesn't match the manifest")]
+    ManifestMismatch(usize),
+
+    #[error("archive message with hash '{}' is not signed by the archive author's keys", .0.to_base64())]
+    InvalidSignature(Hash),
+
+    #[error("archive key '{}' doesn't belong to the archive author's identity", .0.to_base64())]
+    UnknownKey(VerifyingKey),
+
+    #[error("no storage for blockchain with address '{0}'")]
+    NoStorage(String)
+}
+
+/// Type of an archived event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveEventKind {
+    Post,
+    Comment,
+    Reaction,
+    Delegation,
+    Rotation
+}
+
+impl ArchiveEventKind {
+    /// Get kind of provided event, or `None` if such events are not archived.
+    pub const fn from_event(event: &Events) -> Option<Self> {
+        match event {
+            Events::Post(_)       => Some(Self::Post),
+            Events::Comment(_)    => Some(Self::Comment),
+            Events::Reaction(_)   => Some(Self::Reaction),
+            Events::Delegation(_) => Some(Self::Delegation),
+            Events::Rotation(_)   => Some(Self::Rotation),
+
+            _ => None
+        }
+    }
+}
+
+/// Archived event description.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ArchiveEntry {
+    /// Hash of the flowerpot message with the event.
+    #[serde(with = "crate::serde_utils::hash")]
+    pub message_hash: Hash,
+
+    /// Hash of the block of the original blockchain where the event is
+    /// stored.
+    #[serde(with = "crate::serde_utils::hash")]
+    pub block_hash: Hash,
+
+    /// Timestamp of the block of the original blockchain where the event is
+    /// stored.
+    #[serde(with = "crate::serde_utils::timestamp")]
+    pub timestamp: UtcDateTime,
+
+    /// Type of the event.
+    pub kind: ArchiveEventKind,
+
+    /// Verifying key which signed the message. One of the archive author's
+    /// identity keys.
+    #[serde(with = "crate::serde_utils::verifying_key")]
+    pub signer: VerifyingKey
+}
+
+/// JSON manifest of an archive.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ArchiveManifest {
+    /// Identity key the archive was exported for.
+    #[serde(with = "crate::serde_utils::verifying_key")]
+    pub author: VerifyingKey,
+
+    /// All the keys of the author's identity at the export time: rotated
+    /// identity keys and delegated device keys. Archived messages are signed
+    /// by these keys.
+    #[serde(with = "crate::serde_utils::verifying_keys")]
+    pub keys: Vec<VerifyingKey>,
+
+    /// Base64 encoded address of the blockchain the events were exported
+    /// from, if known.
+    pub chain: Option<String>,
+
+    /// Timestamp when the archive was made.
+    #[serde(with = "crate::serde_utils::timestamp")]
+    pub exported_at: UtcDateTime,
+
+    /// Archived events in the original blockchain order, one for each
+    /// archived message.
+    pub entries: Vec<ArchiveEntry>
+}
+
+/// Result of republishing an archive to a blockchain.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ArchiveImport {
+    /// Hashes of the sent messages.
+    pub sent: Vec<Hash>,
+
+    /// Hashes of the skipped messages which don't have proof of work stamps
+    /// required by the blockchain's stamp policy. Stamps are bound to the
+    /// blockchain address, so stamps minted for the original blockchain are
+    /// not valid on another one, and archived messages can't be stamped again
+    /// without changing their signatures.
+    pub unstamped: Vec<Hash>
+}
+
+/// Archive of all the posts, comments, reactions and identity events
+/// (device keys delegations and key rotations) signed by the keys of a single
+/// identity.
+///
+/// Binary representation starts with the `Archive::MAGIC` bytes and a version
+/// byte, followed by the manifest and all the raw messages, each prefixed by
+/// its u32 little-endian length.
+#[derive(Debug, Clone)]
+pub struct Archive {
+    manifest: ArchiveManifest,
+    messages: Vec<Message>
+}
+
+impl Archive {
+    /// Bytes every encoded archive starts with.
+    pub const MAGIC: &[u8] = b"garden archive";
+
+    /// Current archive version.
+    pub const VERSION: u8 = 0;
+
+    /// Export all the archived events signed by the identity keys of provided
+    /// author (see `Index::identity_keys`) from provided flowerpot blockchain
+    /// storage.
+    ///
+    /// Storage is read directly and the index is used only to resolve the
+    /// author's identity keys, so events rejected by the index (e.g. replaced
+    /// reactions or events without required proof of work stamps) are
+    /// archived too. Messages with malformed signatures are skipped.
+    pub fn export(
+        storage: &dyn Storage,
+        index: &Index,
+        author: &VerifyingKey
+    ) -> Result<Self, ArchiveError> {
+        let keys = index.identity_keys(author)
+            .into_iter()
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let mut entries = Vec::new();
+        let mut messages = Vec::new();
+
+        let mut last_block = Hash::ZERO;
+
+        while let Some(hash) = storage.next_block(&last_block)? {
+            let Some(block) = storage.read_block(&hash)? else {
+                break;
+            };
+
+            for message in block.inline_messages() {
+                let Ok(event) = Events::from_bytes(message.data()) else {
+                    continue;
+                };
+
+                let Some(kind) = ArchiveEventKind::from_event(&event) else {
+                    continue;
+                };
+
+                let Ok((true, signer)) = message.verify() else {
+                    continue;
+                };
+
+                if !keys.contains(&signer) {
+                    continue;
+                }
+
+                entries.push(ArchiveEntry {
+                    message_hash: *message.hash(),
+                    block_hash: *block.hash(),
+                    timestamp: *block.timestamp(),
+                    kind,
+                    signer
+                });
+
+                messages.push(message.clone());
+            }
+
+            last_block = hash;
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            author = author.to_base64(),
+            keys = keys.len(),
+            events = entries.len(),
+            "export garden archive"
+        );
+
+        Ok(Self {
+            manifest: ArchiveManifest {
+                author: author.clone(),
+                keys,
+                chain: None,
+                exported_at: UtcDateTime::now(),
+                entries
+            },
+            messages
+        })
+    }
+
+    #[inline]
+    pub const fn manifest(&self) -> &ArchiveManifest {
+        &self.manifest
+    }
+
+    /// Set base64 encoded address of the blockchain the events were exported
+    /// from.
+    #[inline]
+    pub fn with_chain(mut self, chain: impl ToString) -> Self {
+        self.manifest.chain = Some(chain.to_string());
+
+        self
+    }
+
+    /// Get archived messages in the original blockchain order.
+    #[inline]
+    pub fn messages(&self) -> &[Message] {
+        &self.messages
+    }
+
+    /// Rebuild keys of the archive author's identity using only the archived
+    /// messages: keys rotated from or to the author's key, and device keys
+    /// authorized by one of these keys and accepted by the device.
+    ///
+    /// Messages with invalid signatures are ignored.
+    pub fn identity_keys(&self) -> Vec<VerifyingKey> {
+        let events = self.messages.iter()
+            .filter_map(|message| {
+                let (true, signer) = message.verify().ok()? else {
+                    return None;
+                };
+
+                Some((signer, Events::from_bytes(message.data()).ok()?))
+            })
+            .collect::<Vec<_>>();
+
+        let mut keys = vec![self.manifest.author.clone()];
+
+        // Repeat until no new keys are found because identity events can be
+        // archived in any order relative to each other.
+        loop {
+            let mut new_keys = Vec::new();
+
+            for (signer, event) in &events {
+                match event {
+                    // Rotation is signed by both old and new keys.
+                    Events::Rotation(rotation) => {
+                        if !rotation.verify(signer).unwrap_or(false) {
+                            continue;
+                        }
+
+                        if keys.contains(signer) {
+                            new_keys.push(rotation.new_key().clone());
+                        }
+
+                        else if keys.contains(rotation.new_key()) {
+                            new_keys.push(signer.clone());
+                        }
+                    }
+
+                    // Device must be authorized by an identity key and accept
+                    // the authorization.
+                    Events::Delegation(delegation) => {
+                        if delegation.action() != &DelegationAction::Authorize
+                            || !keys.contains(signer)
+                        {
+                            continue;
+                        }
+
+                        let accepted = events.iter().any(|(device, event)| {
+                            let Events::Delegation(accept) = event else {
+                                return false;
+                            };
+
+                            device == delegation.key()
+                                && accept.action() == &DelegationAction::Accept
+                                && accept.key() == signer
+                        });
+
+                        if accepted {
+                            new_keys.push(delegation.key().clone());
+                        }
+                    }
+
+                    _ => ()
+                }
+            }
+
+            new_keys.retain(|key| !keys.contains(key));
+
+            if new_keys.is_empty() {
+                break;
+            }
+
+            for key in new_keys {
+                if !keys.contains(&key) {
+                    keys.push(key);
+                }
+            }
+        }
+
+        keys
+    }
+
+    /// Check that archived messages match the manifest and are signed by the
+    /// archive author's identity keys. Doesn't require access to any
+    /// blockchain.
+    ///
+    /// Manifest keys are not trusted: the author's identity keys are rebuilt
+    /// from the archived identity events (see `Archive::identity_keys`), and
+    /// every manifest key and message signer must be one of them.
+    pub fn verify(&self) -> Result<(), ArchiveError> {
+        if self.messages.len() != self.manifest.entries.len() {
+            return Err(ArchiveError::ManifestMismatch(self.messages.len()));
+        }
+
+        let identity_keys = self.identity_keys();
+
+        for key in &self.manifest.keys {
+            if !identity_keys.contains(key) {
+                return Err(ArchiveError::UnknownKey(key.clone()));
+            }
+        }
+
+        let messages = self.messages.iter()
+            .zip(&self.manifest.entries)
+            .enumerate();
+
+        for (i, (message, entry)) in messages {
+            if message.hash() != &entry.message_hash {
+                return Err(ArchiveError::ManifestMismatch(i));
+            }
+
+            let kind = Events::from_bytes(message.data())
+                .ok()
+                .and_then(|event| ArchiveEventKind::from_event(&event));
+
+            if kind != Some(entry.kind) {
+                return Err(ArchiveError::ManifestMismatch(i));
+            }
+
+            if !self.manifest.keys.contains(&entry.signer) {
+                return Err(ArchiveError::InvalidSignature(entry.message_hash));
+            }
+
+            let is_signed = message.verify()
+                .is_ok_and(|(is_valid, signer)| is_valid && signer == entry.signer);
+
+            if !is_signed {
+                return Err(ArchiveError::InvalidSignature(entry.message_hash));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Encode archive into bytes which can be stored in a file.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ArchiveError> {
+        fn push_data(bytes: &mut Vec<u8>, data: &[u8]) {
+            bytes.extend((data.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(data);
+        }
+
+        let manifest = serde_json::to_vec_pretty(&self.manifest)?;
+
+        let mut bytes = Vec::with_capacity(Self::MAGIC.len() + manifest.len() + 5);
+
+        bytes.extend_from_slice(Self::MAGIC);
+        bytes.push(Self::VERSION);
+
+        push_data(&mut bytes, &manifest);
+
+        for message in &self.messages {
+            push_data(&mut bytes, &message.to_bytes());
+        }
+
+        Ok(bytes)
+    }
+
+    /// Decode archive from bytes produced by the `to_bytes` method.
+    ///
+    /// Decoded archive is not verified, use the `verify` method to check its
+    /// messages.
+    pub fn from_bytes(bytes: impl AsRef<[u8]>) -> Result<Self, ArchiveError> {
+        fn read_data<'a>(bytes: &mut &'a [u8]) -> Option<&'a [u8]> {
+            let (len, rest) = bytes.split_at_checked(4)?;
+            let len = u32::from_le_bytes(len.try_into().ok()?) as usize;
+
+            let (data, rest) = rest.split_at_checked(len)?;
+
+            *bytes = rest;
+
+            Some(data)
+        }
+
+        let Some(bytes) = bytes.as_ref().strip_prefix(Self::MAGIC) else {
+            return Err(ArchiveError::InvalidFormat);
+        };
+
+        let Some((&version, mut bytes)) = bytes.split_first() else {
+            return Err(ArchiveError::InvalidFormat);
+        };
+
+        if version != Self::VERSION {
+            return Err(ArchiveError::UnsupportedVersion(version));
+        }
+
+        let Some(manifest) = read_data(&mut bytes) else {
+            return Err(ArchiveError::InvalidFormat);
+        };
+
+        let manifest = serde_json::from_slice::<ArchiveManifest>(manifest)?;
+
+        let mut messages = Vec::with_capacity(manifest.entries.len());
+
+        while !bytes.is_empty() {
+            let Some(message) = read_data(&mut bytes) else {
+                return Err(ArchiveError::InvalidFormat);
+            };
+
+            let Ok(message) = Message::from_bytes(message) else {
+                return Err(ArchiveError::InvalidFormat);
+            };
+
+            messages.push(message);
+        }
+
+        Ok(Self {
+            manifest,
+            messages
+        })
+    }
+}
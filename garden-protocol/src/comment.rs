@@ -16,30 +16,39 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use alloc::boxed::Box;
+use alloc::string::{String, FromUtf8Error};
+use alloc::vec::Vec;
+
 use flowerpot::crypto::hash::Hash;
 
-use super::post::Content;
+use super::post::{Content, ValidationError};
 use super::Event;
 
 #[derive(Debug, thiserror::Error)]
 pub enum CommentEventError {
     #[error("invalid unicode sequence: {0}")]
-    InvalidUnicode(#[from] std::string::FromUtf8Error),
+    InvalidUnicode(#[from] FromUtf8Error),
 
     #[error("provided comment event bytes slice is too short")]
     SliceTooShort,
 
-    #[error("invalid content")]
-    InvalidContent
+    #[error(transparent)]
+    Validation(#[from] ValidationError)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CommentEvent {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
     ref_message_hash: Hash,
     content: Content
 }
 
 impl CommentEvent {
+    /// Max possible size of the comment event's binary representation.
+    pub const MAX_SIZE: usize = Hash::SIZE + Content::MAX_LENGTH;
+
     /// Create new comment event. Reference address is a flowerpot message hash
     /// of another comment or a post.
     pub fn new(
@@ -86,9 +95,7 @@ impl Event for CommentEvent {
 
         let content = String::from_utf8(event[Hash::SIZE..].to_vec())?;
 
-        let Some(content) = Content::new(content) else {
-            return Err(CommentEventError::InvalidContent);
-        };
+        let content = Content::new(content)?;
 
         Ok(Self {
             ref_message_hash: Hash::from(ref_message_hash),
@@ -0,0 +1,178 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use flowerpot::crypto::sign::VerifyingKey;
+
+use super::Event;
+
+/// Action performed by the delegation event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum DelegationAction {
+    /// Master key authorizes a device key to act on its behalf. Must be
+    /// signed by the master key.
+    Authorize,
+
+    /// Device key accepts the authorization from a master key. Must be signed
+    /// by the device key.
+    ///
+    /// Without this action anybody could attribute someone else's posts to
+    /// their own identity.
+    Accept,
+
+    /// Master key revokes previously authorized device key. Must be signed by
+    /// the master key.
+    Revoke
+}
+
+impl DelegationAction {
+    pub const AUTHORIZE: u8 = 0;
+    pub const ACCEPT: u8    = 1;
+    pub const REVOKE: u8    = 2;
+
+    pub const fn to_byte(&self) -> u8 {
+        match self {
+            Self::Authorize => Self::AUTHORIZE,
+            Self::Accept    => Self::ACCEPT,
+            Self::Revoke    => Self::REVOKE
+        }
+    }
+
+    pub const fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            Self::AUTHORIZE => Some(Self::Authorize),
+            Self::ACCEPT    => Some(Self::Accept),
+            Self::REVOKE    => Some(Self::Revoke),
+
+            _ => None
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DelegationEventError {
+    #[error("provided delegation event bytes slice is too short")]
+    SliceTooShort,
+
+    #[error("unknown delegation action: {0}")]
+    UnknownAction(u8),
+
+    #[error("invalid verifying key")]
+    InvalidVerifyingKey
+}
+
+/// Delegation event allows a master identity key to use separate device keys
+/// for signing garden events. Delegation becomes valid when the master key
+/// authorizes the device key and the device key accepts this authorization.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DelegationEvent {
+    action: DelegationAction,
+
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::verifying_key"))]
+    key: VerifyingKey
+}
+
+impl DelegationEvent {
+    /// Create new event which authorizes provided device key to sign events
+    /// on behalf of the master key (event's signer).
+    #[inline]
+    pub const fn authorize(device_key: VerifyingKey) -> Self {
+        Self {
+            action: DelegationAction::Authorize,
+            key: device_key
+        }
+    }
+
+    /// Create new event which accepts authorization from provided master key
+    /// for the device key (event's signer).
+    #[inline]
+    pub const fn accept(master_key: VerifyingKey) -> Self {
+        Self {
+            action: DelegationAction::Accept,
+            key: master_key
+        }
+    }
+
+    /// Create new event which revokes provided device key of the master key
+    /// (event's signer).
+    #[inline]
+    pub const fn revoke(device_key: VerifyingKey) -> Self {
+        Self {
+            action: DelegationAction::Revoke,
+            key: device_key
+        }
+    }
+
+    #[inline(always)]
+    pub const fn action(&self) -> &DelegationAction {
+        &self.action
+    }
+
+    /// Verifying key of the other delegation side: the device key for
+    /// `Authorize` and `Revoke` actions, and the master key for `Accept`.
+    #[inline(always)]
+    pub const fn key(&self) -> &VerifyingKey {
+        &self.key
+    }
+}
+
+impl Event for DelegationEvent {
+    type Error = DelegationEventError;
+
+    fn to_bytes(&self) -> Box<[u8]> {
+        let mut buf = Vec::with_capacity(1 + VerifyingKey::SIZE);
+
+        buf.push(self.action.to_byte());
+        buf.extend(self.key.to_bytes());
+
+        buf.into_boxed_slice()
+    }
+
+    fn from_bytes(event: &[u8]) -> Result<Self, Self::Error> where Self: Sized {
+        if event.len() < 1 + VerifyingKey::SIZE {
+            return Err(DelegationEventError::SliceTooShort);
+        }
+
+        let Some(action) = DelegationAction::from_byte(event[0]) else {
+            return Err(DelegationEventError::UnknownAction(event[0]));
+        };
+
+        let mut key = [0; VerifyingKey::SIZE];
+
+        key.copy_from_slice(&event[1..1 + VerifyingKey::SIZE]);
+
+        let Some(key) = VerifyingKey::from_bytes(&key) else {
+            return Err(DelegationEventError::InvalidVerifyingKey);
+        };
+
+        Ok(Self {
+            action,
+            key
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(1 + VerifyingKey::SIZE)
+    }
+}
@@ -0,0 +1,126 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use alloc::vec::Vec;
+
+use miniz_oxide::inflate::TINFLStatus;
+
+/// Encoding of the garden event's binary body.
+///
+/// Events are stored on the flowerpot blockchain forever, so large posts and
+/// comments can be compressed to save some space. Encoded event body is
+/// prefixed with a single encoding byte.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// Event body is stored as is.
+    #[default]
+    Plain,
+
+    /// Event body is compressed using raw deflate (RFC 1951) codec.
+    Deflate
+}
+
+impl Encoding {
+    pub const PLAIN: u8   = 0;
+    pub const DEFLATE: u8 = 1;
+
+    /// Deflate compression level used to encode events.
+    ///
+    /// Changing this value changes the binary representation of new events,
+    /// but old events will still be decoded correctly.
+    pub const DEFLATE_LEVEL: u8 = 9;
+
+    pub const fn to_byte(&self) -> u8 {
+        match self {
+            Self::Plain   => Self::PLAIN,
+            Self::Deflate => Self::DEFLATE
+        }
+    }
+
+    pub const fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            Self::PLAIN   => Some(Self::Plain),
+            Self::DEFLATE => Some(Self::Deflate),
+
+            _ => None
+        }
+    }
+
+    /// Encode provided bytes slice.
+    pub fn encode(&self, bytes: &[u8]) -> Vec<u8> {
+        match self {
+            Self::Plain => bytes.to_vec(),
+
+            Self::Deflate => {
+                miniz_oxide::deflate::compress_to_vec(bytes, Self::DEFLATE_LEVEL)
+            }
+        }
+    }
+
+    /// Decode provided bytes slice. Return error if the decoded bytes slice
+    /// would exceed provided max size.
+    ///
+    /// Decoder never allocates more than `max_size` bytes so it's safe to use
+    /// on untrusted input (e.g. compression bombs).
+    pub fn decode(
+        &self,
+        bytes: &[u8],
+        max_size: usize
+    ) -> Result<Vec<u8>, EncodingError> {
+        match self {
+            Self::Plain => {
+                if bytes.len() > max_size {
+                    return Err(EncodingError::TooLarge(max_size));
+                }
+
+                Ok(bytes.to_vec())
+            }
+
+            Self::Deflate => {
+                miniz_oxide::inflate::decompress_to_vec_with_limit(bytes, max_size)
+                    .map_err(|err| {
+                        match err.status {
+                            TINFLStatus::HasMoreOutput => EncodingError::TooLarge(max_size),
+                            _ => EncodingError::InvalidData
+                        }
+                    })
+            }
+        }
+    }
+}
+
+impl core::fmt::Display for Encoding {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Plain   => f.write_str("plain"),
+            Self::Deflate => f.write_str("deflate")
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum EncodingError {
+    #[error("unknown event encoding: {0}")]
+    UnknownEncoding(u8),
+
+    #[error("decoded event body exceeds max allowed size of {0} bytes")]
+    TooLarge(usize),
+
+    #[error("invalid encoded event body")]
+    InvalidData
+}
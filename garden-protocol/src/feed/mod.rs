@@ -0,0 +1,413 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::str::FromStr;
+
+use flowerpot::crypto::sign::VerifyingKey;
+
+use time::{UtcDateTime, Date, Month, Time};
+
+use crate::{Tag, ValidationError};
+
+mod plan;
+
+pub mod ranking;
+
+pub use plan::{Plan, FeedItem};
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseErrorKind {
+    #[error("unknown filter '{0}', expected one of: tag, author, since, before, is, has")]
+    UnknownFilter(String),
+
+    #[error("filter '{0}' has no value")]
+    MissingValue(String),
+
+    #[error("invalid tag: {0}")]
+    InvalidTag(#[from] ValidationError),
+
+    #[error("invalid author key '{0}', expected base64 encoded verifying key")]
+    InvalidKey(String),
+
+    #[error("invalid date '{0}', expected YYYY-MM-DD")]
+    InvalidDate(String),
+
+    #[error("invalid number '{0}'")]
+    InvalidNumber(String),
+
+    #[error("unknown value '{value}' of filter '{filter}', expected one of: {expected}")]
+    UnknownValue {
+        filter: &'static str,
+        value: String,
+        expected: &'static str
+    },
+
+    #[error("unterminated quote")]
+    UnterminatedQuote,
+
+    #[error("negation sign is not followed by a term")]
+    EmptyNegation
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{kind} (at position {})", .position + 1)]
+pub struct ParseError {
+    /// Index of the first character of the invalid term.
+    pub position: usize,
+
+    /// Reason of the error.
+    pub kind: ParseErrorKind
+}
+
+/// Kind of a feed item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Kind {
+    /// Post.
+    Post,
+
+    /// Comment to a post or another comment.
+    Reply
+}
+
+/// Indexed events referencing a feed item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Relation {
+    Comments,
+    Reactions
+}
+
+impl Relation {
+    pub const fn to_name(&self) -> &'static str {
+        match self {
+            Self::Comments  => "comments",
+            Self::Reactions => "reactions"
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    GreaterOrEqual,
+    Greater
+}
+
+impl Comparison {
+    pub const fn to_operator(&self) -> &'static str {
+        match self {
+            Self::Less           => "<",
+            Self::LessOrEqual    => "<=",
+            Self::Equal          => "=",
+            Self::GreaterOrEqual => ">=",
+            Self::Greater        => ">"
+        }
+    }
+
+    /// Compare two values using the current comparison.
+    pub const fn compare(&self, a: u64, b: u64) -> bool {
+        match self {
+            Self::Less           => a < b,
+            Self::LessOrEqual    => a <= b,
+            Self::Equal          => a == b,
+            Self::GreaterOrEqual => a >= b,
+            Self::Greater        => a > b
+        }
+    }
+}
+
+/// Single condition of a feed query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Filter {
+    /// `tag:<tag>` - post has provided tag.
+    Tag(Tag),
+
+    /// `author:<key>` - item is made by any key of the provided key's identity.
+    Author(VerifyingKey),
+
+    /// `since:<YYYY-MM-DD>` - item is made at or after provided date.
+    Since(UtcDateTime),
+
+    /// `before:<YYYY-MM-DD>` - item is made before provided date.
+    Before(UtcDateTime),
+
+    /// `is:post` or `is:reply` - item has provided kind.
+    Is(Kind),
+
+    /// `reactions>10`, `comments<=5` - amount of related events. `has:<relation>`
+    /// is a shortcut for `<relation>>0`.
+    Count(Relation, Comparison, u64),
+
+    /// Any other word or quoted phrase - item's content contains it
+    /// (case-insensitive).
+    Text(String)
+}
+
+/// Feed query term - a filter which can be negated by the `-` prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Term {
+    pub negated: bool,
+    pub filter: Filter
+}
+
+impl std::fmt::Display for Term {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.negated {
+            write!(f, "-")?;
+        }
+
+        match &self.filter {
+            Filter::Tag(tag) => write!(f, "tag:{}", tag.as_str()),
+            Filter::Author(key) => write!(f, "author:{}", key.to_base64()),
+
+            Filter::Since(date) => write!(f, "since:{}", date.date()),
+            Filter::Before(date) => write!(f, "before:{}", date.date()),
+
+            Filter::Is(Kind::Post) => write!(f, "is:post"),
+            Filter::Is(Kind::Reply) => write!(f, "is:reply"),
+
+            Filter::Count(relation, comparison, value) => {
+                write!(f, "{}{}{value}", relation.to_name(), comparison.to_operator())
+            }
+
+            Filter::Text(text) if text.contains(char::is_whitespace) => {
+                write!(f, "\"{text}\"")
+            }
+
+            Filter::Text(text) => write!(f, "{text}")
+        }
+    }
+}
+
+/// Parsed feed query - a list of terms which all must match a feed item.
+///
+/// ```text
+/// tag:sport -tag:nsfw author:<key> since:2026-01-01 reactions>10 is:reply
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FeedQuery {
+    terms: Vec<Term>
+}
+
+impl FeedQuery {
+    /// Try to parse feed query.
+    pub fn parse(query: impl AsRef<str>) -> Result<Self, ParseError> {
+        let query = query.as_ref().chars().collect::<Vec<_>>();
+
+        let mut terms = Vec::new();
+        let mut i = 0;
+
+        while i < query.len() {
+            if query[i].is_whitespace() {
+                i += 1;
+
+                continue;
+            }
+
+            let position = i;
+            let negated = query[i] == '-';
+
+            if negated {
+                i += 1;
+            }
+
+            // Quoted phrase.
+            if query.get(i) == Some(&'"') {
+                let Some(length) = query[i + 1..].iter().position(|c| *c == '"') else {
+                    return Err(ParseError {
+                        position,
+                        kind: ParseErrorKind::UnterminatedQuote
+                    });
+                };
+
+                let phrase = query[i + 1..i + 1 + length].iter()
+                    .collect::<String>()
+                    .to_lowercase();
+
+                i += length + 2;
+
+                if !phrase.is_empty() {
+                    terms.push(Term {
+                        negated,
+                        filter: Filter::Text(phrase)
+                    });
+                }
+
+                continue;
+            }
+
+            let start = i;
+
+            while i < query.len() && !query[i].is_whitespace() {
+                i += 1;
+            }
+
+            if start == i {
+                return Err(ParseError {
+                    position,
+                    kind: ParseErrorKind::EmptyNegation
+                });
+            }
+
+            let word = query[start..i].iter().collect::<String>();
+
+            let filter = parse_filter(&word).map_err(|kind| {
+                ParseError { position, kind }
+            })?;
+
+            terms.push(Term { negated, filter });
+        }
+
+        Ok(Self { terms })
+    }
+
+    /// Get list of the query terms.
+    #[inline(always)]
+    pub fn terms(&self) -> &[Term] {
+        &self.terms
+    }
+
+    /// Compile the query into an execution plan.
+    #[inline]
+    pub fn plan(&self) -> Plan {
+        Plan::new(self)
+    }
+}
+
+impl FromStr for FeedQuery {
+    type Err = ParseError;
+
+    #[inline]
+    fn from_str(query: &str) -> Result<Self, Self::Err> {
+        Self::parse(query)
+    }
+}
+
+impl std::fmt::Display for FeedQuery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, term) in self.terms.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+
+            write!(f, "{term}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse a single unquoted term without negation sign.
+fn parse_filter(word: &str) -> Result<Filter, ParseErrorKind> {
+    if let Some((name, value)) = word.split_once(':') {
+        let name = name.to_lowercase();
+
+        if value.is_empty() {
+            return Err(ParseErrorKind::MissingValue(name));
+        }
+
+        return match name.as_str() {
+            "tag" => Ok(Filter::Tag(Tag::new(value.to_lowercase())?)),
+
+            "author" => VerifyingKey::from_base64(value)
+                .map(Filter::Author)
+                .ok_or_else(|| ParseErrorKind::InvalidKey(value.to_string())),
+
+            "since" => parse_date(value).map(Filter::Since),
+            "before" => parse_date(value).map(Filter::Before),
+
+            "is" => match value.to_lowercase().as_str() {
+                "post" => Ok(Filter::Is(Kind::Post)),
+                "reply" | "comment" => Ok(Filter::Is(Kind::Reply)),
+
+                _ => Err(ParseErrorKind::UnknownValue {
+                    filter: "is",
+                    value: value.to_string(),
+                    expected: "post, reply"
+                })
+            },
+
+            "has" => match value.to_lowercase().as_str() {
+                "comments" | "replies" => Ok(Filter::Count(Relation::Comments, Comparison::Greater, 0)),
+                "reactions" => Ok(Filter::Count(Relation::Reactions, Comparison::Greater, 0)),
+
+                _ => Err(ParseErrorKind::UnknownValue {
+                    filter: "has",
+                    value: value.to_string(),
+                    expected: "comments, reactions"
+                })
+            },
+
+            _ => Err(ParseErrorKind::UnknownFilter(name))
+        };
+    }
+
+    // Relations count comparison.
+    if let Some(operator) = word.find(['<', '>', '=']) {
+        let relation = match word[..operator].to_lowercase().as_str() {
+            "comments" | "replies" => Some(Relation::Comments),
+            "reactions" => Some(Relation::Reactions),
+
+            _ => None
+        };
+
+        if let Some(relation) = relation {
+            let rest = &word[operator..];
+
+            let (comparison, value) = if let Some(value) = rest.strip_prefix("<=") {
+                (Comparison::LessOrEqual, value)
+            } else if let Some(value) = rest.strip_prefix(">=") {
+                (Comparison::GreaterOrEqual, value)
+            } else if let Some(value) = rest.strip_prefix('<') {
+                (Comparison::Less, value)
+            } else if let Some(value) = rest.strip_prefix('>') {
+                (Comparison::Greater, value)
+            } else {
+                (Comparison::Equal, &rest[1..])
+            };
+
+            let value = value.parse::<u64>()
+                .map_err(|_| ParseErrorKind::InvalidNumber(value.to_string()))?;
+
+            return Ok(Filter::Count(relation, comparison, value));
+        }
+    }
+
+    Ok(Filter::Text(word.to_lowercase()))
+}
+
+/// Parse `YYYY-MM-DD` date.
+fn parse_date(value: &str) -> Result<UtcDateTime, ParseErrorKind> {
+    let error = || ParseErrorKind::InvalidDate(value.to_string());
+
+    let mut parts = value.splitn(3, '-');
+
+    let (Some(year), Some(month), Some(day)) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(error());
+    };
+
+    let year = year.parse::<i32>().map_err(|_| error())?;
+    let month = month.parse::<u8>().map_err(|_| error())?;
+    let day = day.parse::<u8>().map_err(|_| error())?;
+
+    let month = Month::try_from(month).map_err(|_| error())?;
+    let date = Date::from_calendar_date(year, month, day).map_err(|_| error())?;
+
+    Ok(UtcDateTime::new(date, Time::MIDNIGHT))
+}
@@ -0,0 +1,308 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use flowerpot::crypto::hash::Hash;
+use flowerpot::crypto::sign::VerifyingKey;
+use flowerpot::storage::Storage;
+
+use time::UtcDateTime;
+
+use crate::Tag;
+use crate::index::{Index, IndexReadError};
+use crate::index::post::PostInfo;
+use crate::index::comment::CommentInfo;
+
+use super::{FeedQuery, Term, Filter, Kind, Relation};
+
+/// Item of a custom feed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeedItem {
+    Post(PostInfo),
+    Reply(CommentInfo)
+}
+
+impl FeedItem {
+    #[inline]
+    pub const fn kind(&self) -> Kind {
+        match self {
+            Self::Post(_)  => Kind::Post,
+            Self::Reply(_) => Kind::Reply
+        }
+    }
+
+    #[inline]
+    pub const fn message_hash(&self) -> &Hash {
+        match self {
+            Self::Post(post)   => &post.message_hash,
+            Self::Reply(reply) => &reply.message_hash
+        }
+    }
+
+    #[inline]
+    pub const fn timestamp(&self) -> &UtcDateTime {
+        match self {
+            Self::Post(post)   => &post.timestamp,
+            Self::Reply(reply) => &reply.timestamp
+        }
+    }
+
+    #[inline]
+    fn fields(&self) -> ItemRef<'_> {
+        match self {
+            Self::Post(post) => ItemRef::from(post),
+
+            Self::Reply(reply) => ItemRef {
+                kind: Kind::Reply,
+                message_hash: &reply.message_hash,
+                author: &reply.author,
+                identity: &reply.identity,
+                timestamp: &reply.timestamp,
+                content: &reply.content,
+                tags: &[]
+            }
+        }
+    }
+}
+
+/// Borrowed fields of a feed item needed to evaluate query terms.
+struct ItemRef<'a> {
+    kind: Kind,
+    message_hash: &'a Hash,
+    author: &'a VerifyingKey,
+    identity: &'a VerifyingKey,
+    timestamp: &'a UtcDateTime,
+    content: &'a str,
+    tags: &'a [Tag]
+}
+
+impl<'a> From<&'a PostInfo> for ItemRef<'a> {
+    fn from(post: &'a PostInfo) -> Self {
+        Self {
+            kind: Kind::Post,
+            message_hash: &post.message_hash,
+            author: &post.author,
+            identity: &post.identity,
+            timestamp: &post.timestamp,
+            content: &post.content,
+            tags: &post.tags
+        }
+    }
+}
+
+/// Execution plan of a feed query.
+///
+/// Terms are split into two stages: terms which can be checked using the
+/// index only (item kind, amount of comments and reactions) and terms which
+/// require reading the item from the blockchain storage. Items are read from
+/// the storage only if they pass the first stage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    /// Posts can match the query.
+    posts: bool,
+
+    /// Replies can match the query.
+    replies: bool,
+
+    /// Terms checked using the index only.
+    index_terms: Vec<Term>,
+
+    /// Terms checked on the items read from the storage.
+    storage_terms: Vec<Term>
+}
+
+impl Plan {
+    pub fn new(query: &FeedQuery) -> Self {
+        let mut posts = true;
+        let mut replies = true;
+
+        let mut index_terms = Vec::new();
+        let mut storage_terms = Vec::new();
+
+        for term in query.terms() {
+            match (&term.filter, term.negated) {
+                (Filter::Is(Kind::Post), false) |
+                (Filter::Is(Kind::Reply), true) => replies = false,
+
+                (Filter::Is(Kind::Reply), false) |
+                (Filter::Is(Kind::Post), true) => posts = false,
+
+                // Only posts have tags.
+                (Filter::Tag(_), false) => {
+                    replies = false;
+
+                    storage_terms.push(term.clone());
+                }
+
+                (Filter::Count(..), _) => index_terms.push(term.clone()),
+
+                _ => storage_terms.push(term.clone())
+            }
+        }
+
+        Self {
+            posts,
+            replies,
+            index_terms,
+            storage_terms
+        }
+    }
+
+    /// Check if the plan can return posts.
+    #[inline(always)]
+    pub const fn includes_posts(&self) -> bool {
+        self.posts
+    }
+
+    /// Check if the plan can return replies.
+    #[inline(always)]
+    pub const fn includes_replies(&self) -> bool {
+        self.replies
+    }
+
+    /// Check if an item with provided kind and message hash passes the index
+    /// stage of the plan.
+    pub fn matches_index(
+        &self,
+        index: &Index,
+        kind: Kind,
+        message_hash: &Hash
+    ) -> bool {
+        let kind = match kind {
+            Kind::Post  => self.posts,
+            Kind::Reply => self.replies
+        };
+
+        kind && self.index_terms.iter().all(|term| {
+            let Filter::Count(relation, comparison, value) = &term.filter else {
+                return true;
+            };
+
+            let count = match relation {
+                Relation::Comments  => index.comments_of(message_hash).count(),
+                Relation::Reactions => index.reactions_of(message_hash).count()
+            };
+
+            comparison.compare(count as u64, *value) != term.negated
+        })
+    }
+
+    /// Check if provided feed item matches the plan.
+    #[inline]
+    pub fn matches(&self, index: &Index, item: &FeedItem) -> bool {
+        self.matches_item(index, item.fields())
+    }
+
+    /// Check if provided post matches the plan.
+    #[inline]
+    pub fn matches_post(&self, index: &Index, post: &PostInfo) -> bool {
+        self.matches_item(index, ItemRef::from(post))
+    }
+
+    fn matches_item(&self, index: &Index, item: ItemRef<'_>) -> bool {
+        if !self.matches_index(index, item.kind, item.message_hash) {
+            return false;
+        }
+
+        self.storage_terms.iter().all(|term| {
+            let matches = match &term.filter {
+                Filter::Tag(tag) => item.tags.contains(tag),
+
+                // Any key of the identity: its rotated keys and delegated
+                // device keys.
+                Filter::Author(key) => index.identity_keys(key).contains(&item.author),
+
+                Filter::Since(date) => item.timestamp >= date,
+                Filter::Before(date) => item.timestamp < date,
+
+                Filter::Text(text) => item.content.to_lowercase().contains(text),
+
+                // Index stage terms.
+                Filter::Is(_) | Filter::Count(..) => true
+            };
+
+            matches != term.negated
+        })
+    }
+
+    /// Execute the plan over provided index and blockchain storage. Returned
+    /// items are sorted from the newest to the oldest.
+    pub fn execute(
+        &self,
+        index: &Index,
+        storage: &dyn Storage
+    ) -> Result<Vec<FeedItem>, IndexReadError> {
+        let mut items = Vec::new();
+
+        if self.posts {
+            for post in index.posts() {
+                if !self.matches_index(index, Kind::Post, post.message_hash()) {
+                    continue;
+                }
+
+                let item = FeedItem::Post(post.read(index, storage)?);
+
+                if self.matches(index, &item) {
+                    items.push(item);
+                }
+            }
+        }
+
+        if self.replies {
+            for comment in index.comments() {
+                if !self.matches_index(index, Kind::Reply, comment.message_hash()) {
+                    continue;
+                }
+
+                let item = FeedItem::Reply(comment.read(index, storage)?);
+
+                if self.matches(index, &item) {
+                    items.push(item);
+                }
+            }
+        }
+
+        items.sort_by(|a, b| b.timestamp().cmp(a.timestamp()));
+
+        Ok(items)
+    }
+}
+
+impl std::fmt::Display for Plan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.posts, self.replies) {
+            (true, true)   => writeln!(f, "scan posts and replies")?,
+            (true, false)  => writeln!(f, "scan posts")?,
+            (false, true)  => writeln!(f, "scan replies")?,
+            (false, false) => return writeln!(f, "empty result")
+        }
+
+        for term in &self.index_terms {
+            writeln!(f, "filter by index: {term}")?;
+        }
+
+        if !self.storage_terms.is_empty() {
+            writeln!(f, "read from storage")?;
+
+            for term in &self.storage_terms {
+                writeln!(f, "filter: {term}")?;
+            }
+        }
+
+        writeln!(f, "sort by timestamp")
+    }
+}
@@ -0,0 +1,201 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use flowerpot::crypto::hash::Hash;
+
+use time::{UtcDateTime, Duration};
+
+use crate::index::Index;
+
+/// Feed item properties used to rank it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RankItem {
+    /// Message hash of the item, used to order items with equal score and
+    /// timestamp.
+    pub message_hash: Hash,
+
+    /// Timestamp of the item.
+    pub timestamp: UtcDateTime,
+
+    /// Amount of reactions to the item.
+    pub reactions: u64,
+
+    /// Amount of comments to the item.
+    pub comments: u64
+}
+
+impl RankItem {
+    /// Count reactions and comments to the item with provided message hash
+    /// using the index.
+    pub fn from_index(
+        index: &Index,
+        message_hash: Hash,
+        timestamp: UtcDateTime
+    ) -> Self {
+        Self {
+            message_hash,
+            timestamp,
+            reactions: index.reactions_of(&message_hash).count() as u64,
+            comments: index.comments_of(&message_hash).count() as u64
+        }
+    }
+}
+
+/// Feed ranking strategy.
+///
+/// Items are ordered by their score from the highest to the lowest. Items with
+/// equal score are ordered from the newest to the oldest, and then by their
+/// message hash, so ranking of the same items at the same time is always the
+/// same.
+pub trait Ranking {
+    /// Calculate score of provided item at the `now` time, or return `None`
+    /// if the item must be excluded from the feed.
+    fn score(&self, item: &RankItem, now: UtcDateTime) -> Option<f64>;
+}
+
+/// Order items from the newest to the oldest.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Chronological;
+
+impl Ranking for Chronological {
+    #[inline]
+    fn score(&self, _item: &RankItem, _now: UtcDateTime) -> Option<f64> {
+        Some(0.0)
+    }
+}
+
+/// Order items made within the time window by amount of their reactions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Top {
+    pub window: Duration
+}
+
+impl Top {
+    pub const DAY: Self = Self {
+        window: Duration::DAY
+    };
+
+    pub const WEEK: Self = Self {
+        window: Duration::WEEK
+    };
+}
+
+impl Default for Top {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::WEEK
+    }
+}
+
+impl Ranking for Top {
+    fn score(&self, item: &RankItem, now: UtcDateTime) -> Option<f64> {
+        if now - item.timestamp > self.window {
+            return None;
+        }
+
+        Some(item.reactions as f64)
+    }
+}
+
+/// Order items by amount of their reactions and comments decayed by the
+/// item's age:
+///
+/// ```text
+/// score = (reactions + comments * comment_weight + 1) / (age_hours + 2) ^ gravity
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hot {
+    /// How fast the score decays with time.
+    pub gravity: f64,
+
+    /// Weight of a comment relative to a reaction.
+    pub comment_weight: f64
+}
+
+impl Default for Hot {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            gravity: 1.8,
+            comment_weight: 2.0
+        }
+    }
+}
+
+impl Ranking for Hot {
+    fn score(&self, item: &RankItem, now: UtcDateTime) -> Option<f64> {
+        let points = item.reactions as f64
+            + item.comments as f64 * self.comment_weight
+            + 1.0;
+
+        // Items from the future are treated as just created.
+        let age = (now - item.timestamp).as_seconds_f64().max(0.0) / 3600.0;
+
+        Some(points / (age + 2.0).powf(self.gravity))
+    }
+}
+
+/// Built-in ranking strategies.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Strategy {
+    Chronological(Chronological),
+    Top(Top),
+    Hot(Hot)
+}
+
+impl Default for Strategy {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::Chronological(Chronological)
+    }
+}
+
+impl Ranking for Strategy {
+    #[inline]
+    fn score(&self, item: &RankItem, now: UtcDateTime) -> Option<f64> {
+        match self {
+            Self::Chronological(ranking) => ranking.score(item, now),
+            Self::Top(ranking) => ranking.score(item, now),
+            Self::Hot(ranking) => ranking.score(item, now)
+        }
+    }
+}
+
+/// Rank provided items using the strategy at the `now` time. Items excluded by
+/// the strategy are removed.
+pub fn rank<T>(
+    ranking: &(impl Ranking + ?Sized),
+    items: impl IntoIterator<Item = (RankItem, T)>,
+    now: UtcDateTime
+) -> Vec<T> {
+    let mut items = items.into_iter()
+        .filter_map(|(item, value)| {
+            ranking.score(&item, now).map(|score| (score, item, value))
+        })
+        .collect::<Vec<_>>();
+
+    items.sort_by(|(a_score, a, _), (b_score, b, _)| {
+        b_score.total_cmp(a_score)
+            .then_with(|| b.timestamp.cmp(&a.timestamp))
+            .then_with(|| a.message_hash.as_bytes().cmp(b.message_hash.as_bytes()))
+    });
+
+    items.into_iter()
+        .map(|(_, _, value)| value)
+        .collect()
+}
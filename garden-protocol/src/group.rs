@@ -0,0 +1,545 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use flowerpot::crypto::hash::Hash;
+use flowerpot::crypto::sign::{SigningKey, VerifyingKey};
+
+use sha2::{Sha256, Digest};
+use chacha20poly1305::{ChaCha20Poly1305, KeyInit, Nonce};
+use chacha20poly1305::aead::{Aead, Payload};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use rand_core::CryptoRngCore;
+
+use crate::{Events, EventDecodeError, PostEvent, CommentEvent};
+
+use super::Event;
+
+#[derive(Debug, thiserror::Error)]
+pub enum GroupEventError {
+    #[error("provided group event bytes slice is too short")]
+    SliceTooShort,
+
+    #[error("invalid verifying key")]
+    InvalidVerifyingKey
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GroupOpenError {
+    #[error("failed to decrypt group message")]
+    Decryption,
+
+    #[error("failed to decode group message content: {0}")]
+    Event(#[from] EventDecodeError),
+
+    #[error("group message must contain a post or a comment")]
+    InvalidEventType
+}
+
+/// Symmetric key used to encrypt messages of a group within a single epoch.
+/// Group admin generates a new key every time a member is removed.
+#[derive(Clone, PartialEq, Eq)]
+pub struct GroupKey([u8; 32]);
+
+impl GroupKey {
+    pub const SIZE: usize = 32;
+
+    /// Generate new random group key.
+    pub fn random(rng: &mut impl CryptoRngCore) -> Self {
+        let mut key = [0; Self::SIZE];
+
+        rng.fill_bytes(&mut key);
+
+        Self(key)
+    }
+
+    #[inline(always)]
+    pub const fn from_bytes(bytes: [u8; Self::SIZE]) -> Self {
+        Self(bytes)
+    }
+
+    #[inline(always)]
+    pub const fn as_bytes(&self) -> &[u8; Self::SIZE] {
+        &self.0
+    }
+}
+
+impl core::fmt::Debug for GroupKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // Never print the secret key.
+        f.write_str("GroupKey(..)")
+    }
+}
+
+/// Group creation event. Hash of the message which stores this event is the
+/// group identifier, and the event's author becomes the group admin.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GroupEvent {
+    nonce: [u8; 16]
+}
+
+impl GroupEvent {
+    /// Size of the group event's binary representation.
+    pub const SIZE: usize = 16;
+
+    /// Create new group event with a random nonce, so the same admin can
+    /// create many groups.
+    pub fn new(rng: &mut impl CryptoRngCore) -> Self {
+        let mut nonce = [0; Self::SIZE];
+
+        rng.fill_bytes(&mut nonce);
+
+        Self {
+            nonce
+        }
+    }
+
+    #[inline(always)]
+    pub const fn nonce(&self) -> &[u8; Self::SIZE] {
+        &self.nonce
+    }
+}
+
+impl Event for GroupEvent {
+    type Error = GroupEventError;
+
+    #[inline]
+    fn to_bytes(&self) -> Box<[u8]> {
+        Box::new(self.nonce)
+    }
+
+    fn from_bytes(event: &[u8]) -> Result<Self, Self::Error> where Self: Sized {
+        let Some(nonce) = event.get(..Self::SIZE) else {
+            return Err(GroupEventError::SliceTooShort);
+        };
+
+        let mut event = Self {
+            nonce: [0; Self::SIZE]
+        };
+
+        event.nonce.copy_from_slice(nonce);
+
+        Ok(event)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(Self::SIZE)
+    }
+}
+
+/// Group membership event adds a member to the group at some key epoch by
+/// wrapping the epoch's group key to the member's verifying key. Must be
+/// signed by the group admin.
+///
+/// Group key is encrypted using a key derived from the ECDH exchange between
+/// a random ephemeral key and the member's key, so only the member can
+/// unwrap it.
+///
+/// To remove a member the admin generates a new group key and adds all the
+/// remaining members to the next epoch. Members which weren't re-added can't
+/// read or write new group messages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GroupMemberEvent {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
+    group: Hash,
+
+    epoch: u32,
+
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::verifying_key"))]
+    member: VerifyingKey,
+
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::bytes"))]
+    ephemeral_key: [u8; 33],
+
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::bytes"))]
+    wrapped_key: [u8; 48]
+}
+
+impl GroupMemberEvent {
+    /// Context string used to derive the group key wrapping key.
+    pub const CONTEXT: &[u8] = b"garden group key wrap";
+
+    /// Size of the compressed ephemeral public key.
+    pub const EPHEMERAL_KEY_SIZE: usize = 33;
+
+    /// Size of the encrypted group key with its authentication tag.
+    pub const WRAPPED_KEY_SIZE: usize = GroupKey::SIZE + 16;
+
+    /// Size of the group membership event's binary representation.
+    pub const SIZE: usize = Hash::SIZE
+        + 4
+        + VerifyingKey::SIZE
+        + Self::EPHEMERAL_KEY_SIZE
+        + Self::WRAPPED_KEY_SIZE;
+
+    /// Wrap group key of provided epoch to the member's key. Returned event
+    /// must be signed by the group admin.
+    pub fn new(
+        group: impl Into<Hash>,
+        epoch: u32,
+        member: VerifyingKey,
+        key: &GroupKey,
+        rng: &mut impl CryptoRngCore
+    ) -> Result<Self, GroupEventError> {
+        let group = group.into();
+
+        let Ok(member_key) = k256::PublicKey::from_sec1_bytes(&member.to_bytes()) else {
+            return Err(GroupEventError::InvalidVerifyingKey);
+        };
+
+        let ephemeral = k256::ecdh::EphemeralSecret::random(rng);
+        let shared_secret = ephemeral.diffie_hellman(&member_key);
+
+        let wrapping_key = Self::wrapping_key(
+            shared_secret.raw_secret_bytes(),
+            &group,
+            epoch,
+            &member
+        );
+
+        // Wrapping key is unique for every ephemeral key so the nonce can be
+        // constant.
+        let wrapped_key = ChaCha20Poly1305::new(&wrapping_key.into())
+            .encrypt(&Nonce::default(), key.as_bytes().as_slice())
+            .expect("failed to encrypt group key");
+
+        let ephemeral_key = ephemeral.public_key()
+            .to_encoded_point(true);
+
+        Ok(Self {
+            group,
+            epoch,
+            member,
+            ephemeral_key: ephemeral_key.as_bytes()
+                .try_into()
+                .expect("invalid compressed public key size"),
+            wrapped_key: wrapped_key.try_into()
+                .expect("invalid wrapped group key size")
+        })
+    }
+
+    /// Derive key which encrypts the group key. Group, epoch and member are
+    /// mixed in so the wrapped key can't be reused in another event.
+    fn wrapping_key(
+        shared_secret: &[u8],
+        group: &Hash,
+        epoch: u32,
+        member: &VerifyingKey
+    ) -> [u8; 32] {
+        Sha256::new()
+            .chain_update(Self::CONTEXT)
+            .chain_update(shared_secret)
+            .chain_update(group.as_bytes())
+            .chain_update(epoch.to_le_bytes())
+            .chain_update(member.to_bytes())
+            .finalize()
+            .into()
+    }
+
+    /// Hash of the group creation message.
+    #[inline(always)]
+    pub const fn group(&self) -> &Hash {
+        &self.group
+    }
+
+    /// Group key epoch. Incremented by the admin on every key rotation.
+    #[inline(always)]
+    pub const fn epoch(&self) -> u32 {
+        self.epoch
+    }
+
+    #[inline(always)]
+    pub const fn member(&self) -> &VerifyingKey {
+        &self.member
+    }
+
+    /// Try to unwrap the group key using the member's signing key. Return
+    /// `None` if the key was wrapped to another member or the event was
+    /// tampered.
+    pub fn unwrap_key(&self, signing_key: &SigningKey) -> Option<GroupKey> {
+        let secret = k256::SecretKey::from_slice(&signing_key.to_bytes()).ok()?;
+        let ephemeral = k256::PublicKey::from_sec1_bytes(&self.ephemeral_key).ok()?;
+
+        let shared_secret = k256::ecdh::diffie_hellman(
+            secret.to_nonzero_scalar(),
+            ephemeral.as_affine()
+        );
+
+        let wrapping_key = Self::wrapping_key(
+            shared_secret.raw_secret_bytes(),
+            &self.group,
+            self.epoch,
+            &self.member
+        );
+
+        let key = ChaCha20Poly1305::new(&wrapping_key.into())
+            .decrypt(&Nonce::default(), self.wrapped_key.as_slice())
+            .ok()?;
+
+        Some(GroupKey(key.try_into().ok()?))
+    }
+}
+
+impl Event for GroupMemberEvent {
+    type Error = GroupEventError;
+
+    fn to_bytes(&self) -> Box<[u8]> {
+        let mut buf = Vec::with_capacity(Self::SIZE);
+
+        buf.extend(self.group.as_bytes());
+        buf.extend(self.epoch.to_le_bytes());
+        buf.extend(self.member.to_bytes());
+        buf.extend(self.ephemeral_key);
+        buf.extend(self.wrapped_key);
+
+        buf.into_boxed_slice()
+    }
+
+    fn from_bytes(event: &[u8]) -> Result<Self, Self::Error> where Self: Sized {
+        if event.len() < Self::SIZE {
+            return Err(GroupEventError::SliceTooShort);
+        }
+
+        let mut group = [0; Hash::SIZE];
+        let mut epoch = [0; 4];
+        let mut member = [0; VerifyingKey::SIZE];
+        let mut ephemeral_key = [0; Self::EPHEMERAL_KEY_SIZE];
+        let mut wrapped_key = [0; Self::WRAPPED_KEY_SIZE];
+
+        let (bytes, event) = event.split_at(Hash::SIZE);
+
+        group.copy_from_slice(bytes);
+
+        let (bytes, event) = event.split_at(4);
+
+        epoch.copy_from_slice(bytes);
+
+        let (bytes, event) = event.split_at(VerifyingKey::SIZE);
+
+        member.copy_from_slice(bytes);
+
+        let (bytes, event) = event.split_at(Self::EPHEMERAL_KEY_SIZE);
+
+        ephemeral_key.copy_from_slice(bytes);
+        wrapped_key.copy_from_slice(&event[..Self::WRAPPED_KEY_SIZE]);
+
+        let Some(member) = VerifyingKey::from_bytes(&member) else {
+            return Err(GroupEventError::InvalidVerifyingKey);
+        };
+
+        Ok(Self {
+            group: Hash::from(group),
+            epoch: u32::from_le_bytes(epoch),
+            member,
+            ephemeral_key,
+            wrapped_key
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(Self::SIZE)
+    }
+}
+
+/// Content of a group message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
+pub enum GroupContent {
+    Post(PostEvent),
+    Comment(CommentEvent)
+}
+
+impl From<PostEvent> for GroupContent {
+    #[inline(always)]
+    fn from(value: PostEvent) -> Self {
+        Self::Post(value)
+    }
+}
+
+impl From<CommentEvent> for GroupContent {
+    #[inline(always)]
+    fn from(value: CommentEvent) -> Self {
+        Self::Comment(value)
+    }
+}
+
+impl From<GroupContent> for Events {
+    #[inline]
+    fn from(value: GroupContent) -> Self {
+        match value {
+            GroupContent::Post(post) => Self::Post(post),
+            GroupContent::Comment(comment) => Self::Comment(comment)
+        }
+    }
+}
+
+/// Group message is a post or a comment encrypted with the group key of some
+/// epoch. Comments can reference both public and group messages.
+///
+/// Only members of the group at the message's epoch can decrypt it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GroupMessageEvent {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
+    group: Hash,
+
+    epoch: u32,
+
+    nonce: [u8; 12],
+
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::bytes"))]
+    ciphertext: Box<[u8]>
+}
+
+impl GroupMessageEvent {
+    /// Size of the random encryption nonce.
+    pub const NONCE_SIZE: usize = 12;
+
+    /// Encrypt post or comment using the group key of provided epoch.
+    pub fn seal(
+        group: impl Into<Hash>,
+        epoch: u32,
+        key: &GroupKey,
+        content: impl Into<GroupContent>,
+        rng: &mut impl CryptoRngCore
+    ) -> Self {
+        let group = group.into();
+
+        let mut nonce = [0; Self::NONCE_SIZE];
+
+        rng.fill_bytes(&mut nonce);
+
+        let content = Events::from(content.into()).to_bytes();
+
+        let ciphertext = ChaCha20Poly1305::new(&(*key.as_bytes()).into())
+            .encrypt(&Nonce::from(nonce), Payload {
+                msg: &content,
+                aad: &Self::associated_data(&group, epoch)
+            })
+            .expect("failed to encrypt group message");
+
+        Self {
+            group,
+            epoch,
+            nonce,
+            ciphertext: ciphertext.into_boxed_slice()
+        }
+    }
+
+    /// Data authenticated together with the ciphertext, so the message can't
+    /// be moved to another group or epoch.
+    fn associated_data(group: &Hash, epoch: u32) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Hash::SIZE + 4);
+
+        data.extend(group.as_bytes());
+        data.extend(epoch.to_le_bytes());
+
+        data
+    }
+
+    /// Hash of the group creation message.
+    #[inline(always)]
+    pub const fn group(&self) -> &Hash {
+        &self.group
+    }
+
+    /// Epoch of the group key used to encrypt the message.
+    #[inline(always)]
+    pub const fn epoch(&self) -> u32 {
+        self.epoch
+    }
+
+    #[inline(always)]
+    pub const fn ciphertext(&self) -> &[u8] {
+        &self.ciphertext
+    }
+
+    /// Decrypt message content using the group key of the message's epoch.
+    pub fn open(&self, key: &GroupKey) -> Result<GroupContent, GroupOpenError> {
+        let content = ChaCha20Poly1305::new(&(*key.as_bytes()).into())
+            .decrypt(&Nonce::from(self.nonce), Payload {
+                msg: &self.ciphertext,
+                aad: &Self::associated_data(&self.group, self.epoch)
+            })
+            .map_err(|_| GroupOpenError::Decryption)?;
+
+        match Events::from_bytes(content)? {
+            Events::Post(post) => Ok(GroupContent::Post(post)),
+            Events::Comment(comment) => Ok(GroupContent::Comment(comment)),
+
+            _ => Err(GroupOpenError::InvalidEventType)
+        }
+    }
+}
+
+impl Event for GroupMessageEvent {
+    type Error = GroupEventError;
+
+    fn to_bytes(&self) -> Box<[u8]> {
+        let mut buf = Vec::with_capacity(
+            Hash::SIZE + 4 + Self::NONCE_SIZE + self.ciphertext.len()
+        );
+
+        buf.extend(self.group.as_bytes());
+        buf.extend(self.epoch.to_le_bytes());
+        buf.extend(self.nonce);
+        buf.extend(&self.ciphertext);
+
+        buf.into_boxed_slice()
+    }
+
+    fn from_bytes(event: &[u8]) -> Result<Self, Self::Error> where Self: Sized {
+        if event.len() < Hash::SIZE + 4 + Self::NONCE_SIZE {
+            return Err(GroupEventError::SliceTooShort);
+        }
+
+        let mut group = [0; Hash::SIZE];
+        let mut epoch = [0; 4];
+        let mut nonce = [0; Self::NONCE_SIZE];
+
+        let (bytes, event) = event.split_at(Hash::SIZE);
+
+        group.copy_from_slice(bytes);
+
+        let (bytes, event) = event.split_at(4);
+
+        epoch.copy_from_slice(bytes);
+
+        let (bytes, ciphertext) = event.split_at(Self::NONCE_SIZE);
+
+        nonce.copy_from_slice(bytes);
+
+        Ok(Self {
+            group: Hash::from(group),
+            epoch: u32::from_le_bytes(epoch),
+            nonce,
+            ciphertext: Box::from(ciphertext)
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(Hash::SIZE + 4 + Self::NONCE_SIZE + self.ciphertext.len())
+    }
+}
@@ -0,0 +1,122 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::num::NonZeroUsize;
+
+use lru::LruCache;
+
+use flowerpot::crypto::hash::Hash;
+
+use crate::index::post::PostInfo;
+use crate::index::comment::CommentInfo;
+
+/// Bounded least recently used cache of decoded garden posts and comments
+/// info, keyed by their message hashes.
+///
+/// Cached infos contain identities of their authors resolved by the index,
+/// so the cache must be cleared when the index is reset or when keys
+/// delegations or rotations are indexed.
+#[derive(Debug)]
+pub struct InfoCache {
+    posts: LruCache<Hash, PostInfo>,
+    comments: LruCache<Hash, CommentInfo>
+}
+
+impl Default for InfoCache {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl InfoCache {
+    /// Default maximal amount of posts and, separately, comments stored in
+    /// the cache.
+    pub const DEFAULT_CAPACITY: usize = 1024;
+
+    /// Create new empty cache which stores up to `capacity` posts and up to
+    /// `capacity` comments.
+    pub fn new(capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity)
+            .unwrap_or(NonZeroUsize::MIN);
+
+        Self {
+            posts: LruCache::new(capacity),
+            comments: LruCache::new(capacity)
+        }
+    }
+
+    /// Get maximal amount of posts and comments stored in the cache.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.posts.cap().get()
+    }
+
+    /// Change maximal amount of posts and comments stored in the cache,
+    /// evicting the least recently used ones if needed.
+    pub fn resize(&mut self, capacity: usize) {
+        let capacity = NonZeroUsize::new(capacity)
+            .unwrap_or(NonZeroUsize::MIN);
+
+        self.posts.resize(capacity);
+        self.comments.resize(capacity);
+    }
+
+    /// Get amount of cached posts and comments.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.posts.len() + self.comments.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.posts.is_empty() && self.comments.is_empty()
+    }
+
+    /// Get cached post info and mark it as recently used.
+    #[inline]
+    pub fn post(&mut self, message_hash: &Hash) -> Option<&PostInfo> {
+        self.posts.get(message_hash)
+    }
+
+    /// Get cached comment info and mark it as recently used.
+    #[inline]
+    pub fn comment(&mut self, message_hash: &Hash) -> Option<&CommentInfo> {
+        self.comments.get(message_hash)
+    }
+
+    /// Store post info, evicting the least recently used post if the cache
+    /// is full.
+    #[inline]
+    pub fn insert_post(&mut self, post: PostInfo) {
+        self.posts.put(post.message_hash, post);
+    }
+
+    /// Store comment info, evicting the least recently used comment if the
+    /// cache is full.
+    #[inline]
+    pub fn insert_comment(&mut self, comment: CommentInfo) {
+        self.comments.put(comment.message_hash, comment);
+    }
+
+    /// Remove all the cached infos.
+    pub fn clear(&mut self) {
+        self.posts.clear();
+        self.comments.clear();
+    }
+}
@@ -0,0 +1,235 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::HashSet;
+
+use flowerpot::crypto::base64;
+use flowerpot::crypto::hash::Hash;
+use flowerpot::crypto::sign::{SigningKey, SignatureError};
+use flowerpot::address::Address;
+use flowerpot::block::Block;
+use flowerpot::message::Message;
+use flowerpot::storage::{Storage, StorageError};
+
+use crate::StampPolicy;
+
+#[derive(Debug, thiserror::Error)]
+pub enum CreateChainError {
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+
+    #[error("failed to sign root block: {0}")]
+    Signature(#[from] SignatureError),
+
+    #[error("storage already contains a blockchain")]
+    StorageNotEmpty
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SealBlockError {
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+
+    #[error("failed to sign block: {0}")]
+    Signature(#[from] SignatureError),
+
+    #[error("node has no blockchain storage")]
+    NoStorage
+}
+
+/// Get address of a garden protocol blockchain created with provided root
+/// signer and chain id.
+#[inline]
+pub fn address(root_signer: &SigningKey, chain_id: u32) -> Address {
+    Address::new(root_signer.verifying_key(), chain_id)
+}
+
+/// Create a new garden protocol blockchain with provided root signer and
+/// chain id, and write its root block to provided empty storage. Return
+/// address of the created blockchain.
+pub fn create(
+    root_signer: &SigningKey,
+    chain_id: u32,
+    storage: &dyn Storage
+) -> Result<Address, CreateChainError> {
+    if storage.root_block()?.is_some() {
+        return Err(CreateChainError::StorageNotEmpty);
+    }
+
+    let root_block = Block::create(root_signer, Hash::ZERO, Vec::new())?;
+
+    storage.write_block(&root_block)?;
+
+    Ok(address(root_signer, chain_id))
+}
+
+/// Seal provided messages which are not stored in the blockchain yet into a
+/// new block signed by the validator and write it to provided storage.
+///
+/// Garden chains are validated by their root signer, so the validator should
+/// be the key the chain was created with. If the storage is empty then the
+/// sealed block becomes the root block. Return created block, or `None` if
+/// there are no new messages to seal.
+pub fn seal(
+    validator: &SigningKey,
+    storage: &dyn Storage,
+    messages: impl IntoIterator<Item = Message>
+) -> Result<Option<Block>, SealBlockError> {
+    let prev_hash = storage.tail_block()?.unwrap_or(Hash::ZERO);
+
+    let mut sealed = HashSet::new();
+    let mut block_messages = Vec::new();
+
+    for message in messages {
+        if sealed.insert(*message.hash()) && !storage.has_message(message.hash())? {
+            block_messages.push(message);
+        }
+    }
+
+    if block_messages.is_empty() {
+        return Ok(None);
+    }
+
+    let block = Block::create(validator, prev_hash, block_messages)?;
+
+    storage.write_block(&block)?;
+
+    Ok(Some(block))
+}
+
+/// Invite into a garden protocol blockchain. Contains everything needed to
+/// join the chain and can be shared as a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invite {
+    /// Name of the garden.
+    pub name: String,
+
+    /// Garden protocol blockchain address.
+    pub address: Address,
+
+    /// Proof of work stamps policy used by the garden.
+    pub stamps: StampPolicy,
+
+    /// Addresses of flowerpot nodes to bootstrap from.
+    pub bootstrap: Vec<String>
+}
+
+impl Invite {
+    /// Current invite encoding version.
+    const VERSION: u8 = 0;
+
+    /// Encode invite into a string which can be shared with other users.
+    ///
+    /// Name and bootstrap addresses longer than `u16::MAX` bytes are truncated
+    /// at the last character boundary which fits.
+    pub fn to_base64(&self) -> String {
+        fn push_str(bytes: &mut Vec<u8>, value: &str) {
+            let mut len = value.len().min(u16::MAX as usize);
+
+            while !value.is_char_boundary(len) {
+                len -= 1;
+            }
+
+            bytes.extend((len as u16).to_le_bytes());
+            bytes.extend_from_slice(&value.as_bytes()[..len]);
+        }
+
+        let mut bytes = Vec::new();
+
+        bytes.push(Self::VERSION);
+
+        push_str(&mut bytes, &self.address.to_base64());
+        push_str(&mut bytes, &self.name);
+
+        for difficulty in [
+            self.stamps.post,
+            self.stamps.comment,
+            self.stamps.reaction,
+            self.stamps.delegation,
+            self.stamps.rotation,
+            self.stamps.report,
+            self.stamps.group
+        ] {
+            bytes.extend(difficulty.to_le_bytes());
+        }
+
+        for address in &self.bootstrap {
+            push_str(&mut bytes, address);
+        }
+
+        base64::encode(bytes)
+    }
+
+    /// Decode invite from a string produced by the `to_base64` method.
+    pub fn from_base64(invite: impl AsRef<str>) -> Option<Self> {
+        fn read_bytes<'a>(bytes: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+            let (value, rest) = bytes.split_at_checked(len)?;
+
+            *bytes = rest;
+
+            Some(value)
+        }
+
+        fn read_str(bytes: &mut &[u8]) -> Option<String> {
+            let len = read_bytes(bytes, 2)?;
+            let len = u16::from_le_bytes([len[0], len[1]]) as usize;
+
+            let value = read_bytes(bytes, len)?;
+
+            String::from_utf8(value.to_vec()).ok()
+        }
+
+        fn read_u32(bytes: &mut &[u8]) -> Option<u32> {
+            let value = read_bytes(bytes, 4)?;
+
+            Some(u32::from_le_bytes(value.try_into().ok()?))
+        }
+
+        let bytes = base64::decode(invite.as_ref().trim()).ok()?;
+
+        let (&Self::VERSION, mut bytes) = bytes.split_first()? else {
+            return None;
+        };
+
+        let address = Address::from_base64(&read_str(&mut bytes)?)?;
+        let name = read_str(&mut bytes)?;
+
+        let stamps = StampPolicy {
+            post: read_u32(&mut bytes)?,
+            comment: read_u32(&mut bytes)?,
+            reaction: read_u32(&mut bytes)?,
+            delegation: read_u32(&mut bytes)?,
+            rotation: read_u32(&mut bytes)?,
+            report: read_u32(&mut bytes)?,
+            group: read_u32(&mut bytes)?
+        };
+
+        let mut bootstrap = Vec::new();
+
+        while !bytes.is_empty() {
+            bootstrap.push(read_str(&mut bytes)?);
+        }
+
+        Some(Self {
+            name,
+            address,
+            stamps,
+            bootstrap
+        })
+    }
+}
@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+use std::net::TcpListener;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use flowerpot::crypto::key_exchange::SecretKey;
+use flowerpot::protocol::network::{PacketStream, PacketStreamOptions};
+use flowerpot::node::NodeHandler;
+
+/// Delay before accepting new connections after the first failed attempt.
+/// It's doubled after each consecutive failure.
+pub const MIN_ACCEPT_BACKOFF: Duration = Duration::from_millis(50);
+
+/// Max delay before accepting new connections after failed attempts.
+pub const MAX_ACCEPT_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Spawn background thread which accepts incoming connections of provided
+/// TCP listener, initializes packet streams over them and adds them to the
+/// flowerpot node.
+///
+/// Failed accept attempts (e.g. when the process ran out of file descriptors)
+/// are retried with exponential backoff instead of spinning the thread.
+/// Connections which failed the packet stream handshake are dropped.
+pub fn listen(
+    listener: TcpListener,
+    node: NodeHandler,
+    secret_key: SecretKey,
+    options: PacketStreamOptions
+) -> JoinHandle<()> {
+    std::thread::spawn(move || {
+        let mut backoff = MIN_ACCEPT_BACKOFF;
+
+        loop {
+            let stream = match listener.accept() {
+                Ok((stream, _)) => {
+                    backoff = MIN_ACCEPT_BACKOFF;
+
+                    stream
+                }
+
+                Err(err) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(?err, ?backoff, "failed to accept incoming connection");
+
+                    #[cfg(not(feature = "tracing"))]
+                    let _ = err;
+
+                    std::thread::sleep(backoff);
+
+                    backoff = (backoff * 2).min(MAX_ACCEPT_BACKOFF);
+
+                    continue;
+                }
+            };
+
+            let Ok(stream) = PacketStream::init(&secret_key, &options, stream) else {
+                continue;
+            };
+
+            node.add_stream(stream);
+        }
+    })
+}
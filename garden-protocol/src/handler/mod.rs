@@ -0,0 +1,730 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicBool, Ordering};
+use std::sync::mpsc::{Sender, Receiver};
+
+use spin::{RwLock, RwLockReadGuard, Mutex, MutexGuard};
+
+use flowerpot::crypto::hash::Hash;
+use flowerpot::crypto::sign::{SigningKey, SignatureError};
+
+#[cfg(feature = "archive")]
+use flowerpot::crypto::sign::VerifyingKey;
+use flowerpot::address::Address;
+use flowerpot::message::Message;
+use flowerpot::node::NodeHandler;
+use flowerpot::storage::Storage;
+
+use crate::index::{Index, IndexUpdateError};
+use crate::index::notification::Notification;
+use crate::index::post::{PostInfo, PostIndex};
+use crate::index::comment::{CommentInfo, CommentIndex};
+use crate::feed::{Plan, FeedItem};
+
+#[cfg(feature = "archive")]
+use crate::archive::{Archive, ArchiveImport, ArchiveError};
+
+use super::{
+    Events, PostEvent, CommentEvent, ReactionEvent, DelegationEvent,
+    RotationEvent, ReportEvent, GroupEvent, GroupMemberEvent,
+    GroupMessageEvent, Stamp, StampPolicy
+};
+
+pub mod query;
+pub mod outbox;
+pub mod send;
+pub mod cache;
+pub mod chain;
+pub mod listener;
+
+use query::{QueryError, Query, Page, Cursor, Sort, Direction};
+use outbox::{Outbox, OutboxEntry};
+use send::{SendTask, SendError};
+use cache::InfoCache;
+use chain::{CreateChainError, SealBlockError};
+
+/// A helper struct that holds reference to background flowerpot node handler,
+/// a database indexer, and allows to execute garden protocol related actions
+/// and query data.
+#[derive(Clone)]
+pub struct Handler {
+    /// Garden protocol blockchain address.
+    address: Arc<Address>,
+
+    /// Flowerpot node handler.
+    node: NodeHandler,
+
+    /// Garden protocol indexer.
+    index: Arc<RwLock<Index>>,
+
+    /// Senders of the index notifications subscribers.
+    subscribers: Arc<RwLock<Vec<Sender<Notification>>>>,
+
+    /// Events sent by the handler.
+    outbox: Arc<RwLock<Outbox>>,
+
+    /// Recently read posts and comments.
+    cache: Arc<Mutex<InfoCache>>,
+
+    /// Proof of work stamps policy used to index and send events.
+    stamp_policy: StampPolicy
+}
+
+impl Handler {
+    /// Create new garden handler from provided flowerpot node handler and hash
+    /// of the root block of a blockchain where garden protocol is stored.
+    #[inline]
+    pub fn new(address: impl Into<Address>, node: NodeHandler) -> Self {
+        Self::with_stamp_policy(address, node, StampPolicy::default())
+    }
+
+    /// Create new garden handler which indexes only events with proof of
+    /// work stamps required by provided policy, and mints such stamps for
+    /// sent events.
+    pub fn with_stamp_policy(
+        address: impl Into<Address>,
+        node: NodeHandler,
+        stamp_policy: StampPolicy
+    ) -> Self {
+        let address = address.into();
+
+        Self {
+            address: Arc::new(address.clone()),
+            node,
+            index: Arc::new(RwLock::new(Index::with_stamp_policy(address, stamp_policy))),
+            subscribers: Arc::new(RwLock::new(Vec::new())),
+            outbox: Arc::new(RwLock::new(Outbox::default())),
+            cache: Arc::new(Mutex::new(InfoCache::default())),
+            stamp_policy
+        }
+    }
+
+    /// Create a new garden protocol blockchain with provided root signer and
+    /// chain id in provided empty storage, and return its address.
+    ///
+    /// The storage should be attached to a flowerpot node and used to create
+    /// a handler of the new chain.
+    #[inline]
+    pub fn create_chain(
+        root_signer: &SigningKey,
+        chain_id: u32,
+        storage: &dyn Storage
+    ) -> Result<Address, CreateChainError> {
+        chain::create(root_signer, chain_id, storage)
+    }
+
+    /// Seal events sent by the handler and provided messages which are not
+    /// stored in the blockchain yet into a new block signed by the validator,
+    /// and broadcast it to the network.
+    ///
+    /// This method should be called periodically by the node of the chain's
+    /// root signer. Return hash of the created block, or `None` if there are
+    /// no events to seal.
+    pub fn seal_block(
+        &self,
+        validator: &SigningKey,
+        messages: impl IntoIterator<Item = Message>
+    ) -> Result<Option<Hash>, SealBlockError> {
+        let messages = self.outbox.read()
+            .entries()
+            .map(|entry| entry.message().clone())
+            .chain(messages)
+            .collect::<Vec<_>>();
+
+        let block = self.node.map_storage(&self.address, |storage| {
+            chain::seal(validator, storage, messages)
+        });
+
+        let Some(block) = block.ok_or(SealBlockError::NoStorage)?? else {
+            return Ok(None);
+        };
+
+        let hash = *block.hash();
+
+        self.node.send_block(self.address.as_ref().clone(), block);
+
+        Ok(Some(hash))
+    }
+
+    /// Get reference to the garden protocol blockchain address.
+    #[inline]
+    pub const fn address(&self) -> &Arc<Address> {
+        &self.address
+    }
+
+    /// Get reference to the flowerpot node handler.
+    #[inline]
+    pub const fn node(&self) -> &NodeHandler {
+        &self.node
+    }
+
+    /// Get reference to the garden protocol index.
+    #[inline]
+    pub fn index(&self) -> RwLockReadGuard<'_, Index> {
+        self.index.read()
+    }
+
+    /// Get reference to the list of events sent by the handler.
+    #[inline]
+    pub fn outbox(&self) -> RwLockReadGuard<'_, Outbox> {
+        self.outbox.read()
+    }
+
+    /// Get reference to the cache of recently read posts and comments.
+    #[inline]
+    pub fn cache(&self) -> MutexGuard<'_, InfoCache> {
+        self.cache.lock()
+    }
+
+    /// Subscribe to the garden protocol index notifications. Notifications
+    /// are sent as soon as the index processes new events during the
+    /// `update` method call.
+    pub fn subscribe(&self) -> Receiver<Notification> {
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        self.subscribers.write().push(sender);
+
+        receiver
+    }
+
+    /// Get amount of the index notifications subscribers. Subscribers which
+    /// have dropped their receivers are removed on the next notification.
+    #[inline]
+    pub fn subscribers(&self) -> usize {
+        self.subscribers.read().len()
+    }
+
+    /// Update garden protocol indexer using blockchain tracker and delivery
+    /// statuses of the sent events.
+    pub fn update(&self) -> Result<(), IndexUpdateError> {
+        let mut notifications = Vec::new();
+
+        let result = {
+            let mut index = self.index.write();
+            let mut outbox = self.outbox.write();
+            let notifications = &mut notifications;
+            let cache = &self.cache;
+
+            self.node.map_storage(&self.address, move |storage| {
+                let identity_events = |index: &Index| {
+                    (index.statistics().delegations(), index.statistics().rotations())
+                };
+
+                let prev_identity_events = identity_events(&index);
+                let mut reset = false;
+
+                let notify = |notification: Notification| {
+                    reset |= matches!(notification, Notification::Reset);
+
+                    notifications.push(notification);
+                };
+
+                #[cfg(feature = "parallel")]
+                index.update_parallel_with(storage, notify)?;
+
+                #[cfg(not(feature = "parallel"))]
+                index.update_with(storage, notify)?;
+
+                // Cached infos contain authors' identities which could change.
+                if reset || identity_events(&index) != prev_identity_events {
+                    cache.lock().clear();
+                }
+
+                outbox.update(storage)?;
+
+                Ok::<_, IndexUpdateError>(())
+            })
+        };
+
+        // Notifications are sent after the index is unlocked so subscribers
+        // can read it while handling them. Notifications of the events indexed
+        // before an error are sent too since the index already contains them.
+        if !notifications.is_empty() {
+            let mut subscribers = self.subscribers.write();
+
+            for notification in notifications {
+                // Drop subscribers which have closed their receivers.
+                subscribers.retain(|subscriber| {
+                    subscriber.send(notification.clone()).is_ok()
+                });
+            }
+        }
+
+        match result {
+            Some(result) => result,
+            None => Ok(())
+        }
+    }
+
+    /// Execute provided callback over the garden protocol blockchain storage.
+    fn with_storage<T>(
+        &self,
+        callback: impl FnOnce(&dyn Storage) -> Result<T, QueryError>
+    ) -> Result<T, QueryError> {
+        self.node.map_storage(&self.address, callback)
+            .unwrap_or_else(|| Err(QueryError::NoStorage(self.address.to_base64())))
+    }
+
+    /// Get amount of blockchain blocks which are not indexed yet.
+    pub fn lag(&self) -> Result<u64, QueryError> {
+        let index = self.index.read();
+
+        self.with_storage(|storage| Ok(index.lag(storage)?))
+    }
+
+    /// Try to read indexed garden post info. Recently read posts are
+    /// returned from the handler's cache.
+    pub fn read_post(&self, post: &PostIndex) -> Result<PostInfo, QueryError> {
+        if let Some(info) = self.cache.lock().post(post.message_hash()) {
+            return Ok(info.clone());
+        }
+
+        let index = self.index.read();
+
+        let info = self.with_storage(|storage| Ok(post.read(&index, storage)?))?;
+
+        self.cache.lock().insert_post(info.clone());
+
+        Ok(info)
+    }
+
+    /// Try to read many indexed garden posts info. Recently read posts are
+    /// returned from the handler's cache, and the rest are decoded together.
+    pub fn read_posts<'a>(
+        &self,
+        posts: impl IntoIterator<Item = &'a PostIndex>
+    ) -> Result<Vec<PostInfo>, QueryError> {
+        let index = self.index.read();
+        let posts = posts.into_iter().collect::<Vec<_>>();
+
+        self.with_storage(|storage| self.read_cached_posts(&index, storage, &posts))
+    }
+
+    /// Read provided posts using the handler's cache and store newly read
+    /// posts in it.
+    fn read_cached_posts(
+        &self,
+        index: &Index,
+        storage: &dyn Storage,
+        posts: &[&PostIndex]
+    ) -> Result<Vec<PostInfo>, QueryError> {
+        let mut infos = {
+            let mut cache = self.cache.lock();
+
+            posts.iter()
+                .map(|post| cache.post(post.message_hash()).cloned())
+                .collect::<Vec<_>>()
+        };
+
+        let missing = posts.iter()
+            .zip(&infos)
+            .filter(|(_, info)| info.is_none())
+            .map(|(post, _)| *post);
+
+        let mut read = query::read_posts(index, storage, missing)?.into_iter();
+
+        let mut cache = self.cache.lock();
+
+        for info in &mut infos {
+            if info.is_none() && let Some(read) = read.next() {
+                cache.insert_post(read.clone());
+
+                *info = Some(read);
+            }
+        }
+
+        Ok(infos.into_iter().flatten().collect())
+    }
+
+    /// Query a page of indexed garden posts.
+    pub fn query_posts(&self, query: &Query) -> Result<Page<PostInfo>, QueryError> {
+        let index = self.index.read();
+
+        self.with_storage(|storage| {
+            let mut posts = index.posts().collect::<Vec<_>>();
+
+            if query.sort == Sort::Timestamp {
+                posts.sort_by_key(|post| *post.timestamp());
+            }
+
+            if query.direction == Direction::Backward {
+                posts.reverse();
+            }
+
+            let (posts, has_more) = query::paginate(&posts, query, |post| {
+                *post.message_hash()
+            })?;
+
+            let next = posts.last()
+                .map(|post| Cursor(*post.message_hash()))
+                .or(query.after);
+
+            let previous = posts.first()
+                .map(|post| Cursor(*post.message_hash()));
+
+            Ok(Page {
+                items: self.read_cached_posts(&index, storage, posts)?,
+                next,
+                previous,
+                has_more
+            })
+        })
+    }
+
+    /// Execute custom feed query plan over the indexed garden events.
+    pub fn query_feed(&self, plan: &Plan) -> Result<Vec<FeedItem>, QueryError> {
+        let index = self.index.read();
+
+        self.with_storage(|storage| Ok(plan.execute(&index, storage)?))
+    }
+
+    /// Try to read indexed garden comment info. Recently read comments are
+    /// returned from the handler's cache.
+    pub fn read_comment(
+        &self,
+        comment: &CommentIndex
+    ) -> Result<CommentInfo, QueryError> {
+        if let Some(info) = self.cache.lock().comment(comment.message_hash()) {
+            return Ok(info.clone());
+        }
+
+        let index = self.index.read();
+
+        let info = self.with_storage(|storage| Ok(comment.read(&index, storage)?))?;
+
+        self.cache.lock().insert_comment(info.clone());
+
+        Ok(info)
+    }
+
+    /// Mint proof of work stamp required by the handler's policy for provided
+    /// event on a background thread, create a new flowerpot message from the
+    /// stamped event using provided signing key and send it to the network
+    /// using underlying node handler.
+    ///
+    /// Returned task resolves into hash of the sent message. Its delivery
+    /// status is tracked by the handler's outbox.
+    fn send_event(
+        &self,
+        signing_key: &SigningKey,
+        event: Events
+    ) -> SendTask {
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        let task = SendTask {
+            difficulty: self.stamp_policy.required(&event),
+            attempts: Arc::new(AtomicU64::new(0)),
+            cancelled: Arc::new(AtomicBool::new(false)),
+            result: receiver
+        };
+
+        let handler = self.clone();
+        let signing_key = signing_key.clone();
+        let difficulty = task.difficulty;
+        let attempts = task.attempts.clone();
+        let cancelled = task.cancelled.clone();
+
+        std::thread::spawn(move || {
+            let result = handler.mint_and_send(
+                &signing_key,
+                &event,
+                difficulty,
+                |made| {
+                    attempts.store(made, Ordering::Release);
+
+                    !cancelled.load(Ordering::Acquire)
+                }
+            );
+
+            let _ = sender.send(result);
+        });
+
+        task
+    }
+
+    fn mint_and_send(
+        &self,
+        signing_key: &SigningKey,
+        event: &Events,
+        difficulty: u32,
+        progress: impl FnMut(u64) -> bool
+    ) -> Result<Hash, SendError> {
+        let mut data = event.to_bytes();
+
+        if difficulty > 0 {
+            let Some(stamp) = Stamp::mint(
+                &self.address,
+                &signing_key.verifying_key(),
+                &data,
+                difficulty,
+                progress
+            ) else {
+                return Err(SendError::Cancelled);
+            };
+
+            data = stamp.stamp(&data);
+        }
+
+        let message = Message::create(signing_key, data)?;
+        let hash = *message.hash();
+
+        self.outbox.write().push(message.clone());
+
+        self.node.send_message(self.address.as_ref().clone(), message);
+
+        Ok(hash)
+    }
+
+    /// Send already sent event with provided message hash to the network
+    /// again (e.g. if it was dropped).
+    ///
+    /// Return `false` if there's no such event in the outbox.
+    pub fn resubmit(&self, message_hash: &Hash) -> bool {
+        let Some(message) = self.outbox.write().resubmit(message_hash) else {
+            return false;
+        };
+
+        self.node.send_message(self.address.as_ref().clone(), message);
+
+        true
+    }
+
+    /// Stop tracking delivery status of the sent event with provided message
+    /// hash.
+    #[inline]
+    pub fn forget(&self, message_hash: &Hash) -> Option<OutboxEntry> {
+        self.outbox.write().remove(message_hash)
+    }
+
+    /// Create a new flowerpot message from new post event using provided
+    /// signing key and send it to the network using underlying node handler.
+    #[inline]
+    pub fn send_post(
+        &self,
+        signing_key: &SigningKey,
+        post: PostEvent
+    ) -> SendTask {
+        self.send_event(signing_key, Events::from(post))
+    }
+
+    /// Create a new flowerpot message from new comment event using provided
+    /// signing key and send it to the network using underlying node handler.
+    #[inline]
+    pub fn send_comment(
+        &self,
+        signing_key: &SigningKey,
+        comment: CommentEvent
+    ) -> SendTask {
+        self.send_event(signing_key, Events::from(comment))
+    }
+
+    /// Create a new flowerpot message from new reaction event using provided
+    /// signing key and send it to the network using underlying node handler.
+    #[inline]
+    pub fn send_reaction(
+        &self,
+        signing_key: &SigningKey,
+        reaction: ReactionEvent
+    ) -> SendTask {
+        self.send_event(signing_key, Events::from(reaction))
+    }
+
+    /// Create a new flowerpot message from new delegation event using provided
+    /// signing key and send it to the network using underlying node handler.
+    ///
+    /// Authorize and revoke events must be signed by the master key, accept
+    /// events must be signed by the device key.
+    #[inline]
+    pub fn send_delegation(
+        &self,
+        signing_key: &SigningKey,
+        delegation: DelegationEvent
+    ) -> SendTask {
+        self.send_event(signing_key, Events::from(delegation))
+    }
+
+    /// Create a new flowerpot message from new report event using provided
+    /// signing key and send it to the network using underlying node handler.
+    #[inline]
+    pub fn send_report(
+        &self,
+        signing_key: &SigningKey,
+        report: ReportEvent
+    ) -> SendTask {
+        self.send_event(signing_key, Events::from(report))
+    }
+
+    /// Create a new key rotation event signed by both old and new signing keys
+    /// and send it to the network using underlying node handler.
+    pub fn send_rotation(
+        &self,
+        old_signing_key: &SigningKey,
+        new_signing_key: &SigningKey
+    ) -> Result<SendTask, SignatureError> {
+        let rotation = RotationEvent::new(
+            &old_signing_key.verifying_key(),
+            new_signing_key
+        )?;
+
+        Ok(self.send_event(old_signing_key, Events::from(rotation)))
+    }
+
+    /// Create a new flowerpot message from new group creation event using
+    /// provided signing key and send it to the network using underlying node
+    /// handler.
+    ///
+    /// The signer becomes the group admin. Hash of the sent message is the
+    /// group identifier.
+    #[inline]
+    pub fn send_group(
+        &self,
+        signing_key: &SigningKey,
+        group: GroupEvent
+    ) -> SendTask {
+        self.send_event(signing_key, Events::from(group))
+    }
+
+    /// Create a new flowerpot message from new group member event using
+    /// provided signing key and send it to the network using underlying node
+    /// handler.
+    ///
+    /// Member events must be signed by the group admin.
+    #[inline]
+    pub fn add_group_member(
+        &self,
+        signing_key: &SigningKey,
+        member: GroupMemberEvent
+    ) -> SendTask {
+        self.send_event(signing_key, Events::from(member))
+    }
+
+    /// Create a new flowerpot message from new encrypted group message event
+    /// using provided signing key and send it to the network using underlying
+    /// node handler.
+    ///
+    /// Group messages must be signed by a member of the message's epoch.
+    #[inline]
+    pub fn send_group_message(
+        &self,
+        signing_key: &SigningKey,
+        message: GroupMessageEvent
+    ) -> SendTask {
+        self.send_event(signing_key, Events::from(message))
+    }
+
+    /// Export all the posts, comments, reactions and identity events signed
+    /// by the identity keys of provided author into a portable archive.
+    #[cfg(feature = "archive")]
+    pub fn export_archive(
+        &self,
+        author: &VerifyingKey
+    ) -> Result<Archive, ArchiveError> {
+        let index = self.index.read();
+
+        let archive = self.node.map_storage(&self.address, |storage| {
+            Archive::export(storage, &index, author)
+        });
+
+        let Some(archive) = archive else {
+            return Err(ArchiveError::NoStorage(self.address.to_base64()));
+        };
+
+        Ok(archive?.with_chain(self.address.to_base64()))
+    }
+
+    /// Verify provided archive and republish its messages which are not
+    /// stored in the garden protocol blockchain yet. Messages keep their
+    /// original signatures and hashes, so archived comments and reactions
+    /// keep referencing archived posts.
+    ///
+    /// Proof of work stamps are bound to the blockchain address, so stamps of
+    /// messages archived from another blockchain are not valid here. Messages
+    /// without stamps required by the handler's stamp policy would not be
+    /// indexed, so they are not sent and are reported as unstamped instead.
+    ///
+    /// Delivery statuses of the sent messages can be tracked using the outbox.
+    #[cfg(feature = "archive")]
+    pub fn import_archive(
+        &self,
+        archive: &Archive
+    ) -> Result<ArchiveImport, ArchiveError> {
+        archive.verify()?;
+
+        let messages = self.node.map_storage(&self.address, |storage| {
+            let mut messages = Vec::new();
+
+            let entries = archive.messages().iter()
+                .zip(&archive.manifest().entries);
+
+            for (message, entry) in entries {
+                if storage.read_message(message.hash())?.is_none() {
+                    messages.push((message.clone(), &entry.signer));
+                }
+            }
+
+            Ok::<_, ArchiveError>(messages)
+        });
+
+        let Some(messages) = messages else {
+            return Err(ArchiveError::NoStorage(self.address.to_base64()));
+        };
+
+        let mut outbox = self.outbox.write();
+        let mut import = ArchiveImport::default();
+
+        for (message, signer) in messages? {
+            let hash = *message.hash();
+
+            // Archive is verified so all its messages are garden events.
+            let is_stamped = Events::from_bytes(message.data())
+                .is_ok_and(|event| {
+                    self.stamp_policy.check(&self.address, signer, &event, message.data())
+                });
+
+            if !is_stamped {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(
+                    message_hash = hash.to_base64(),
+                    "skip archived event without required proof of work stamp"
+                );
+
+                import.unstamped.push(hash);
+
+                continue;
+            }
+
+            outbox.push(message.clone());
+
+            self.node.send_message(self.address.as_ref().clone(), message);
+
+            import.sent.push(hash);
+        }
+
+        Ok(import)
+    }
+}
+
+impl std::fmt::Debug for Handler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Handler")
+            .field("address", &self.address.to_base64())
+            .finish()
+    }
+}
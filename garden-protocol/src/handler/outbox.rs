@@ -0,0 +1,171 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use flowerpot::crypto::hash::Hash;
+use flowerpot::message::Message;
+use flowerpot::storage::{Storage, StorageError};
+
+use time::{UtcDateTime, Duration};
+
+/// Delivery status of a sent event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeliveryStatus {
+    /// Event was sent to the network but is not stored in the blockchain yet.
+    Pending,
+
+    /// Event is stored in the blockchain block with provided hash.
+    Included {
+        block_hash: Hash
+    },
+
+    /// Event wasn't included in the blockchain within the
+    /// `Outbox::DROP_TIMEOUT` and most likely was dropped by the network.
+    /// It can be resubmitted.
+    Dropped
+}
+
+/// Event sent to the network.
+#[derive(Debug, Clone)]
+pub struct OutboxEntry {
+    /// Flowerpot message with the sent event.
+    pub(super) message: Message,
+
+    /// Current delivery status of the event.
+    pub(super) status: DeliveryStatus,
+
+    /// Timestamp when the event was sent (or resubmitted) last time.
+    pub(super) sent_at: UtcDateTime,
+
+    /// Amount of times the event was sent.
+    pub(super) attempts: u32
+}
+
+impl OutboxEntry {
+    #[inline]
+    pub const fn message(&self) -> &Message {
+        &self.message
+    }
+
+    #[inline]
+    pub const fn status(&self) -> &DeliveryStatus {
+        &self.status
+    }
+
+    #[inline]
+    pub const fn sent_at(&self) -> &UtcDateTime {
+        &self.sent_at
+    }
+
+    #[inline]
+    pub const fn attempts(&self) -> u32 {
+        self.attempts
+    }
+}
+
+/// List of events sent by the current handler with their delivery statuses.
+#[derive(Default, Debug, Clone)]
+pub struct Outbox {
+    entries: Vec<OutboxEntry>
+}
+
+impl Outbox {
+    /// Pending events which weren't included in the blockchain within this
+    /// timeout are considered dropped.
+    pub const DROP_TIMEOUT: Duration = Duration::minutes(10);
+
+    /// Add new sent message to the outbox.
+    pub fn push(&mut self, message: Message) {
+        self.entries.push(OutboxEntry {
+            message,
+            status: DeliveryStatus::Pending,
+            sent_at: UtcDateTime::now(),
+            attempts: 1
+        });
+    }
+
+    /// Mark event as sent again and return its message.
+    pub fn resubmit(&mut self, message_hash: &Hash) -> Option<Message> {
+        let entry = self.entries.iter_mut()
+            .find(|entry| entry.message.hash() == message_hash)?;
+
+        entry.status = DeliveryStatus::Pending;
+        entry.sent_at = UtcDateTime::now();
+        entry.attempts += 1;
+
+        Some(entry.message.clone())
+    }
+
+    /// Remove event from the outbox.
+    pub fn remove(&mut self, message_hash: &Hash) -> Option<OutboxEntry> {
+        let index = self.entries.iter()
+            .position(|entry| entry.message.hash() == message_hash)?;
+
+        Some(self.entries.remove(index))
+    }
+
+    /// Update delivery statuses of the sent events using provided flowerpot
+    /// blockchain storage.
+    #[inline]
+    pub fn update(&mut self, storage: &dyn Storage) -> Result<(), StorageError> {
+        self.update_at(storage, UtcDateTime::now())
+    }
+
+    /// Update delivery statuses of the sent events using provided flowerpot
+    /// blockchain storage as if it was provided time now.
+    pub fn update_at(
+        &mut self,
+        storage: &dyn Storage,
+        now: UtcDateTime
+    ) -> Result<(), StorageError> {
+        for entry in &mut self.entries {
+            // Included events can go back to pending if the blockchain was
+            // changed (e.g. by reorg).
+            match storage.find_message(entry.message.hash())? {
+                Some(block_hash) => {
+                    entry.status = DeliveryStatus::Included { block_hash };
+                }
+
+                None if now - entry.sent_at > Self::DROP_TIMEOUT => {
+                    entry.status = DeliveryStatus::Dropped;
+                }
+
+                None => entry.status = DeliveryStatus::Pending
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get sent event with provided message hash.
+    pub fn get(&self, message_hash: &Hash) -> Option<&OutboxEntry> {
+        self.entries.iter()
+            .find(|entry| entry.message.hash() == message_hash)
+    }
+
+    /// Get delivery status of the sent event with provided message hash.
+    #[inline]
+    pub fn status(&self, message_hash: &Hash) -> Option<&DeliveryStatus> {
+        self.get(message_hash).map(OutboxEntry::status)
+    }
+
+    /// Get iterator over all the sent events.
+    #[inline]
+    pub fn entries(&self) -> impl Iterator<Item = &OutboxEntry> {
+        self.entries.iter()
+    }
+}
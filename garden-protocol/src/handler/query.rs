@@ -0,0 +1,216 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use flowerpot::crypto::base64;
+use flowerpot::crypto::hash::Hash;
+use flowerpot::message::Message;
+use flowerpot::storage::{Storage, StorageError};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::index::{Index, IndexReadError};
+use crate::index::post::{PostInfo, PostIndex};
+
+#[derive(Debug, thiserror::Error)]
+pub enum QueryError {
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+
+    #[error(transparent)]
+    Read(#[from] IndexReadError),
+
+    #[error("no storage for blockchain with address '{0}'")]
+    NoStorage(String),
+
+    #[error("cursor references a message which is not indexed")]
+    InvalidCursor
+}
+
+/// Order in which indexed events are returned.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sort {
+    /// Order in which events are stored in the blockchain.
+    #[default]
+    BlockOrder,
+
+    /// Order of events' block timestamps. Events with the same timestamp are
+    /// returned in the block order.
+    Timestamp
+}
+
+/// Direction in which indexed events are paged.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// From the oldest events to the newest.
+    #[default]
+    Forward,
+
+    /// From the newest events to the oldest.
+    Backward
+}
+
+impl Direction {
+    /// Get opposite direction.
+    #[inline]
+    pub const fn reverse(&self) -> Self {
+        match self {
+            Self::Forward  => Self::Backward,
+            Self::Backward => Self::Forward
+        }
+    }
+}
+
+/// Opaque position within the query results. Query with a cursor returns only
+/// events which come after the cursor in the query direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cursor(pub(super) Hash);
+
+impl Cursor {
+    const VERSION: u8 = 0;
+
+    /// Encode cursor into a string which can be stored by the client.
+    pub fn to_base64(&self) -> String {
+        let mut bytes = Vec::with_capacity(Hash::SIZE + 1);
+
+        bytes.push(Self::VERSION);
+        bytes.extend_from_slice(self.0.as_bytes());
+
+        base64::encode(bytes)
+    }
+
+    /// Decode cursor from a string produced by the `to_base64` method.
+    pub fn from_base64(cursor: impl AsRef<str>) -> Option<Self> {
+        let bytes = base64::decode(cursor.as_ref()).ok()?;
+
+        let (&Self::VERSION, hash) = bytes.split_first()? else {
+            return None;
+        };
+
+        let hash: [u8; Hash::SIZE] = hash.try_into().ok()?;
+
+        Some(Self(Hash::from(hash)))
+    }
+}
+
+/// Indexed events query.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Query {
+    /// Order of returned events.
+    pub sort: Sort,
+
+    /// Paging direction.
+    pub direction: Direction,
+
+    /// Max amount of returned events.
+    pub limit: usize,
+
+    /// Return only events after this position.
+    pub after: Option<Cursor>
+}
+
+impl Default for Query {
+    fn default() -> Self {
+        Self {
+            sort: Sort::default(),
+            direction: Direction::default(),
+            limit: Self::DEFAULT_LIMIT,
+            after: None
+        }
+    }
+}
+
+impl Query {
+    /// Default max amount of returned events.
+    pub const DEFAULT_LIMIT: usize = 50;
+}
+
+/// Single page of the query results.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page<T> {
+    /// Events of the page in the query direction.
+    pub items: Vec<T>,
+
+    /// Cursor of the last event of the page. Use it with the same query
+    /// direction to get the next page. Stays the same when there are no
+    /// more events yet so it can be used to poll for new events.
+    pub next: Option<Cursor>,
+
+    /// Cursor of the first event of the page. Use it with reversed query
+    /// direction to get the previous page.
+    pub previous: Option<Cursor>,
+
+    /// There are more events after the current page.
+    pub has_more: bool
+}
+
+/// Get slice of the query results page and flag whether there are more items
+/// after it. Items must be sorted in the query order and direction.
+pub(super) fn paginate<'a, T>(
+    items: &'a [T],
+    query: &Query,
+    hash: impl Fn(&T) -> Hash
+) -> Result<(&'a [T], bool), QueryError> {
+    let start = match &query.after {
+        Some(Cursor(after)) => {
+            let position = items.iter()
+                .position(|item| &hash(item) == after)
+                .ok_or(QueryError::InvalidCursor)?;
+
+            position + 1
+        }
+
+        None => 0
+    };
+
+    let end = start.saturating_add(query.limit).min(items.len());
+
+    Ok((&items[start..end], end < items.len()))
+}
+
+/// Read provided posts from the flowerpot blockchain storage. Messages are
+/// read first, so posts can be decoded and verified without accessing the
+/// storage.
+pub(super) fn read_posts<'a>(
+    index: &Index,
+    storage: &dyn Storage,
+    posts: impl IntoIterator<Item = &'a PostIndex>
+) -> Result<Vec<PostInfo>, QueryError> {
+    let messages = posts.into_iter()
+        .map(|post| {
+            let Some(message) = storage.read_message(post.message_hash())? else {
+                return Err(IndexReadError::NoMessageInStorage(*post.message_hash()).into());
+            };
+
+            Ok((post, message))
+        })
+        .collect::<Result<Vec<_>, QueryError>>()?;
+
+    let read_post = |(post, message): &(&PostIndex, Message)| -> Result<PostInfo, QueryError> {
+        Ok(post.read_message(index, message)?)
+    };
+
+    // Decode posts and verify their signatures concurrently if possible.
+    #[cfg(feature = "parallel")]
+    let messages = messages.par_iter();
+
+    #[cfg(not(feature = "parallel"))]
+    let messages = messages.iter();
+
+    messages.map(read_post).collect()
+}
@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, TryRecvError};
+
+use flowerpot::crypto::hash::Hash;
+use flowerpot::crypto::sign::SignatureError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SendError {
+    #[error(transparent)]
+    Signature(#[from] SignatureError),
+
+    #[error("proof of work stamp minting was cancelled")]
+    Cancelled,
+
+    #[error("event sending thread has stopped unexpectedly")]
+    Aborted
+}
+
+/// Handle which can be used to cancel proof of work stamp minting of a sent
+/// event.
+#[derive(Debug, Clone)]
+pub struct CancelHandle(Arc<AtomicBool>);
+
+impl CancelHandle {
+    /// Cancel stamp minting. Has no effect if the event was already sent.
+    #[inline]
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+}
+
+/// Event being sent to the network. Before the event is sent its proof of
+/// work stamp is minted on a background thread.
+#[derive(Debug)]
+pub struct SendTask {
+    pub(super) difficulty: u32,
+    pub(super) attempts: Arc<AtomicU64>,
+    pub(super) cancelled: Arc<AtomicBool>,
+    pub(super) result: Receiver<Result<Hash, SendError>>
+}
+
+impl SendTask {
+    /// Difficulty of the minted stamp. Events with zero difficulty are sent
+    /// without stamp.
+    #[inline]
+    pub const fn difficulty(&self) -> u32 {
+        self.difficulty
+    }
+
+    /// Amount of stamp minting attempts made.
+    #[inline]
+    pub fn attempts(&self) -> u64 {
+        self.attempts.load(Ordering::Acquire)
+    }
+
+    /// Estimated stamp minting progress in `[0.0, 1.0]` range.
+    ///
+    /// Minting is probabilistic so this is only a ratio of made attempts to
+    /// the expected amount of attempts.
+    pub fn progress(&self) -> f64 {
+        if self.difficulty == 0 {
+            return 1.0;
+        }
+
+        let expected = 2.0_f64.powi(self.difficulty as i32);
+
+        (self.attempts() as f64 / expected).min(1.0)
+    }
+
+    /// Get handle which can cancel stamp minting from another thread.
+    #[inline]
+    pub fn cancel_handle(&self) -> CancelHandle {
+        CancelHandle(self.cancelled.clone())
+    }
+
+    /// Cancel stamp minting. Has no effect if the event was already sent.
+    #[inline]
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    /// Get sent message hash if the task is finished, or `None` otherwise.
+    pub fn try_wait(&self) -> Option<Result<Hash, SendError>> {
+        match self.result.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(SendError::Aborted))
+        }
+    }
+
+    /// Block the current thread until the task is finished and return sent
+    /// message hash.
+    pub fn wait(self) -> Result<Hash, SendError> {
+        self.result.recv().unwrap_or(Err(SendError::Aborted))
+    }
+}
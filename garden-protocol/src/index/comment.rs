@@ -28,28 +28,45 @@ use super::{Index, IndexReadError};
 
 /// Information about a garden post comment.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CommentInfo {
     /// Hash of the block of the flowerpot blockchain where the comment info is
     /// stored.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
     pub block_hash: Hash,
 
     /// Hash of the message of the flowerpot blockchain where the comment info
     /// is stored (practically the address of the comment).
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
     pub message_hash: Hash,
 
     /// Hash of the block of the flowerpot blockchain where the original post
     /// or comment referenced by the current comment is stored.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
     pub ref_block_hash: Hash,
 
     /// Hash of the message of the flowerpot blockchain where the original post
     /// or comment referenced by the current comment is stored.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
     pub ref_message_hash: Hash,
 
     /// Flowerpot verifying key of the comment author.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::verifying_key"))]
     pub author: VerifyingKey,
 
+    /// Identity key of the comment author. Differs from `author` if the comment
+    /// was signed by a device key delegated from a master identity key.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::verifying_key"))]
+    pub identity: VerifyingKey,
+
+    /// The comment was signed by a key which was revoked by identity key rotation
+    /// before the comment was created. Such comments can be made by somebody who
+    /// stole the revoked key.
+    pub signed_by_revoked_key: bool,
+
     /// Timestamp when, approximately, the comment was created. Derived from the
     /// block where the post is stored on the flowerpot blockchain.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::timestamp"))]
     pub timestamp: UtcDateTime,
 
     /// Content of the comment.
@@ -58,16 +75,24 @@ pub struct CommentInfo {
 
 /// Index of a garden post comment stored in flowerpot blockchain.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CommentIndex {
     /// Block hash where the current comment is stored.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
     pub(super) block_hash: Hash,
 
     /// Message hash where the current comment is stored.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
     pub(super) message_hash: Hash,
 
     /// Message hash of a post or another comment referenced by the current
     /// comment.
-    pub(super) ref_message_hash: Hash
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
+    pub(super) ref_message_hash: Hash,
+
+    /// Timestamp of the block where the current comment is stored.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::timestamp"))]
+    pub(super) timestamp: UtcDateTime
 }
 
 impl CommentIndex {
@@ -86,18 +111,20 @@ impl CommentIndex {
         &self.ref_message_hash
     }
 
+    #[inline]
+    pub const fn timestamp(&self) -> &UtcDateTime {
+        &self.timestamp
+    }
+
     /// Try to read indexed post comment from provided flowerpot blockchain
     /// storage.
+    ///
+    /// Index is used to resolve the comment author's identity.
     pub fn read(
         &self,
+        index: &Index,
         storage: &dyn Storage
     ) -> Result<CommentInfo, IndexReadError> {
-        // FIXME: we don't need to read the whole block, only some of its
-        //        metadata, but there's currently no logic for it.
-        let Some(block) = storage.read_block(&self.block_hash)? else {
-            return Err(IndexReadError::NoMessageInStorage(self.block_hash));
-        };
-
         let Some(message) = storage.read_message(&self.message_hash)? else {
             return Err(IndexReadError::NoMessageInStorage(self.message_hash));
         };
@@ -112,13 +139,18 @@ impl CommentIndex {
 
         let (_, author) = message.verify()?;
 
+        let identity = index.identity(&author, &self.timestamp).clone();
+        let signed_by_revoked_key = index.is_revoked_key(&author, &self.timestamp);
+
         Ok(CommentInfo {
             block_hash: self.block_hash,
             message_hash: self.message_hash,
             ref_block_hash,
             ref_message_hash: *comment.ref_message_hash(),
             author,
-            timestamp: *block.timestamp(),
+            identity,
+            signed_by_revoked_key,
+            timestamp: self.timestamp,
             content: comment.content().clone()
         })
     }
@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use flowerpot::crypto::sign::VerifyingKey;
+
+use time::UtcDateTime;
+
+/// Indexed delegation of a device key from a master identity key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Delegation {
+    /// Master identity key which delegates the device key.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::verifying_key"))]
+    pub master_key: VerifyingKey,
+
+    /// Device key which can sign events on behalf of the master key.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::verifying_key"))]
+    pub device_key: VerifyingKey,
+
+    /// Timestamp of the block where the master key authorized the device key.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::optional_timestamp"))]
+    pub authorized_at: Option<UtcDateTime>,
+
+    /// Timestamp of the block where the device key accepted the authorization.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::optional_timestamp"))]
+    pub accepted_at: Option<UtcDateTime>,
+
+    /// Timestamp of the block where the master key revoked the device key.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::optional_timestamp"))]
+    pub revoked_at: Option<UtcDateTime>
+}
+
+impl Delegation {
+    /// Get timestamp since which the delegation is valid. Return `None` if
+    /// the delegation was not authorized or accepted yet.
+    pub fn valid_since(&self) -> Option<UtcDateTime> {
+        let authorized_at = self.authorized_at?;
+        let accepted_at = self.accepted_at?;
+
+        Some(authorized_at.max(accepted_at))
+    }
+
+    /// Check if the delegation was valid at the provided timestamp.
+    pub fn is_valid_at(&self, timestamp: &UtcDateTime) -> bool {
+        let Some(valid_since) = self.valid_since() else {
+            return false;
+        };
+
+        match &self.revoked_at {
+            Some(revoked_at) => (valid_since..*revoked_at).contains(timestamp),
+            None => &valid_since <= timestamp
+        }
+    }
+
+    /// Check if the delegation is not revoked.
+    #[inline]
+    pub const fn is_active(&self) -> bool {
+        self.revoked_at.is_none()
+    }
+
+    /// Check if the delegation is authorized, accepted and not revoked.
+    #[inline]
+    pub const fn is_valid(&self) -> bool {
+        self.authorized_at.is_some()
+            && self.accepted_at.is_some()
+            && self.revoked_at.is_none()
+    }
+}
@@ -0,0 +1,381 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+use std::collections::HashMap;
+
+use flowerpot::crypto::hash::Hash;
+use flowerpot::crypto::sign::{SigningKey, VerifyingKey};
+use flowerpot::storage::Storage;
+
+use time::{UtcDateTime, Duration};
+
+use crate::{Events, GroupKey, GroupContent};
+
+use super::{Index, IndexReadError};
+
+/// Indexed encrypted group.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GroupIndex {
+    /// Block hash where the group creation event is stored.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
+    pub(super) block_hash: Hash,
+
+    /// Message hash where the group creation event is stored. Used as the
+    /// group identifier.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
+    pub(super) message_hash: Hash,
+
+    /// Verifying key of the group creator. Only the admin can add members
+    /// and rotate the group key.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::verifying_key"))]
+    pub(super) admin: VerifyingKey,
+
+    /// Current group key epoch.
+    pub(super) epoch: u32,
+
+    /// Members added to the group at all the epochs.
+    pub(super) members: Vec<GroupMemberIndex>,
+
+    /// Timestamp of the block where the group was created.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::timestamp"))]
+    pub(super) timestamp: UtcDateTime
+}
+
+impl GroupIndex {
+    /// Time after a group key rotation during which messages encrypted with
+    /// the previous epoch's key are still accepted, since they could be sent
+    /// before the rotation was stored in the blockchain.
+    pub const EPOCH_GRACE_PERIOD: Duration = Duration::minutes(10);
+
+    #[inline]
+    pub const fn block_hash(&self) -> &Hash {
+        &self.block_hash
+    }
+
+    #[inline]
+    pub const fn message_hash(&self) -> &Hash {
+        &self.message_hash
+    }
+
+    #[inline]
+    pub const fn admin(&self) -> &VerifyingKey {
+        &self.admin
+    }
+
+    #[inline]
+    pub const fn epoch(&self) -> u32 {
+        self.epoch
+    }
+
+    #[inline]
+    pub const fn timestamp(&self) -> &UtcDateTime {
+        &self.timestamp
+    }
+
+    /// Get iterator over the members of the group at the current epoch.
+    pub fn members(&self) -> impl Iterator<Item = &GroupMemberIndex> {
+        self.members.iter()
+            .filter(|member| member.epoch == self.epoch)
+    }
+
+    /// Get iterator over the members added to the group at all the epochs,
+    /// including removed ones.
+    #[inline]
+    pub fn all_members(&self) -> impl Iterator<Item = &GroupMemberIndex> {
+        self.members.iter()
+    }
+
+    /// Check if provided key is a member of the group at the current epoch.
+    #[inline]
+    pub fn is_member(&self, key: &VerifyingKey) -> bool {
+        self.is_member_at(key, self.epoch)
+    }
+
+    /// Check if provided key is a member of the group at provided epoch.
+    pub fn is_member_at(&self, key: &VerifyingKey, epoch: u32) -> bool {
+        self.members.iter()
+            .any(|member| member.epoch == epoch && &member.member == key)
+    }
+
+    /// Get timestamp of the block where the first member of provided epoch
+    /// was added, i.e. when the group key was rotated to this epoch.
+    pub fn epoch_timestamp(&self, epoch: u32) -> Option<&UtcDateTime> {
+        self.members.iter()
+            .find(|member| member.epoch == epoch)
+            .map(|member| &member.timestamp)
+    }
+
+    /// Check if a message encrypted with the key of provided epoch and stored
+    /// in a block with provided timestamp should be accepted.
+    ///
+    /// Messages of the current epoch are always accepted. Messages of the
+    /// previous epoch are accepted within the `EPOCH_GRACE_PERIOD` after the
+    /// group key rotation.
+    pub fn accepts_epoch(&self, epoch: u32, timestamp: &UtcDateTime) -> bool {
+        if epoch == self.epoch {
+            return true;
+        }
+
+        if epoch.checked_add(1) != Some(self.epoch) {
+            return false;
+        }
+
+        self.epoch_timestamp(self.epoch)
+            .is_some_and(|rotated_at| {
+                *timestamp <= *rotated_at + Self::EPOCH_GRACE_PERIOD
+            })
+    }
+}
+
+/// Indexed group member added at some key epoch.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GroupMemberIndex {
+    /// Message hash where the membership event with the wrapped group key is
+    /// stored.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
+    pub(super) message_hash: Hash,
+
+    /// Verifying key of the member.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::verifying_key"))]
+    pub(super) member: VerifyingKey,
+
+    /// Group key epoch the member was added to.
+    pub(super) epoch: u32,
+
+    /// Timestamp of the block where the member was added.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::timestamp"))]
+    pub(super) timestamp: UtcDateTime
+}
+
+impl GroupMemberIndex {
+    #[inline]
+    pub const fn message_hash(&self) -> &Hash {
+        &self.message_hash
+    }
+
+    #[inline]
+    pub const fn member(&self) -> &VerifyingKey {
+        &self.member
+    }
+
+    #[inline]
+    pub const fn epoch(&self) -> u32 {
+        self.epoch
+    }
+
+    #[inline]
+    pub const fn timestamp(&self) -> &UtcDateTime {
+        &self.timestamp
+    }
+}
+
+/// Decrypted group message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GroupMessageInfo {
+    /// Hash of the block of the flowerpot blockchain where the message is
+    /// stored.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
+    pub block_hash: Hash,
+
+    /// Hash of the message of the flowerpot blockchain where the message is
+    /// stored.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
+    pub message_hash: Hash,
+
+    /// Hash of the group creation message.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
+    pub group: Hash,
+
+    /// Group key epoch used to encrypt the message.
+    pub epoch: u32,
+
+    /// Flowerpot verifying key of the message author.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::verifying_key"))]
+    pub author: VerifyingKey,
+
+    /// Timestamp when, approximately, the message was created. Derived from
+    /// the block where the message is stored on the flowerpot blockchain.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::timestamp"))]
+    pub timestamp: UtcDateTime,
+
+    /// Decrypted post or comment.
+    pub content: GroupContent
+}
+
+/// Indexed encrypted group message. Author is stored in the index since the
+/// message content can't be read without the group key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GroupMessageIndex {
+    /// Block hash where the current message is stored.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
+    pub(super) block_hash: Hash,
+
+    /// Message hash where the current message is stored.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
+    pub(super) message_hash: Hash,
+
+    /// Hash of the group creation message.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
+    pub(super) group: Hash,
+
+    /// Group key epoch used to encrypt the message.
+    pub(super) epoch: u32,
+
+    /// Verifying key of the message author.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::verifying_key"))]
+    pub(super) author: VerifyingKey,
+
+    /// Timestamp of the block where the current message is stored.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::timestamp"))]
+    pub(super) timestamp: UtcDateTime
+}
+
+impl GroupMessageIndex {
+    #[inline]
+    pub const fn block_hash(&self) -> &Hash {
+        &self.block_hash
+    }
+
+    #[inline]
+    pub const fn message_hash(&self) -> &Hash {
+        &self.message_hash
+    }
+
+    #[inline]
+    pub const fn group(&self) -> &Hash {
+        &self.group
+    }
+
+    #[inline]
+    pub const fn epoch(&self) -> u32 {
+        self.epoch
+    }
+
+    #[inline]
+    pub const fn author(&self) -> &VerifyingKey {
+        &self.author
+    }
+
+    #[inline]
+    pub const fn timestamp(&self) -> &UtcDateTime {
+        &self.timestamp
+    }
+
+    /// Try to read and decrypt indexed group message from provided flowerpot
+    /// blockchain storage. Return `None` if provided keys don't contain the
+    /// group key of the message's epoch.
+    pub fn read(
+        &self,
+        storage: &dyn Storage,
+        keys: &GroupKeys
+    ) -> Result<Option<GroupMessageInfo>, IndexReadError> {
+        let Some(key) = keys.get(&self.group, self.epoch) else {
+            return Ok(None);
+        };
+
+        let Some(message) = storage.read_message(&self.message_hash)? else {
+            return Err(IndexReadError::NoMessageInStorage(self.message_hash));
+        };
+
+        let Events::GroupMessage(event) = Events::from_bytes(message.data())? else {
+            return Err(IndexReadError::InvalidEventType(self.message_hash));
+        };
+
+        Ok(Some(GroupMessageInfo {
+            block_hash: self.block_hash,
+            message_hash: self.message_hash,
+            group: self.group,
+            epoch: self.epoch,
+            author: self.author.clone(),
+            timestamp: self.timestamp,
+            content: event.open(key)?
+        }))
+    }
+}
+
+/// Group keys of all the epochs available to a group member.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct GroupKeys(HashMap<(Hash, u32), GroupKey>);
+
+impl GroupKeys {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Unwrap all the group keys sent to the owner of provided signing key.
+    ///
+    /// Removed members keep keys of the epochs they were members of, so they
+    /// can still read old group messages.
+    pub fn unwrap(
+        index: &Index,
+        storage: &dyn Storage,
+        signing_key: &SigningKey
+    ) -> Result<Self, IndexReadError> {
+        let verifying_key = signing_key.verifying_key();
+
+        let mut keys = Self::default();
+
+        for group in index.groups() {
+            for member in group.all_members() {
+                if member.member != verifying_key {
+                    continue;
+                }
+
+                let Some(message) = storage.read_message(&member.message_hash)? else {
+                    return Err(IndexReadError::NoMessageInStorage(member.message_hash));
+                };
+
+                let Events::GroupMember(event) = Events::from_bytes(message.data())? else {
+                    return Err(IndexReadError::InvalidEventType(member.message_hash));
+                };
+
+                // Admin could wrap a broken key, such membership is ignored.
+                if let Some(key) = event.unwrap_key(signing_key) {
+                    keys.insert(group.message_hash, member.epoch, key);
+                }
+            }
+        }
+
+        Ok(keys)
+    }
+
+    #[inline]
+    pub fn insert(&mut self, group: Hash, epoch: u32, key: GroupKey) {
+        self.0.insert((group, epoch), key);
+    }
+
+    /// Get group key of provided epoch.
+    #[inline]
+    pub fn get(&self, group: &Hash, epoch: u32) -> Option<&GroupKey> {
+        self.0.get(&(*group, epoch))
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
@@ -17,16 +17,40 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 use flowerpot::crypto::hash::Hash;
-use flowerpot::crypto::sign::SignatureError;
+use flowerpot::crypto::sign::{VerifyingKey, SignatureError};
+use flowerpot::address::Address;
+use flowerpot::block::Block;
+use flowerpot::message::Message;
 use flowerpot::storage::{Storage, StorageError};
 
-use crate::{Events, EventDecodeError};
+use time::UtcDateTime;
+
+use crate::{
+    Events, EventDecodeError, DelegationEvent, DelegationAction, RotationEvent,
+    GroupMemberEvent, GroupOpenError, StampPolicy
+};
 
 pub mod post;
 pub mod comment;
+pub mod reaction;
+pub mod delegation;
+pub mod rotation;
+pub mod report;
+pub mod group;
+pub mod notification;
+pub mod statistics;
 
 use post::PostIndex;
 use comment::CommentIndex;
+use reaction::{ReactionIndex, ReactionPositions};
+use delegation::Delegation;
+use rotation::Rotation;
+use report::ReportIndex;
+use group::{
+    GroupIndex, GroupMemberIndex, GroupMessageIndex, GroupMessageInfo, GroupKeys
+};
+use notification::Notification;
+use statistics::Statistics;
 
 #[derive(Debug, thiserror::Error)]
 pub enum IndexUpdateError {
@@ -34,7 +58,10 @@ pub enum IndexUpdateError {
     Storage(#[from] StorageError),
 
     #[error("failed to decode event: {0}")]
-    Event(#[from] EventDecodeError)
+    Event(#[from] EventDecodeError),
+
+    #[error("failed to verify message signature: {0}")]
+    Signature(#[from] SignatureError)
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -58,7 +85,64 @@ pub enum IndexReadError {
     NoBlockWithMessage(Hash),
 
     #[error("message with hash '{}' contained invalid event type", .0.to_base64())]
-    InvalidEventType(Hash)
+    InvalidEventType(Hash),
+
+    #[error("failed to open group message: {0}")]
+    Group(#[from] GroupOpenError)
+}
+
+/// Amount of blocks which messages are decoded concurrently by the parallel
+/// index update.
+#[cfg(feature = "parallel")]
+pub const PARALLEL_BATCH_SIZE: usize = 64;
+
+/// Decode garden event from provided flowerpot message and verify the
+/// message's signature. Return `None` if the message is not a garden event or
+/// doesn't have proof of work stamp required by provided policy.
+///
+/// Stamps are bound to the blockchain address, so events with required stamps
+/// are rejected if the address is unknown.
+fn decode_message(
+    stamp_policy: &StampPolicy,
+    address: Option<&Address>,
+    message: &Message
+) -> Result<Option<(Events, VerifyingKey)>, SignatureError> {
+    let event = match Events::from_bytes(message.data()) {
+        Ok(event) => event,
+
+        Err(err) => {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                ?err,
+                message_hash = message.hash().to_base64(),
+                "skip message which is not a garden event"
+            );
+
+            #[cfg(not(feature = "tracing"))]
+            let _ = err;
+
+            return Ok(None);
+        }
+    };
+
+    let (_, author) = message.verify()?;
+
+    let is_stamped = match address {
+        Some(address) => stamp_policy.check(address, &author, &event, message.data()),
+        None => stamp_policy.required(&event) == 0
+    };
+
+    if !is_stamped {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            message_hash = message.hash().to_base64(),
+            "skip event without required proof of work stamp"
+        );
+
+        return Ok(None);
+    }
+
+    Ok(Some((event, author)))
 }
 
 /// Runtime-built in-memory index of the actual garden state.
@@ -70,31 +154,222 @@ pub enum IndexReadError {
 /// An actual data is kept within the flowerpot blockchain storage and index
 /// only keeps references (hashes) to the stored data.
 #[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Index {
     /// Hash of the indexed flowerpot blockchain root block.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
     root_block: Hash,
 
     /// Hash of the last indexed flowerpot blockchain block.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
     last_block: Hash,
 
     /// List of indexed posts.
     posts: Vec<PostIndex>,
 
     /// List of indexed comments.
-    comments: Vec<CommentIndex>
+    comments: Vec<CommentIndex>,
+
+    /// List of indexed reactions.
+    reactions: Vec<ReactionIndex>,
+
+    /// Positions of the indexed reactions by their identity and target.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    reaction_positions: ReactionPositions,
+
+    /// List of indexed device keys delegations.
+    delegations: Vec<Delegation>,
+
+    /// List of indexed identity key rotations.
+    rotations: Vec<Rotation>,
+
+    /// List of indexed reports.
+    reports: Vec<ReportIndex>,
+
+    /// List of indexed encrypted groups.
+    groups: Vec<GroupIndex>,
+
+    /// List of indexed encrypted group messages.
+    group_messages: Vec<GroupMessageIndex>,
+
+    /// Address of the indexed blockchain. Proof of work stamps are bound to
+    /// it, so it's needed to check stamps of the indexed events.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::optional_address"))]
+    address: Option<Address>,
+
+    /// Proof of work stamps policy. Events without required stamps are not
+    /// indexed.
+    stamp_policy: StampPolicy,
+
+    /// Statistics of the indexed events.
+    statistics: Statistics
 }
 
 impl Index {
+    /// Create new empty index of the blockchain with provided address and
+    /// proof of work stamps policy.
+    pub fn with_stamp_policy(
+        address: impl Into<Address>,
+        stamp_policy: StampPolicy
+    ) -> Self {
+        Self {
+            address: Some(address.into()),
+            stamp_policy,
+            ..Self::default()
+        }
+    }
+
+    /// Get address of the indexed blockchain if it's known.
+    #[inline]
+    pub const fn address(&self) -> Option<&Address> {
+        self.address.as_ref()
+    }
+
+    /// Get proof of work stamps policy of the index.
+    #[inline]
+    pub const fn stamp_policy(&self) -> &StampPolicy {
+        &self.stamp_policy
+    }
+
     /// Update garden index from provided flowerpot blockchain storage.
+    #[inline]
     pub fn update(
         &mut self,
         storage: &dyn Storage
     ) -> Result<(), IndexUpdateError> {
+        self.update_with(storage, |_| ())
+    }
+
+    /// Update garden index from provided flowerpot blockchain storage and
+    /// call provided callback for every indexed event as soon as it's
+    /// processed.
+    pub fn update_with(
+        &mut self,
+        storage: &dyn Storage,
+        mut notify: impl FnMut(Notification)
+    ) -> Result<(), IndexUpdateError> {
+        if !self.prepare_update(storage, &mut notify)? {
+            return Ok(());
+        }
+
+        // Loop over unindexed blocks.
+        while let Some(hash) = storage.next_block(&self.last_block)? {
+            let Some(block) = storage.read_block(&hash)? else {
+                break;
+            };
+
+            // TODO: iterate over ref messages.
+
+            // Iterate over stored messages.
+            for message in block.inline_messages() {
+                let event = decode_message(&self.stamp_policy, self.address.as_ref(), message)?;
+
+                self.index_message(&block, message, event, &mut notify)?;
+            }
+
+            // Update last indexed block hash.
+            self.last_block = hash;
+        }
+
+        Ok(())
+    }
+
+    /// Update garden index from provided flowerpot blockchain storage,
+    /// decoding and verifying messages concurrently.
+    #[cfg(feature = "parallel")]
+    #[inline]
+    pub fn update_parallel(
+        &mut self,
+        storage: &dyn Storage
+    ) -> Result<(), IndexUpdateError> {
+        self.update_parallel_with(storage, |_| ())
+    }
+
+    /// Update garden index from provided flowerpot blockchain storage and
+    /// call provided callback for every indexed event.
+    ///
+    /// Unlike `update_with`, this method reads a batch of blocks and decodes
+    /// and verifies signatures of all their messages concurrently. Decoded
+    /// events are then indexed in the blockchain order, so the resulting index
+    /// and the order of notifications are the same as in the serial update.
+    #[cfg(feature = "parallel")]
+    pub fn update_parallel_with(
+        &mut self,
+        storage: &dyn Storage,
+        mut notify: impl FnMut(Notification)
+    ) -> Result<(), IndexUpdateError> {
+        use rayon::prelude::*;
+
+        if !self.prepare_update(storage, &mut notify)? {
+            return Ok(());
+        }
+
+        loop {
+            // Read a batch of unindexed blocks.
+            let mut blocks = Vec::with_capacity(PARALLEL_BATCH_SIZE);
+            let mut last_block = self.last_block;
+
+            while blocks.len() < PARALLEL_BATCH_SIZE {
+                let Some(hash) = storage.next_block(&last_block)? else {
+                    break;
+                };
+
+                let Some(block) = storage.read_block(&hash)? else {
+                    break;
+                };
+
+                last_block = hash;
+
+                blocks.push((hash, block));
+            }
+
+            if blocks.is_empty() {
+                break;
+            }
+
+            // TODO: iterate over ref messages.
+
+            // Decode and verify messages of all the blocks concurrently.
+            // Collected vectors keep the order of blocks and messages.
+            let stamp_policy = &self.stamp_policy;
+            let address = self.address.as_ref();
+
+            let events = blocks.par_iter()
+                .map(|(_, block)| {
+                    block.inline_messages()
+                        .par_iter()
+                        .map(|message| decode_message(stamp_policy, address, message))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+
+            // Index decoded events in the blockchain order.
+            for ((hash, block), events) in blocks.iter().zip(events) {
+                for (message, event) in block.inline_messages().iter().zip(events) {
+                    self.index_message(block, message, event?, &mut notify)?;
+                }
+
+                // Update last indexed block hash.
+                self.last_block = *hash;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reset the index if it can't be updated from provided storage and store
+    /// hash of the storage's root block. Return `false` if the storage is
+    /// empty and there's nothing to index.
+    fn prepare_update(
+        &mut self,
+        storage: &dyn Storage,
+        notify: &mut impl FnMut(Notification)
+    ) -> Result<bool, IndexUpdateError> {
         let root_block = storage.root_block()?;
 
         // Can't update the index from empty storage.
         let Some(root_block) = root_block else {
-            return Ok(());
+            return Ok(false);
         };
 
         // Drop the index if root block has changed or the last indexed block
@@ -109,56 +384,603 @@ impl Index {
 
             self.posts.clear();
             self.comments.clear();
+            self.reactions.clear();
+            self.reaction_positions = ReactionPositions::default();
+            self.delegations.clear();
+            self.rotations.clear();
+            self.reports.clear();
+            self.groups.clear();
+            self.group_messages.clear();
+
+            self.statistics = Statistics::default();
+
+            notify(Notification::Reset);
         }
 
         // Store indexed blockchain root block hash.
         self.root_block = root_block;
 
-        // Loop over unindexed blocks.
-        while let Some(hash) = storage.next_block(&self.last_block)? {
-            let Some(block) = storage.read_block(&hash)? else {
-                break;
-            };
+        Ok(true)
+    }
 
-            // TODO: iterate over ref messages.
+    /// Index already decoded event of the message stored in provided block.
+    /// `None` event means that the message was rejected.
+    fn index_message(
+        &mut self,
+        block: &Block,
+        message: &Message,
+        event: Option<(Events, VerifyingKey)>,
+        notify: &mut impl FnMut(Notification)
+    ) -> Result<(), IndexUpdateError> {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            root_block = self.root_block.to_base64(),
+            block_hash = block.hash().to_base64(),
+            message_hash = message.hash().to_base64(),
+            "update garden index"
+        );
 
-            // Iterate over stored messages.
-            for message in block.inline_messages() {
-                #[cfg(feature = "tracing")]
-                tracing::debug!(
-                    root_block = root_block.to_base64(),
-                    block_hash = block.hash().to_base64(),
-                    message_hash = message.hash().to_base64(),
-                    "update garden index"
+        let Some((event, author)) = event else {
+            self.statistics.count_rejected();
+
+            return Ok(());
+        };
+
+        self.statistics.count_event(&event, &author, block.timestamp());
+
+        match event {
+            Events::Post(_) => {
+                let post = PostIndex {
+                    block_hash: *block.hash(),
+                    message_hash: *message.hash(),
+                    timestamp: *block.timestamp()
+                };
+
+                self.posts.push(post.clone());
+
+                notify(Notification::Post(post));
+            }
+
+            Events::Comment(comment) => {
+                let comment = CommentIndex {
+                    block_hash: *block.hash(),
+                    message_hash: *message.hash(),
+                    ref_message_hash: *comment.ref_message_hash(),
+                    timestamp: *block.timestamp()
+                };
+
+                self.comments.push(comment.clone());
+
+                notify(Notification::Comment(comment));
+            }
+
+            Events::Reaction(reaction) => {
+                let identity = self.identity(&author, block.timestamp()).clone();
+
+                self.index_reaction(ReactionIndex {
+                    block_hash: *block.hash(),
+                    message_hash: *message.hash(),
+                    ref_message_hash: *reaction.ref_address(),
+                    identity,
+                    reaction: *reaction.reaction(),
+                    timestamp: *block.timestamp()
+                });
+
+                notify(Notification::Reaction {
+                    block_hash: *block.hash(),
+                    message_hash: *message.hash(),
+                    ref_message_hash: *reaction.ref_address(),
+                    reaction: *reaction.reaction()
+                });
+            }
+
+            Events::Delegation(delegation) => {
+                self.index_delegation(
+                    author,
+                    &delegation,
+                    *block.timestamp()
                 );
+            }
 
-                match Events::from_bytes(message.data())? {
-                    Events::Post(_) => {
-                        self.posts.push(PostIndex {
-                            block_hash: *block.hash(),
-                            message_hash: *message.hash()
-                        });
-                    }
-
-                    Events::Comment(comment) => {
-                        self.comments.push(CommentIndex {
-                            block_hash: *block.hash(),
-                            message_hash: *message.hash(),
-                            ref_message_hash: *comment.ref_message_hash()
-                        });
-                    }
-
-                    _ => ()
-                }
+            Events::Rotation(rotation) => {
+                self.index_rotation(
+                    author,
+                    &rotation,
+                    *message.hash(),
+                    *block.timestamp()
+                );
             }
 
-            // Update last indexed block hash.
-            self.last_block = hash;
+            Events::Report(report) => {
+                self.reports.push(ReportIndex {
+                    block_hash: *block.hash(),
+                    message_hash: *message.hash(),
+                    ref_message_hash: *report.ref_message_hash(),
+                    author,
+                    reason: *report.reason(),
+                    timestamp: *block.timestamp()
+                });
+            }
+
+            Events::Group(_) => {
+                self.groups.push(GroupIndex {
+                    block_hash: *block.hash(),
+                    message_hash: *message.hash(),
+                    admin: author,
+                    epoch: 0,
+                    members: Vec::new(),
+                    timestamp: *block.timestamp()
+                });
+            }
+
+            Events::GroupMember(member) => {
+                self.index_group_member(
+                    author,
+                    &member,
+                    *message.hash(),
+                    *block.timestamp()
+                );
+            }
+
+            Events::GroupMessage(group_message) => {
+                // Author must be a member of the message's epoch, and the
+                // epoch must be current when the message is stored, so
+                // removed members can't post after the key rotation (except
+                // within a short grace period).
+                let is_member = self.group(group_message.group())
+                    .is_some_and(|group| {
+                        group.is_member_at(&author, group_message.epoch())
+                            && group.accepts_epoch(group_message.epoch(), block.timestamp())
+                    });
+
+                if is_member {
+                    self.group_messages.push(GroupMessageIndex {
+                        block_hash: *block.hash(),
+                        message_hash: *message.hash(),
+                        group: *group_message.group(),
+                        epoch: group_message.epoch(),
+                        author,
+                        timestamp: *block.timestamp()
+                    });
+                }
+            }
         }
 
         Ok(())
     }
 
+    /// Store reaction replacing the previous reaction of the same identity.
+    fn index_reaction(&mut self, reaction: ReactionIndex) {
+        self.reaction_positions.sync(&self.reactions);
+
+        let position = self.reaction_positions.get(
+            &reaction.identity,
+            &reaction.ref_message_hash
+        );
+
+        match position {
+            // Reactions within the same block are ordered by their message
+            // hashes, otherwise the latest reaction is counted.
+            Some(position) => {
+                let previous = &mut self.reactions[position];
+
+                let is_newer = if previous.block_hash == reaction.block_hash {
+                    reaction.message_hash.as_bytes() < previous.message_hash.as_bytes()
+                } else {
+                    reaction.timestamp >= previous.timestamp
+                };
+
+                if is_newer {
+                    *previous = reaction;
+                }
+            }
+
+            None => {
+                self.reaction_positions.insert(
+                    reaction.identity.clone(),
+                    reaction.ref_message_hash,
+                    self.reactions.len()
+                );
+
+                self.reactions.push(reaction);
+            }
+        }
+    }
+
+    /// Update device keys delegations using delegation event signed by
+    /// provided author key.
+    fn index_delegation(
+        &mut self,
+        author: VerifyingKey,
+        event: &DelegationEvent,
+        timestamp: UtcDateTime
+    ) {
+        let (master_key, device_key) = match event.action() {
+            DelegationAction::Authorize |
+            DelegationAction::Revoke => (&author, event.key()),
+
+            DelegationAction::Accept => (event.key(), &author)
+        };
+
+        // Device keys cannot delegate other keys, and master keys cannot be
+        // devices of other keys.
+        if master_key == device_key
+            || self.delegations.iter().any(|delegation| {
+                delegation.is_valid() && (
+                    &delegation.device_key == master_key
+                    || &delegation.master_key == device_key
+                )
+            })
+        {
+            return;
+        }
+
+        // Device key can be delegated only to one master key at a time.
+        let delegated_to_another = self.delegations.iter()
+            .any(|delegation| {
+                delegation.is_valid()
+                    && &delegation.device_key == device_key
+                    && &delegation.master_key != master_key
+            });
+
+        if delegated_to_another {
+            return;
+        }
+
+        let delegation = self.delegations.iter_mut()
+            .find(|delegation| {
+                delegation.is_active()
+                    && &delegation.master_key == master_key
+                    && &delegation.device_key == device_key
+            });
+
+        match (event.action(), delegation) {
+            (DelegationAction::Authorize, Some(delegation)) => {
+                delegation.authorized_at.get_or_insert(timestamp);
+            }
+
+            (DelegationAction::Accept, Some(delegation)) => {
+                delegation.accepted_at.get_or_insert(timestamp);
+            }
+
+            (DelegationAction::Revoke, Some(delegation)) => {
+                delegation.revoked_at = Some(timestamp);
+            }
+
+            (DelegationAction::Authorize, None) => {
+                self.delegations.push(Delegation {
+                    master_key: master_key.clone(),
+                    device_key: device_key.clone(),
+                    authorized_at: Some(timestamp),
+                    accepted_at: None,
+                    revoked_at: None
+                });
+            }
+
+            (DelegationAction::Accept, None) => {
+                self.delegations.push(Delegation {
+                    master_key: master_key.clone(),
+                    device_key: device_key.clone(),
+                    authorized_at: None,
+                    accepted_at: Some(timestamp),
+                    revoked_at: None
+                });
+            }
+
+            (DelegationAction::Revoke, None) => ()
+        }
+    }
+
+    /// Store identity key rotation signed by provided author (old) key.
+    fn index_rotation(
+        &mut self,
+        author: VerifyingKey,
+        event: &RotationEvent,
+        message_hash: Hash,
+        timestamp: UtcDateTime
+    ) {
+        // Rotation must be signed by the new key as well. Malformed signature
+        // is treated as invalid one, otherwise a single broken rotation would
+        // stop indexing of the whole chain.
+        if !event.verify(&author).unwrap_or(false) {
+            return;
+        }
+
+        // Each key can be rotated only once, and new key must never be used
+        // in other rotations. This prevents rotation cycles.
+        let is_used = self.rotations.iter().any(|rotation| {
+            rotation.old_key == author
+                || rotation.old_key == *event.new_key()
+                || rotation.new_key == *event.new_key()
+        });
+
+        if author == *event.new_key() || is_used {
+            return;
+        }
+
+        self.rotations.push(Rotation {
+            message_hash,
+            old_key: author.clone(),
+            new_key: event.new_key().clone(),
+            timestamp
+        });
+
+        self.rotate_reactions(&author, event.new_key());
+    }
+
+    /// Move reactions of the rotated identity key to the new key. Reactions
+    /// are deduplicated by identity, so only the latest reaction of the
+    /// identity to each post or comment is kept.
+    fn rotate_reactions(&mut self, old_key: &VerifyingKey, new_key: &VerifyingKey) {
+        if !self.reactions.iter().any(|reaction| &reaction.identity == old_key) {
+            return;
+        }
+
+        let reactions = std::mem::take(&mut self.reactions);
+
+        self.reaction_positions = ReactionPositions::default();
+
+        for mut reaction in reactions {
+            if &reaction.identity == old_key {
+                reaction.identity = new_key.clone();
+            }
+
+            self.index_reaction(reaction);
+        }
+    }
+
+    /// Add group member signed by provided author key.
+    fn index_group_member(
+        &mut self,
+        author: VerifyingKey,
+        event: &GroupMemberEvent,
+        message_hash: Hash,
+        timestamp: UtcDateTime
+    ) {
+        let Some(group) = self.groups.iter_mut()
+            .find(|group| &group.message_hash == event.group())
+        else {
+            return;
+        };
+
+        // Only the group admin can add members.
+        if group.admin != author {
+            return;
+        }
+
+        // Members can be added to the current epoch, or to the next one which
+        // rotates the group key. Old epochs can't be changed.
+        if event.epoch() != group.epoch
+            && Some(event.epoch()) != group.epoch.checked_add(1)
+        {
+            return;
+        }
+
+        let is_added = group.members.iter().any(|member| {
+            member.epoch == event.epoch() && &member.member == event.member()
+        });
+
+        if is_added {
+            return;
+        }
+
+        group.epoch = event.epoch();
+
+        group.members.push(GroupMemberIndex {
+            message_hash,
+            member: event.member().clone(),
+            epoch: event.epoch(),
+            timestamp
+        });
+    }
+
+    /// Get the latest key of an identity by following key rotations starting
+    /// from provided key.
+    pub fn latest_key<'a>(&'a self, key: &'a VerifyingKey) -> &'a VerifyingKey {
+        let mut key = key;
+
+        while let Some(rotation) = self.rotations.iter()
+            .find(|rotation| &rotation.old_key == key)
+        {
+            key = &rotation.new_key;
+        }
+
+        key
+    }
+
+    /// Get identity key of provided event author at provided timestamp.
+    ///
+    /// If the author is a device key delegated by a master key at that time
+    /// then the master key is used. The result is then resolved to the latest
+    /// rotated identity key, so all the events of an identity are attributed
+    /// to its current key.
+    pub fn identity<'a>(
+        &'a self,
+        author: &'a VerifyingKey,
+        timestamp: &UtcDateTime
+    ) -> &'a VerifyingKey {
+        let key = self.delegations.iter()
+            .find(|delegation| {
+                &delegation.device_key == author
+                    && delegation.is_valid_at(timestamp)
+            })
+            .map(|delegation| &delegation.master_key)
+            .unwrap_or(author);
+
+        self.latest_key(key)
+    }
+
+    /// Get list of all the keys which belong to the same identity as provided
+    /// key: all the rotated keys and their delegated device keys.
+    ///
+    /// Should be used to search events of an identity by author.
+    pub fn identity_keys<'a>(
+        &'a self,
+        key: &'a VerifyingKey
+    ) -> Vec<&'a VerifyingKey> {
+        let mut key = self.latest_key(key);
+        let mut keys = vec![key];
+
+        // Go back over the rotations chain.
+        while let Some(rotation) = self.rotations.iter()
+            .find(|rotation| &rotation.new_key == key)
+        {
+            key = &rotation.old_key;
+
+            keys.push(key);
+        }
+
+        let devices = self.delegations.iter()
+            .filter(|delegation| delegation.valid_since().is_some())
+            .filter(|delegation| keys.contains(&&delegation.master_key))
+            .map(|delegation| &delegation.device_key)
+            .collect::<Vec<_>>();
+
+        keys.extend(devices);
+
+        keys
+    }
+
+    /// Check if provided key was revoked by a key rotation before provided
+    /// timestamp. Events signed by revoked keys can be made by somebody who
+    /// stole the key.
+    pub fn is_revoked_key(
+        &self,
+        key: &VerifyingKey,
+        timestamp: &UtcDateTime
+    ) -> bool {
+        self.rotations.iter().any(|rotation| {
+            &rotation.old_key == key && &rotation.timestamp <= timestamp
+        })
+    }
+
+    /// Get iterator over all the indexed reports.
+    #[inline]
+    pub fn reports(&self) -> impl Iterator<Item = &ReportIndex> {
+        self.reports.iter()
+    }
+
+    /// Get iterator over all the indexed encrypted groups.
+    #[inline]
+    pub fn groups(&self) -> impl Iterator<Item = &GroupIndex> {
+        self.groups.iter()
+    }
+
+    /// Get group with provided creation message hash.
+    pub fn group(&self, group: &Hash) -> Option<&GroupIndex> {
+        self.groups.iter().find(|indexed| &indexed.message_hash == group)
+    }
+
+    /// Get iterator over all the groups where provided key is a member at the
+    /// current epoch.
+    pub fn groups_of<'index>(
+        &'index self,
+        key: &'index VerifyingKey
+    ) -> impl Iterator<Item = &'index GroupIndex> {
+        self.groups.iter().filter(move |group| group.is_member(key))
+    }
+
+    /// Get iterator over all the encrypted messages of a group with provided
+    /// creation message hash.
+    ///
+    /// Messages can be decrypted only using the group keys unwrapped by a
+    /// member (see `GroupKeys`).
+    pub fn group_messages<'index>(
+        &'index self,
+        group: &Hash
+    ) -> impl Iterator<Item = &'index GroupMessageIndex> {
+        let group = *group;
+
+        self.group_messages.iter().filter(move |message| {
+            message.group == group
+        })
+    }
+
+    /// Read and decrypt all the messages of a group with provided creation
+    /// message hash. Messages of epochs which keys are not provided are
+    /// skipped, so only members can see the group content.
+    ///
+    /// Messages which can't be decrypted or decoded are skipped as well, so
+    /// a member can't hide the group content from others by sending a broken
+    /// message. Only storage errors abort the reading.
+    pub fn read_group_messages(
+        &self,
+        group: &Hash,
+        storage: &dyn Storage,
+        keys: &GroupKeys
+    ) -> Result<Vec<GroupMessageInfo>, IndexReadError> {
+        let mut messages = Vec::new();
+
+        for message in self.group_messages(group) {
+            match message.read(storage, keys) {
+                Ok(Some(message)) => messages.push(message),
+                Ok(None) => (),
+
+                Err(IndexReadError::Storage(err)) => return Err(err.into()),
+
+                Err(err) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(
+                        ?err,
+                        message_hash = message.message_hash().to_base64(),
+                        "skip group message which can't be read"
+                    );
+
+                    #[cfg(not(feature = "tracing"))]
+                    let _ = err;
+                }
+            }
+        }
+
+        Ok(messages)
+    }
+
+    /// Get statistics of the indexed events.
+    #[inline]
+    pub const fn statistics(&self) -> &Statistics {
+        &self.statistics
+    }
+
+    /// Get amount of blocks in provided flowerpot blockchain storage which
+    /// are not indexed yet.
+    pub fn lag(&self, storage: &dyn Storage) -> Result<u64, StorageError> {
+        let mut lag = 0;
+        let mut block = self.last_block;
+
+        while let Some(next) = storage.next_block(&block)? {
+            lag += 1;
+            block = next;
+        }
+
+        Ok(lag)
+    }
+
+    /// Get iterator over all the reports of a post or a comment with provided
+    /// message hash.
+    pub fn reports_of<'index>(
+        &'index self,
+        message_hash: &Hash
+    ) -> impl Iterator<Item = &'index ReportIndex> {
+        let message_hash = *message_hash;
+
+        self.reports.iter().filter(move |report| {
+            report.ref_message_hash == message_hash
+        })
+    }
+
+    /// Get iterator over all the indexed identity key rotations.
+    #[inline]
+    pub fn rotations(&self) -> impl Iterator<Item = &Rotation> {
+        self.rotations.iter()
+    }
+
+    /// Get iterator over all the indexed device keys delegations, including
+    /// revoked and not yet accepted ones.
+    #[inline]
+    pub fn delegations(&self) -> impl Iterator<Item = &Delegation> {
+        self.delegations.iter()
+    }
+
     /// Get iterator over all the indexed posts.
     #[inline(always)]
     pub const fn posts(&self) -> IndexedPostsIter<'_> {
@@ -173,6 +995,39 @@ impl Index {
     pub const fn comments(&self) -> IndexedCommentsIter<'_> {
         IndexedCommentsIter(self, 0)
     }
+
+    /// Get iterator over all the comments to a post or a comment with
+    /// provided message hash.
+    pub fn comments_of<'index>(
+        &'index self,
+        message_hash: &Hash
+    ) -> impl Iterator<Item = &'index CommentIndex> {
+        let message_hash = *message_hash;
+
+        self.comments.iter().filter(move |comment| {
+            comment.ref_message_hash == message_hash
+        })
+    }
+
+    /// Get iterator over all the indexed reactions. Only the counted reaction
+    /// of each identity is returned.
+    #[inline]
+    pub fn reactions(&self) -> impl Iterator<Item = &ReactionIndex> {
+        self.reactions.iter()
+    }
+
+    /// Get iterator over all the reactions to a post or a comment with
+    /// provided message hash.
+    pub fn reactions_of<'index>(
+        &'index self,
+        message_hash: &Hash
+    ) -> impl Iterator<Item = &'index ReactionIndex> {
+        let message_hash = *message_hash;
+
+        self.reactions.iter().filter(move |reaction| {
+            reaction.ref_message_hash == message_hash
+        })
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
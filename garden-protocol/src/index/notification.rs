@@ -0,0 +1,56 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use flowerpot::crypto::hash::Hash;
+
+use crate::Reaction;
+
+use super::post::PostIndex;
+use super::comment::CommentIndex;
+
+/// Notification about a change of the garden index emitted while it's being
+/// updated.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Notification {
+    /// The index was reset because the blockchain was changed (e.g. its root
+    /// block was replaced or the last indexed block was removed by reorg).
+    /// All the previously notified events must be dropped and will be
+    /// notified again.
+    Reset,
+
+    /// New post was indexed.
+    Post(PostIndex),
+
+    /// New comment was indexed.
+    Comment(CommentIndex),
+
+    /// New reaction was processed.
+    Reaction {
+        /// Hash of the block where the reaction is stored.
+        block_hash: Hash,
+
+        /// Hash of the message where the reaction is stored.
+        message_hash: Hash,
+
+        /// Hash of the message the reaction was made to.
+        ref_message_hash: Hash,
+
+        /// The reaction.
+        reaction: Reaction
+    }
+}
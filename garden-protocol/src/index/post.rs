@@ -18,6 +18,7 @@
 
 use flowerpot::crypto::hash::Hash;
 use flowerpot::crypto::sign::VerifyingKey;
+use flowerpot::message::Message;
 use flowerpot::storage::Storage;
 
 use time::UtcDateTime;
@@ -29,20 +30,35 @@ use super::comment::CommentIndex;
 
 /// Information about a garden post.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PostInfo {
     /// Hash of the block of the flowerpot blockchain where the post info is
     /// stored.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
     pub block_hash: Hash,
 
     /// Hash of the message of the flowerpot blockchain where the post info is
     /// stored (practically the address of the post).
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
     pub message_hash: Hash,
 
     /// Flowerpot verifying key of the post author.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::verifying_key"))]
     pub author: VerifyingKey,
 
+    /// Identity key of the post author. Differs from `author` if the post
+    /// was signed by a device key delegated from a master identity key.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::verifying_key"))]
+    pub identity: VerifyingKey,
+
+    /// The post was signed by a key which was revoked by identity key rotation
+    /// before the post was created. Such posts can be made by somebody who
+    /// stole the revoked key.
+    pub signed_by_revoked_key: bool,
+
     /// Timestamp when, approximately, the post was created. Derived from the
     /// block where the post is stored on the flowerpot blockchain.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::timestamp"))]
     pub timestamp: UtcDateTime,
 
     /// Content of the post.
@@ -52,14 +68,35 @@ pub struct PostInfo {
     pub tags: Box<[Tag]>
 }
 
+impl PostInfo {
+    /// Check if the post has the `nsfw` tag.
+    #[inline]
+    pub fn is_nsfw(&self) -> bool {
+        self.tags.iter().any(Tag::is_nsfw)
+    }
+
+    /// Check if the post has the `spoiler` tag.
+    #[inline]
+    pub fn is_spoiler(&self) -> bool {
+        self.tags.iter().any(Tag::is_spoiler)
+    }
+}
+
 /// Index of a garden post stored in flowerpot blockchain.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PostIndex {
     /// Block hash where the current post is stored.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
     pub(super) block_hash: Hash,
 
     /// Message hash where the current post is stored.
-    pub(super) message_hash: Hash
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
+    pub(super) message_hash: Hash,
+
+    /// Timestamp of the block where the current post is stored.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::timestamp"))]
+    pub(super) timestamp: UtcDateTime
 }
 
 impl PostIndex {
@@ -73,32 +110,51 @@ impl PostIndex {
         &self.message_hash
     }
 
+    #[inline]
+    pub const fn timestamp(&self) -> &UtcDateTime {
+        &self.timestamp
+    }
+
     /// Try to read indexed post from provided flowerpot blockchain storage.
+    ///
+    /// Index is used to resolve the post author's identity.
     pub fn read(
         &self,
+        index: &Index,
         storage: &dyn Storage
     ) -> Result<PostInfo, IndexReadError> {
-        // FIXME: we don't need to read the whole block, only some of its
-        //        metadata, but there's currently no logic for it.
-        let Some(block) = storage.read_block(&self.block_hash)? else {
-            return Err(IndexReadError::NoMessageInStorage(self.block_hash));
-        };
-
         let Some(message) = storage.read_message(&self.message_hash)? else {
             return Err(IndexReadError::NoMessageInStorage(self.message_hash));
         };
 
+        self.read_message(index, &message)
+    }
+
+    /// Try to read indexed post from already fetched flowerpot message. This
+    /// allows to read messages from the storage and decode them separately.
+    pub fn read_message(
+        &self,
+        index: &Index,
+        message: &Message
+    ) -> Result<PostInfo, IndexReadError> {
+        debug_assert_eq!(message.hash(), &self.message_hash);
+
         let Events::Post(post) = Events::from_bytes(message.data())? else {
             return Err(IndexReadError::InvalidEventType(self.message_hash));
         };
 
         let (_, author) = message.verify()?;
 
+        let identity = index.identity(&author, &self.timestamp).clone();
+        let signed_by_revoked_key = index.is_revoked_key(&author, &self.timestamp);
+
         Ok(PostInfo {
             block_hash: self.block_hash,
             message_hash: self.message_hash,
             author,
-            timestamp: *block.timestamp(),
+            identity,
+            signed_by_revoked_key,
+            timestamp: self.timestamp,
             content: post.content().clone(),
             tags: post.tags().to_vec().into_boxed_slice()
         })
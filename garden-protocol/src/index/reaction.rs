@@ -0,0 +1,143 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+
+use flowerpot::crypto::hash::Hash;
+use flowerpot::crypto::sign::VerifyingKey;
+
+use time::UtcDateTime;
+
+use crate::Reaction;
+
+/// Indexed reaction. Reactions are small so the reaction itself is stored in
+/// the index.
+///
+/// Only one reaction of an identity to a post or a comment is indexed: the
+/// latest one, or the one with lower message hash if multiple reactions are
+/// stored within the same block. Reactions made before an identity key
+/// rotation belong to the new key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReactionIndex {
+    /// Block hash where the current reaction is stored.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
+    pub(super) block_hash: Hash,
+
+    /// Message hash where the current reaction is stored.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
+    pub(super) message_hash: Hash,
+
+    /// Message hash of a post or a comment the current reaction was made to.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
+    pub(super) ref_message_hash: Hash,
+
+    /// Latest identity key of the reaction author.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::verifying_key"))]
+    pub(super) identity: VerifyingKey,
+
+    /// The reaction.
+    pub(super) reaction: Reaction,
+
+    /// Timestamp of the block where the current reaction is stored.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::timestamp"))]
+    pub(super) timestamp: UtcDateTime
+}
+
+impl ReactionIndex {
+    #[inline]
+    pub const fn block_hash(&self) -> &Hash {
+        &self.block_hash
+    }
+
+    #[inline]
+    pub const fn message_hash(&self) -> &Hash {
+        &self.message_hash
+    }
+
+    #[inline]
+    pub const fn ref_message_hash(&self) -> &Hash {
+        &self.ref_message_hash
+    }
+
+    #[inline]
+    pub const fn identity(&self) -> &VerifyingKey {
+        &self.identity
+    }
+
+    #[inline]
+    pub const fn reaction(&self) -> &Reaction {
+        &self.reaction
+    }
+
+    #[inline]
+    pub const fn timestamp(&self) -> &UtcDateTime {
+        &self.timestamp
+    }
+}
+
+/// Positions of the indexed reactions in the index's list of reactions keyed
+/// by their identity and target message hash.
+///
+/// Positions are derived from the list of reactions, so they're not
+/// serialized, compared or hashed. They're rebuilt when the list was changed
+/// without them (e.g. after deserialization or index reset).
+#[derive(Default, Debug, Clone)]
+pub(super) struct ReactionPositions(HashMap<(VerifyingKey, Hash), usize>);
+
+impl ReactionPositions {
+    /// Rebuild positions if they don't match provided list of reactions.
+    pub(super) fn sync(&mut self, reactions: &[ReactionIndex]) {
+        if self.0.len() == reactions.len() {
+            return;
+        }
+
+        self.0 = reactions.iter()
+            .enumerate()
+            .map(|(i, reaction)| {
+                ((reaction.identity.clone(), reaction.ref_message_hash), i)
+            })
+            .collect();
+    }
+
+    /// Get position of the reaction of provided identity to provided post or
+    /// comment.
+    #[inline]
+    pub(super) fn get(&self, identity: &VerifyingKey, target: &Hash) -> Option<usize> {
+        self.0.get(&(identity.clone(), *target)).copied()
+    }
+
+    #[inline]
+    pub(super) fn insert(&mut self, identity: VerifyingKey, target: Hash, position: usize) {
+        self.0.insert((identity, target), position);
+    }
+}
+
+impl PartialEq for ReactionPositions {
+    #[inline(always)]
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for ReactionPositions {}
+
+impl std::hash::Hash for ReactionPositions {
+    #[inline(always)]
+    fn hash<H: std::hash::Hasher>(&self, _state: &mut H) {}
+}
@@ -0,0 +1,148 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use flowerpot::crypto::hash::Hash;
+use flowerpot::crypto::sign::VerifyingKey;
+use flowerpot::storage::Storage;
+
+use time::UtcDateTime;
+
+use crate::{Events, Content, ReportReason};
+
+use super::IndexReadError;
+
+/// Information about a garden report of a post or a comment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReportInfo {
+    /// Hash of the block of the flowerpot blockchain where the report is
+    /// stored.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
+    pub block_hash: Hash,
+
+    /// Hash of the message of the flowerpot blockchain where the report is
+    /// stored.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
+    pub message_hash: Hash,
+
+    /// Hash of the message of the flowerpot blockchain where the reported
+    /// post or comment is stored.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
+    pub ref_message_hash: Hash,
+
+    /// Flowerpot verifying key of the report author.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::verifying_key"))]
+    pub author: VerifyingKey,
+
+    /// Timestamp when, approximately, the report was created. Derived from the
+    /// block where the report is stored on the flowerpot blockchain.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::timestamp"))]
+    pub timestamp: UtcDateTime,
+
+    /// Reason of the report.
+    pub reason: ReportReason,
+
+    /// Note of the report author.
+    pub note: Content
+}
+
+/// Indexed report. Unlike posts and comments, report author and reason are
+/// stored in the index so clients could use them to moderate content without
+/// reading the storage.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReportIndex {
+    /// Block hash where the current report is stored.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
+    pub(super) block_hash: Hash,
+
+    /// Message hash where the current report is stored.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
+    pub(super) message_hash: Hash,
+
+    /// Message hash of a post or a comment reported by the current report.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
+    pub(super) ref_message_hash: Hash,
+
+    /// Verifying key of the report author.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::verifying_key"))]
+    pub(super) author: VerifyingKey,
+
+    /// Reason of the report.
+    pub(super) reason: ReportReason,
+
+    /// Timestamp of the block where the current report is stored.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::timestamp"))]
+    pub(super) timestamp: UtcDateTime
+}
+
+impl ReportIndex {
+    #[inline]
+    pub const fn block_hash(&self) -> &Hash {
+        &self.block_hash
+    }
+
+    #[inline]
+    pub const fn message_hash(&self) -> &Hash {
+        &self.message_hash
+    }
+
+    #[inline]
+    pub const fn ref_message_hash(&self) -> &Hash {
+        &self.ref_message_hash
+    }
+
+    #[inline]
+    pub const fn author(&self) -> &VerifyingKey {
+        &self.author
+    }
+
+    #[inline]
+    pub const fn reason(&self) -> &ReportReason {
+        &self.reason
+    }
+
+    #[inline]
+    pub const fn timestamp(&self) -> &UtcDateTime {
+        &self.timestamp
+    }
+
+    /// Try to read indexed report from provided flowerpot blockchain storage.
+    pub fn read(
+        &self,
+        storage: &dyn Storage
+    ) -> Result<ReportInfo, IndexReadError> {
+        let Some(message) = storage.read_message(&self.message_hash)? else {
+            return Err(IndexReadError::NoMessageInStorage(self.message_hash));
+        };
+
+        let Events::Report(report) = Events::from_bytes(message.data())? else {
+            return Err(IndexReadError::InvalidEventType(self.message_hash));
+        };
+
+        Ok(ReportInfo {
+            block_hash: self.block_hash,
+            message_hash: self.message_hash,
+            ref_message_hash: self.ref_message_hash,
+            author: self.author.clone(),
+            timestamp: self.timestamp,
+            reason: self.reason,
+            note: report.note().clone()
+        })
+    }
+}
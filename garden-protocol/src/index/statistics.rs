@@ -0,0 +1,224 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::BTreeMap;
+
+use flowerpot::crypto::sign::VerifyingKey;
+
+use time::{UtcDateTime, Time};
+
+use crate::Events;
+
+/// Statistics of the indexed garden events. They're updated incrementally
+/// with the index itself.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Statistics {
+    posts: u64,
+    comments: u64,
+    reactions: u64,
+    delegations: u64,
+    rotations: u64,
+    reports: u64,
+    groups: u64,
+    group_members: u64,
+    group_messages: u64,
+
+    /// Amount of messages which couldn't be decoded or didn't have proof of
+    /// work stamps required by the index policy.
+    rejected: u64,
+
+    /// Amount of events made by each author (base64 encoded key).
+    authors: BTreeMap<String, u64>,
+
+    /// Amount of posts with each tag.
+    tags: BTreeMap<String, u64>,
+
+    /// Amount of events per hour (unix timestamp of the hour start).
+    hours: BTreeMap<i64, u64>
+}
+
+impl Statistics {
+    const HOUR: i64 = 60 * 60;
+
+    /// Count indexed event made by provided author and stored in a block with
+    /// provided timestamp.
+    pub(super) fn count_event(
+        &mut self,
+        event: &Events,
+        author: &VerifyingKey,
+        timestamp: &UtcDateTime
+    ) {
+        match event {
+            Events::Post(post) => {
+                self.posts += 1;
+
+                for tag in post.tags() {
+                    *self.tags.entry(String::from(tag.as_str())).or_default() += 1;
+                }
+            }
+
+            Events::Comment(_)    => self.comments += 1,
+            Events::Reaction(_)   => self.reactions += 1,
+            Events::Delegation(_) => self.delegations += 1,
+            Events::Rotation(_)   => self.rotations += 1,
+            Events::Report(_)     => self.reports += 1,
+
+            Events::Group(_)        => self.groups += 1,
+            Events::GroupMember(_)  => self.group_members += 1,
+            Events::GroupMessage(_) => self.group_messages += 1
+        }
+
+        *self.authors.entry(author.to_base64()).or_default() += 1;
+
+        let hour = timestamp.unix_timestamp().div_euclid(Self::HOUR) * Self::HOUR;
+
+        *self.hours.entry(hour).or_default() += 1;
+    }
+
+    /// Count rejected message.
+    #[inline]
+    pub(super) fn count_rejected(&mut self) {
+        self.rejected += 1;
+    }
+
+    #[inline]
+    pub const fn posts(&self) -> u64 {
+        self.posts
+    }
+
+    #[inline]
+    pub const fn comments(&self) -> u64 {
+        self.comments
+    }
+
+    #[inline]
+    pub const fn reactions(&self) -> u64 {
+        self.reactions
+    }
+
+    #[inline]
+    pub const fn delegations(&self) -> u64 {
+        self.delegations
+    }
+
+    #[inline]
+    pub const fn rotations(&self) -> u64 {
+        self.rotations
+    }
+
+    #[inline]
+    pub const fn reports(&self) -> u64 {
+        self.reports
+    }
+
+    #[inline]
+    pub const fn groups(&self) -> u64 {
+        self.groups
+    }
+
+    #[inline]
+    pub const fn group_members(&self) -> u64 {
+        self.group_members
+    }
+
+    #[inline]
+    pub const fn group_messages(&self) -> u64 {
+        self.group_messages
+    }
+
+    /// Total amount of indexed events.
+    #[inline]
+    pub const fn total(&self) -> u64 {
+        self.posts
+            + self.comments
+            + self.reactions
+            + self.delegations
+            + self.rotations
+            + self.reports
+            + self.groups
+            + self.group_members
+            + self.group_messages
+    }
+
+    /// Amount of rejected messages.
+    #[inline]
+    pub const fn rejected(&self) -> u64 {
+        self.rejected
+    }
+
+    /// Amount of unique events authors (signing keys).
+    #[inline]
+    pub fn unique_authors(&self) -> usize {
+        self.authors.len()
+    }
+
+    /// Get iterator over amounts of events per hour, sorted by time.
+    pub fn per_hour(&self) -> impl Iterator<Item = (UtcDateTime, u64)> {
+        self.hours.iter().flat_map(|(hour, amount)| {
+            UtcDateTime::from_unix_timestamp(*hour)
+                .ok()
+                .map(|hour| (hour, *amount))
+        })
+    }
+
+    /// Get amounts of events per day, sorted by time.
+    pub fn per_day(&self) -> Vec<(UtcDateTime, u64)> {
+        let mut days = Vec::<(UtcDateTime, u64)>::new();
+
+        for (hour, amount) in self.per_hour() {
+            let day = hour.replace_time(Time::MIDNIGHT);
+
+            match days.last_mut() {
+                Some((last, total)) if *last == day => *total += amount,
+                _ => days.push((day, amount))
+            }
+        }
+
+        days
+    }
+
+    /// Get up to `limit` most used tags with amounts of posts using them.
+    pub fn top_tags(&self, limit: usize) -> Vec<(&str, u64)> {
+        top(&self.tags, limit)
+    }
+
+    /// Get up to `limit` most active authors with amounts of their events.
+    pub fn top_authors(&self, limit: usize) -> Vec<(VerifyingKey, u64)> {
+        top(&self.authors, limit)
+            .into_iter()
+            .flat_map(|(author, amount)| {
+                VerifyingKey::from_base64(author)
+                    .map(|author| (author, amount))
+            })
+            .collect()
+    }
+}
+
+/// Get up to `limit` entries with the largest values. Entries with the same
+/// values are sorted by their keys to keep the output stable.
+fn top(entries: &BTreeMap<String, u64>, limit: usize) -> Vec<(&str, u64)> {
+    let mut entries = entries.iter()
+        .map(|(key, value)| (key.as_str(), *value))
+        .collect::<Vec<_>>();
+
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    entries.truncate(limit);
+
+    entries
+}
@@ -16,23 +16,65 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
 use flowerpot::crypto::sign::VerifyingKey;
+
+#[cfg(feature = "std")]
 use flowerpot::address::Address;
+
+#[cfg(feature = "std")]
 use flowerpot::message::Message;
 
 mod post;
 mod comment;
 mod reaction;
+mod delegation;
+mod rotation;
+mod report;
+mod group;
+mod encoding;
+mod stamp;
+
+#[cfg(feature = "serde")]
+mod serde_utils;
 
+#[cfg(feature = "std")]
 pub mod index;
+
+#[cfg(feature = "std")]
 pub mod handler;
 
-pub use post::{Content, Tag, PostEvent, PostEventError};
+#[cfg(feature = "std")]
+pub mod feed;
+
+#[cfg(feature = "archive")]
+pub mod archive;
+
+#[cfg(feature = "testing")]
+pub mod testing;
+
+pub use post::{Content, Tag, PostEvent, PostEventError, ValidationError};
 pub use comment::{CommentEvent, CommentEventError};
 pub use reaction::{Reaction, ReactionEvent, ReactionEventError};
+pub use delegation::{DelegationAction, DelegationEvent, DelegationEventError};
+pub use rotation::{RotationEvent, RotationEventError};
+pub use report::{ReportReason, ReportEvent, ReportEventError};
+pub use group::{
+    GroupKey, GroupContent, GroupEvent, GroupMemberEvent, GroupMessageEvent,
+    GroupEventError, GroupOpenError
+};
+pub use encoding::{Encoding, EncodingError};
+pub use stamp::{Stamp, StampPolicy};
 
 pub trait Event {
-    type Error: std::error::Error;
+    type Error: core::error::Error;
 
     /// Convert event to the binary representation.
     fn to_bytes(&self) -> Box<[u8]>;
@@ -56,6 +98,12 @@ pub enum EventDecodeError {
     #[error("unknown event: {0}")]
     UnknownEvent(u16),
 
+    #[error("stamped event cannot contain another stamped event")]
+    NestedStamp,
+
+    #[error("failed to decode event body: {0}")]
+    Encoding(#[from] EncodingError),
+
     #[error(transparent)]
     Post(#[from] PostEventError),
 
@@ -63,62 +111,137 @@ pub enum EventDecodeError {
     Comment(#[from] CommentEventError),
 
     #[error(transparent)]
-    Reaction(#[from] ReactionEventError)
+    Reaction(#[from] ReactionEventError),
+
+    #[error(transparent)]
+    Delegation(#[from] DelegationEventError),
+
+    #[error(transparent)]
+    Rotation(#[from] RotationEventError),
+
+    #[error(transparent)]
+    Report(#[from] ReportEventError),
+
+    #[error(transparent)]
+    Group(#[from] GroupEventError)
 }
 
 /// Event is the main component of the garden protocol. It encodes some action
 /// performed in the network, stored as flowerpot blockchain transaction.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
 pub enum Events {
     Post(PostEvent),
     Comment(CommentEvent),
-    Reaction(ReactionEvent)
+    Reaction(ReactionEvent),
+    Delegation(DelegationEvent),
+    Rotation(RotationEvent),
+    Report(ReportEvent),
+    Group(GroupEvent),
+    GroupMember(GroupMemberEvent),
+    GroupMessage(GroupMessageEvent)
 }
 
 impl Events {
-    pub const V1_POST: u16     = 0;
-    pub const V1_COMMENT: u16  = 1;
-    pub const V1_REACTION: u16 = 2;
+    pub const V1_POST: u16            = 0;
+    pub const V1_COMMENT: u16         = 1;
+    pub const V1_REACTION: u16        = 2;
+    pub const V1_ENCODED_POST: u16    = 3;
+    pub const V1_ENCODED_COMMENT: u16 = 4;
+    pub const V1_DELEGATION: u16      = 5;
+    pub const V1_ROTATION: u16        = 6;
+    pub const V1_REPORT: u16          = 7;
+    pub const V1_STAMPED: u16         = 8;
+    pub const V1_GROUP: u16           = 9;
+    pub const V1_GROUP_MEMBER: u16    = 10;
+    pub const V1_GROUP_MESSAGE: u16   = 11;
 
+    /// Convert event to the binary representation.
+    ///
+    /// Posts and comments are compressed if it makes their binary
+    /// representation smaller.
     pub fn to_bytes(&self) -> Box<[u8]> {
-        fn alloc(event: &impl Event) -> Vec<u8> {
-            match event.size_hint() {
-                Some(size) => Vec::with_capacity(size + 2),
-                None => Vec::new()
-            }
-        }
+        let plain = self.to_bytes_with_encoding(Encoding::Plain);
 
         match self {
-            Self::Post(event) => {
-                let mut buf = alloc(event);
+            Self::Post(_) | Self::Comment(_) => {
+                let encoded = self.to_bytes_with_encoding(Encoding::Deflate);
+
+                if encoded.len() < plain.len() {
+                    encoded
+                } else {
+                    plain
+                }
+            }
 
-                buf.extend(Self::V1_POST.to_le_bytes());
-                buf.extend(event.to_bytes());
+            _ => plain
+        }
+    }
 
-                buf.into_boxed_slice()
-            }
+    /// Convert event to the binary representation using provided body
+    /// encoding.
+    ///
+    /// Only posts and comments support body encoding, other events are always
+    /// stored as is. `Encoding::Plain` produces the original V1 format.
+    pub fn to_bytes_with_encoding(&self, encoding: Encoding) -> Box<[u8]> {
+        fn plain(id: u16, event: &impl Event) -> Box<[u8]> {
+            let mut buf = match event.size_hint() {
+                Some(size) => Vec::with_capacity(size + 2),
+                None => Vec::new()
+            };
 
-            Self::Comment(event) => {
-                let mut buf = alloc(event);
+            buf.extend(id.to_le_bytes());
+            buf.extend(event.to_bytes());
 
-                buf.extend(Self::V1_COMMENT.to_le_bytes());
-                buf.extend(event.to_bytes());
+            buf.into_boxed_slice()
+        }
 
-                buf.into_boxed_slice()
-            }
+        fn encode(id: u16, event: &impl Event, encoding: Encoding) -> Box<[u8]> {
+            let body = encoding.encode(&event.to_bytes());
 
-            Self::Reaction(event) => {
-                let mut buf = alloc(event);
+            let mut buf = Vec::with_capacity(body.len() + 3);
 
-                buf.extend(Self::V1_REACTION.to_le_bytes());
-                buf.extend(event.to_bytes());
+            buf.extend(id.to_le_bytes());
+            buf.push(encoding.to_byte());
+            buf.extend(body);
 
-                buf.into_boxed_slice()
-            }
+            buf.into_boxed_slice()
+        }
+
+        match (self, encoding) {
+            (Self::Post(event), Encoding::Plain) => plain(Self::V1_POST, event),
+            (Self::Comment(event), Encoding::Plain) => plain(Self::V1_COMMENT, event),
+            (Self::Reaction(event), _) => plain(Self::V1_REACTION, event),
+            (Self::Delegation(event), _) => plain(Self::V1_DELEGATION, event),
+            (Self::Rotation(event), _) => plain(Self::V1_ROTATION, event),
+            (Self::Report(event), _) => plain(Self::V1_REPORT, event),
+            (Self::Group(event), _) => plain(Self::V1_GROUP, event),
+            (Self::GroupMember(event), _) => plain(Self::V1_GROUP_MEMBER, event),
+            (Self::GroupMessage(event), _) => plain(Self::V1_GROUP_MESSAGE, event),
+
+            (Self::Post(event), _) => encode(Self::V1_ENCODED_POST, event, encoding),
+            (Self::Comment(event), _) => encode(Self::V1_ENCODED_COMMENT, event, encoding)
         }
     }
 
     pub fn from_bytes(event: impl AsRef<[u8]>) -> Result<Self, EventDecodeError> {
+        /// Decode encoded event body, limiting its decoded size.
+        fn decode(
+            event: &[u8],
+            max_size: usize
+        ) -> Result<Vec<u8>, EventDecodeError> {
+            let Some(encoding) = event.first() else {
+                return Err(EventDecodeError::SliceTooShort);
+            };
+
+            let Some(encoding) = Encoding::from_byte(*encoding) else {
+                return Err(EncodingError::UnknownEncoding(*encoding).into());
+            };
+
+            Ok(encoding.decode(&event[1..], max_size)?)
+        }
+
         let event = event.as_ref();
 
         if event.len() < 2 {
@@ -146,6 +269,68 @@ impl Events {
                 ))
             }
 
+            Self::V1_ENCODED_POST => {
+                let body = decode(&event[2..], PostEvent::MAX_SIZE)?;
+
+                Ok(Self::Post(PostEvent::from_bytes(&body)?))
+            }
+
+            Self::V1_ENCODED_COMMENT => {
+                let body = decode(&event[2..], CommentEvent::MAX_SIZE)?;
+
+                Ok(Self::Comment(CommentEvent::from_bytes(&body)?))
+            }
+
+            Self::V1_DELEGATION => {
+                Ok(Self::Delegation(
+                    DelegationEvent::from_bytes(&event[2..])?
+                ))
+            }
+
+            Self::V1_ROTATION => {
+                Ok(Self::Rotation(
+                    RotationEvent::from_bytes(&event[2..])?
+                ))
+            }
+
+            Self::V1_REPORT => {
+                Ok(Self::Report(
+                    ReportEvent::from_bytes(&event[2..])?
+                ))
+            }
+
+            Self::V1_GROUP => {
+                Ok(Self::Group(
+                    GroupEvent::from_bytes(&event[2..])?
+                ))
+            }
+
+            Self::V1_GROUP_MEMBER => {
+                Ok(Self::GroupMember(
+                    GroupMemberEvent::from_bytes(&event[2..])?
+                ))
+            }
+
+            Self::V1_GROUP_MESSAGE => {
+                Ok(Self::GroupMessage(
+                    GroupMessageEvent::from_bytes(&event[2..])?
+                ))
+            }
+
+            // Stamp only proves the work spent on the event and doesn't
+            // change its meaning.
+            Self::V1_STAMPED => {
+                let Some((_, event)) = Stamp::unstamp(event) else {
+                    return Err(EventDecodeError::SliceTooShort);
+                };
+
+                if Stamp::unstamp(event).is_some() {
+                    return Err(EventDecodeError::NestedStamp);
+                }
+
+                Self::from_bytes(event)
+            }
+
             _ => Err(EventDecodeError::UnknownEvent(id))
         }
     }
@@ -172,14 +357,75 @@ impl From<ReactionEvent> for Events {
     }
 }
 
+impl From<DelegationEvent> for Events {
+    #[inline(always)]
+    fn from(value: DelegationEvent) -> Self {
+        Self::Delegation(value)
+    }
+}
+
+impl From<RotationEvent> for Events {
+    #[inline(always)]
+    fn from(value: RotationEvent) -> Self {
+        Self::Rotation(value)
+    }
+}
+
+impl From<ReportEvent> for Events {
+    #[inline(always)]
+    fn from(value: ReportEvent) -> Self {
+        Self::Report(value)
+    }
+}
+
+impl From<GroupEvent> for Events {
+    #[inline(always)]
+    fn from(value: GroupEvent) -> Self {
+        Self::Group(value)
+    }
+}
+
+impl From<GroupMemberEvent> for Events {
+    #[inline(always)]
+    fn from(value: GroupMemberEvent) -> Self {
+        Self::GroupMember(value)
+    }
+}
+
+impl From<GroupMessageEvent> for Events {
+    #[inline(always)]
+    fn from(value: GroupMessageEvent) -> Self {
+        Self::GroupMessage(value)
+    }
+}
+
 /// Filter function for garden protocol related flowerpot messages. This
 /// function will try to decode the message into a garden protocol event and
 /// return `true` on success.
+#[cfg(feature = "std")]
 #[inline]
 pub fn messages_filter(
-    _address: &Address,
+    address: &Address,
+    message: &Message,
+    author: &VerifyingKey
+) -> bool {
+    messages_filter_with(&StampPolicy::default(), address, message, author)
+}
+
+/// Filter function for garden protocol related flowerpot messages. This
+/// function will try to decode the message into a garden protocol event and
+/// return `true` if it succeeded and the event has proof of work stamp
+/// minted by the message author for provided blockchain address and required
+/// by provided policy.
+#[cfg(feature = "std")]
+pub fn messages_filter_with(
+    policy: &StampPolicy,
+    address: &Address,
     message: &Message,
-    _author: &VerifyingKey
+    author: &VerifyingKey
 ) -> bool {
-    Events::from_bytes(message.data()).is_ok()
+    match Events::from_bytes(message.data()) {
+        Ok(event) => policy.check(address, author, &event, message.data()),
+        Err(_) => false
+    }
 }
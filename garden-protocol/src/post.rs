@@ -16,10 +16,13 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use regex::Regex;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString, FromUtf8Error};
+use alloc::vec::Vec;
 
 use super::Event;
 
+#[cfg(feature = "std")]
 lazy_static::lazy_static! {
     /// Post tag regex. The rules are:
     ///
@@ -29,24 +32,82 @@ lazy_static::lazy_static! {
     ///    than 255 characters (bytes).
     ///
     /// The name length must be verified separately from the regex.
-    pub static ref TAG_REGEX: Regex = Regex::new(r#"^[a-z0-9]{1,255}$|^[a-z0-9]{1,255}[a-z0-9\-]{0,255}[a-z0-9]{1,255}$"#)
+    pub static ref TAG_REGEX: regex::Regex = regex::Regex::new(r#"^[a-z0-9]{1,255}$|^[a-z0-9]{1,255}[a-z0-9\-]{0,255}[a-z0-9]{1,255}$"#)
         .expect("failed to build tag regex");
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ValidationError {
+    #[error("content is too long by {} bytes", .length - .max_length)]
+    ContentTooLong {
+        length: usize,
+        max_length: usize
+    },
+
+    #[error("tag cannot be empty")]
+    EmptyTag,
+
+    #[error("tag '{tag}' is too long by {} bytes", .tag.len() - .max_length)]
+    TagTooLong {
+        tag: String,
+        max_length: usize
+    },
+
+    #[error("tag '{tag}' contains illegal character '{character}' at position {}", .index + 1)]
+    IllegalTagCharacter {
+        tag: String,
+        character: char,
+        index: usize
+    },
+
+    #[error("tag '{tag}' cannot start with a dash")]
+    LeadingDash {
+        tag: String
+    },
+
+    #[error("tag '{tag}' cannot end with a dash")]
+    TrailingDash {
+        tag: String
+    },
+
+    #[error("post has {amount} tags which is {} more than allowed", .amount - .max_amount)]
+    TooManyTags {
+        amount: usize,
+        max_amount: usize
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "String", into = "String"))]
 pub struct Content(String);
 
 impl Content {
-    /// Create new content string, return `None` if its length exceeds max
+    /// Max allowed content length in bytes.
+    pub const MAX_LENGTH: usize = u16::MAX as usize;
+
+    /// Create new content string, return error if its length exceeds max
     /// allowed size (65,535 bytes).
-    pub fn new(content: impl ToString) -> Option<Self> {
+    pub fn new(content: impl ToString) -> Result<Self, ValidationError> {
         let content = content.to_string();
 
-        if content.len() > u16::MAX as usize {
-            return None;
+        if content.len() > Self::MAX_LENGTH {
+            return Err(ValidationError::ContentTooLong {
+                length: content.len(),
+                max_length: Self::MAX_LENGTH
+            });
         }
 
-        Some(Self(content))
+        Ok(Self(content))
+    }
+}
+
+impl TryFrom<String> for Content {
+    type Error = ValidationError;
+
+    #[inline]
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
     }
 }
 
@@ -57,7 +118,7 @@ impl From<Content> for String {
     }
 }
 
-impl std::ops::Deref for Content {
+impl core::ops::Deref for Content {
     type Target = String;
 
     #[inline(always)]
@@ -67,21 +128,92 @@ impl std::ops::Deref for Content {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "String", into = "String"))]
 pub struct Tag(String);
 
 impl Tag {
-    /// Create new tag string, return `None` if its length exceeds max allowed
-    /// size (255 bytes).
-    pub fn new(tag: impl ToString) -> Option<Self> {
+    /// Max allowed tag length in bytes.
+    pub const MAX_LENGTH: usize = u8::MAX as usize;
+
+    /// Not safe for work posts must be hidden by default.
+    pub const NSFW: &str = "nsfw";
+
+    /// Spoiler posts must be muted (their content must be invisible until
+    /// clicked on).
+    pub const SPOILER: &str = "spoiler";
+
+    /// Create new tag string, return error if it doesn't follow the tag
+    /// rules (see `TAG_REGEX`).
+    pub fn new(tag: impl ToString) -> Result<Self, ValidationError> {
         let tag = tag.to_string();
 
-        if !(1..=u8::MAX as usize).contains(&tag.len())
-            || !TAG_REGEX.is_match(&tag)
-        {
-            return None;
+        if tag.is_empty() {
+            return Err(ValidationError::EmptyTag);
+        }
+
+        let illegal_char = tag.chars()
+            .enumerate()
+            .find(|(_, character)| {
+                !matches!(character, 'a'..='z' | '0'..='9' | '-')
+            });
+
+        if let Some((index, character)) = illegal_char {
+            return Err(ValidationError::IllegalTagCharacter {
+                tag,
+                character,
+                index
+            });
         }
 
-        Some(Self(tag))
+        // Tag contains only ASCII characters here so its length in bytes
+        // is equal to its length in characters.
+        if tag.len() > Self::MAX_LENGTH {
+            return Err(ValidationError::TagTooLong {
+                tag,
+                max_length: Self::MAX_LENGTH
+            });
+        }
+
+        if tag.starts_with('-') {
+            return Err(ValidationError::LeadingDash { tag });
+        }
+
+        if tag.ends_with('-') {
+            return Err(ValidationError::TrailingDash { tag });
+        }
+
+        #[cfg(feature = "std")]
+        debug_assert!(TAG_REGEX.is_match(&tag));
+
+        Ok(Self(tag))
+    }
+
+    /// Check if this is the special `nsfw` tag.
+    #[inline]
+    pub fn is_nsfw(&self) -> bool {
+        self.0 == Self::NSFW
+    }
+
+    /// Check if this is the special `spoiler` tag.
+    #[inline]
+    pub fn is_spoiler(&self) -> bool {
+        self.0 == Self::SPOILER
+    }
+
+    /// Check if this tag has special meaning (see README).
+    #[inline]
+    pub fn is_special(&self) -> bool {
+        self.is_nsfw() || self.is_spoiler()
+    }
+}
+
+impl TryFrom<String> for Tag {
+    type Error = ValidationError;
+
+    #[inline]
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
     }
 }
 
@@ -92,7 +224,7 @@ impl From<Tag> for String {
     }
 }
 
-impl std::ops::Deref for Tag {
+impl core::ops::Deref for Tag {
     type Target = String;
 
     #[inline(always)]
@@ -104,39 +236,48 @@ impl std::ops::Deref for Tag {
 #[derive(Debug, thiserror::Error)]
 pub enum PostEventError {
     #[error("invalid unicode sequence: {0}")]
-    InvalidUnicode(#[from] std::string::FromUtf8Error),
+    InvalidUnicode(#[from] FromUtf8Error),
 
     #[error("provided post event bytes slice is too short")]
     SliceTooShort,
 
-    #[error("invalid content")]
-    InvalidContent,
-
-    #[error("invalid tag")]
-    InvalidTag
+    #[error(transparent)]
+    Validation(#[from] ValidationError)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "PostEventFields"))]
 pub struct PostEvent {
     content: Content,
     tags: Box<[Tag]>
 }
 
 impl PostEvent {
-    /// Create new post event. Return `None` if provided tags len exceeds max
+    /// Max allowed amount of tags.
+    pub const MAX_TAGS: usize = u8::MAX as usize;
+
+    /// Max possible size of the post event's binary representation.
+    pub const MAX_SIZE: usize = 2 + Content::MAX_LENGTH + 1
+        + Self::MAX_TAGS * (1 + Tag::MAX_LENGTH);
+
+    /// Create new post event. Return error if provided tags len exceeds max
     /// allowed amount (255 items).
     pub fn new(
         content: Content,
         tags: impl IntoIterator<Item = Tag>
-    ) -> Option<Self> {
+    ) -> Result<Self, ValidationError> {
         let tags = tags.into_iter()
             .collect::<Box<[Tag]>>();
 
-        if tags.len() > u8::MAX as usize {
-            return None;
+        if tags.len() > Self::MAX_TAGS {
+            return Err(ValidationError::TooManyTags {
+                amount: tags.len(),
+                max_amount: Self::MAX_TAGS
+            });
         }
 
-        Some(Self {
+        Ok(Self {
             content,
             tags
         })
@@ -151,6 +292,36 @@ impl PostEvent {
     pub const fn tags(&self) -> &[Tag] {
         &self.tags
     }
+
+    /// Check if the post has the `nsfw` tag.
+    #[inline]
+    pub fn is_nsfw(&self) -> bool {
+        self.tags.iter().any(Tag::is_nsfw)
+    }
+
+    /// Check if the post has the `spoiler` tag.
+    #[inline]
+    pub fn is_spoiler(&self) -> bool {
+        self.tags.iter().any(Tag::is_spoiler)
+    }
+}
+
+/// Raw post event fields used to validate deserialized post events.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct PostEventFields {
+    content: Content,
+    tags: Vec<Tag>
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<PostEventFields> for PostEvent {
+    type Error = ValidationError;
+
+    #[inline]
+    fn try_from(value: PostEventFields) -> Result<Self, Self::Error> {
+        Self::new(value.content, value.tags)
+    }
 }
 
 impl Event for PostEvent {
@@ -190,7 +361,8 @@ impl Event for PostEvent {
 
         let content_len = u16::from_le_bytes([event[0], event[1]]) as usize;
 
-        if n < content_len + 2 {
+        // Content and tags amount.
+        if n < content_len + 3 {
             return Err(PostEventError::SliceTooShort);
         }
 
@@ -198,32 +370,30 @@ impl Event for PostEvent {
 
         let content = String::from_utf8(event[2..content_len + 2].to_vec())?;
 
-        let Some(content) = Content::new(content) else {
-            return Err(PostEventError::InvalidContent);
-        };
+        let content = Content::new(content)?;
 
         let mut tags = Vec::with_capacity(tags_amount);
 
         let mut tags_offset = content_len + 3;
 
-        // TODO: more length checks
-
         for _ in 0..tags_amount {
-            let tag_len = event[tags_offset] as usize;
+            let Some(tag_len) = event.get(tags_offset) else {
+                return Err(PostEventError::SliceTooShort);
+            };
+
+            let tag_len = *tag_len as usize;
 
             tags_offset += 1;
 
-            let tag = &event[tags_offset..tags_offset + tag_len];
+            let Some(tag) = event.get(tags_offset..tags_offset + tag_len) else {
+                return Err(PostEventError::SliceTooShort);
+            };
 
             tags_offset += tag_len;
 
             let tag = String::from_utf8(tag.to_vec())?;
 
-            let Some(tag) = Tag::new(tag) else {
-                return Err(PostEventError::InvalidTag);
-            };
-
-            tags.push(tag);
+            tags.push(Tag::new(tag)?);
         }
 
         Ok(Self {
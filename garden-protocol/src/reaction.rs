@@ -16,7 +16,11 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use std::str::FromStr;
+use core::str::FromStr;
+
+use alloc::boxed::Box;
+use alloc::string::{String, FromUtf8Error};
+use alloc::vec::Vec;
 
 use flowerpot::crypto::hash::Hash;
 
@@ -47,7 +51,7 @@ impl Reaction {
     }
 }
 
-impl std::str::FromStr for Reaction {
+impl FromStr for Reaction {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -60,17 +64,42 @@ impl std::str::FromStr for Reaction {
     }
 }
 
-impl std::fmt::Display for Reaction {
+impl core::fmt::Display for Reaction {
     #[inline]
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.write_str(self.to_name())
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Reaction {
+    #[inline]
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.to_name())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Reaction {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D
+    ) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let name = String::deserialize(deserializer)?;
+
+        Self::from_str(&name)
+            .map_err(|_| D::Error::custom(alloc::format!("unknown reaction name: {name}")))
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ReactionEventError {
     #[error("invalid unicode sequence: {0}")]
-    InvalidUnicode(#[from] std::string::FromUtf8Error),
+    InvalidUnicode(#[from] FromUtf8Error),
 
     #[error("provided comment event bytes slice is too short")]
     SliceTooShort,
@@ -80,7 +109,9 @@ pub enum ReactionEventError {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ReactionEvent {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
     ref_address: Hash,
     reaction: Reaction
 }
@@ -0,0 +1,210 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use alloc::boxed::Box;
+use alloc::string::{String, FromUtf8Error};
+use alloc::vec::Vec;
+
+use flowerpot::crypto::hash::Hash;
+
+use super::post::{Content, ValidationError};
+use super::Event;
+
+/// Category of the report reason.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ReportReason {
+    /// Unwanted advertisement or flood.
+    Spam,
+
+    /// Harassment or hate speech towards people.
+    Harassment,
+
+    /// Content which is illegal.
+    Illegal,
+
+    /// Not safe for work content without `nsfw` tag.
+    Nsfw,
+
+    /// Spoilers without `spoiler` tag.
+    Spoiler,
+
+    /// Intentionally false information.
+    Misinformation,
+
+    /// Any other reason, should be explained in the report note.
+    Other
+}
+
+impl ReportReason {
+    pub const SPAM: u8           = 0;
+    pub const HARASSMENT: u8     = 1;
+    pub const ILLEGAL: u8        = 2;
+    pub const NSFW: u8           = 3;
+    pub const SPOILER: u8        = 4;
+    pub const MISINFORMATION: u8 = 5;
+    pub const OTHER: u8          = 255;
+
+    pub const fn to_byte(&self) -> u8 {
+        match self {
+            Self::Spam           => Self::SPAM,
+            Self::Harassment     => Self::HARASSMENT,
+            Self::Illegal        => Self::ILLEGAL,
+            Self::Nsfw           => Self::NSFW,
+            Self::Spoiler        => Self::SPOILER,
+            Self::Misinformation => Self::MISINFORMATION,
+            Self::Other          => Self::OTHER
+        }
+    }
+
+    pub const fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            Self::SPAM           => Some(Self::Spam),
+            Self::HARASSMENT     => Some(Self::Harassment),
+            Self::ILLEGAL        => Some(Self::Illegal),
+            Self::NSFW           => Some(Self::Nsfw),
+            Self::SPOILER        => Some(Self::Spoiler),
+            Self::MISINFORMATION => Some(Self::Misinformation),
+            Self::OTHER          => Some(Self::Other),
+
+            _ => None
+        }
+    }
+
+    pub const fn to_name(&self) -> &'static str {
+        match self {
+            Self::Spam           => "spam",
+            Self::Harassment     => "harassment",
+            Self::Illegal        => "illegal",
+            Self::Nsfw           => "nsfw",
+            Self::Spoiler        => "spoiler",
+            Self::Misinformation => "misinformation",
+            Self::Other          => "other"
+        }
+    }
+}
+
+impl core::fmt::Display for ReportReason {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.to_name())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReportEventError {
+    #[error("invalid unicode sequence: {0}")]
+    InvalidUnicode(#[from] FromUtf8Error),
+
+    #[error("provided report event bytes slice is too short")]
+    SliceTooShort,
+
+    #[error("unknown report reason: {0}")]
+    UnknownReason(u8),
+
+    #[error(transparent)]
+    Validation(#[from] ValidationError)
+}
+
+/// Report event is used to notify moderators about inappropriate posts or
+/// comments. Protocol doesn't define how reports are handled, clients should
+/// decide themselves whose reports to trust.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReportEvent {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::hash"))]
+    ref_message_hash: Hash,
+    reason: ReportReason,
+    note: Content
+}
+
+impl ReportEvent {
+    /// Max possible size of the report event's binary representation.
+    pub const MAX_SIZE: usize = Hash::SIZE + 1 + Content::MAX_LENGTH;
+
+    /// Create new report event. Reference address is a flowerpot message hash
+    /// of a post or a comment.
+    pub fn new(
+        ref_message_hash: impl Into<Hash>,
+        reason: ReportReason,
+        note: Content
+    ) -> Self {
+        Self {
+            ref_message_hash: ref_message_hash.into(),
+            reason,
+            note
+        }
+    }
+
+    #[inline(always)]
+    pub const fn ref_message_hash(&self) -> &Hash {
+        &self.ref_message_hash
+    }
+
+    #[inline(always)]
+    pub const fn reason(&self) -> &ReportReason {
+        &self.reason
+    }
+
+    #[inline(always)]
+    pub const fn note(&self) -> &Content {
+        &self.note
+    }
+}
+
+impl Event for ReportEvent {
+    type Error = ReportEventError;
+
+    fn to_bytes(&self) -> Box<[u8]> {
+        let mut buf = Vec::with_capacity(Hash::SIZE + 1 + self.note.len());
+
+        buf.extend(self.ref_message_hash.as_bytes());
+        buf.push(self.reason.to_byte());
+        buf.extend(self.note.as_bytes());
+
+        buf.into_boxed_slice()
+    }
+
+    fn from_bytes(event: &[u8]) -> Result<Self, Self::Error> where Self: Sized {
+        if event.len() < Hash::SIZE + 1 {
+            return Err(ReportEventError::SliceTooShort);
+        }
+
+        let mut ref_message_hash = [0; Hash::SIZE];
+
+        ref_message_hash.copy_from_slice(&event[..Hash::SIZE]);
+
+        let Some(reason) = ReportReason::from_byte(event[Hash::SIZE]) else {
+            return Err(ReportEventError::UnknownReason(event[Hash::SIZE]));
+        };
+
+        let note = String::from_utf8(event[Hash::SIZE + 1..].to_vec())?;
+
+        Ok(Self {
+            ref_message_hash: Hash::from(ref_message_hash),
+            reason,
+            note: Content::new(note)?
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(Hash::SIZE + 1 + self.note.len())
+    }
+}
@@ -0,0 +1,151 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use flowerpot::crypto::sign::{SigningKey, VerifyingKey, Signature, SignatureError};
+
+use super::Event;
+
+#[derive(Debug, thiserror::Error)]
+pub enum RotationEventError {
+    #[error("provided rotation event bytes slice is too short")]
+    SliceTooShort,
+
+    #[error("invalid verifying key")]
+    InvalidVerifyingKey,
+
+    #[error("invalid signature")]
+    InvalidSignature
+}
+
+/// Key rotation event links the event's signer (old key) with a new identity
+/// key. The event must be signed by the old key, and it contains a signature
+/// of the new key, so both keys must be owned by the same person.
+///
+/// After rotation the old key is considered revoked.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RotationEvent {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::verifying_key"))]
+    new_key: VerifyingKey,
+
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::signature"))]
+    new_key_signature: Signature
+}
+
+impl RotationEvent {
+    /// Context string used to sign the rotation payload by the new key.
+    pub const CONTEXT: &[u8] = b"garden identity key rotation";
+
+    /// Create new key rotation event. Returned event must be signed by the
+    /// old key.
+    pub fn new(
+        old_key: &VerifyingKey,
+        new_signing_key: &SigningKey
+    ) -> Result<Self, SignatureError> {
+        let new_key = new_signing_key.verifying_key();
+
+        let payload = Self::payload(old_key, &new_key);
+
+        Ok(Self {
+            new_key_signature: new_signing_key.sign(payload)?,
+            new_key
+        })
+    }
+
+    /// Build payload signed by the new key.
+    pub fn payload(old_key: &VerifyingKey, new_key: &VerifyingKey) -> Vec<u8> {
+        let mut payload = Vec::with_capacity(
+            Self::CONTEXT.len() + VerifyingKey::SIZE * 2
+        );
+
+        payload.extend_from_slice(Self::CONTEXT);
+        payload.extend(old_key.to_bytes());
+        payload.extend(new_key.to_bytes());
+
+        payload
+    }
+
+    #[inline(always)]
+    pub const fn new_key(&self) -> &VerifyingKey {
+        &self.new_key
+    }
+
+    #[inline(always)]
+    pub const fn new_key_signature(&self) -> &Signature {
+        &self.new_key_signature
+    }
+
+    /// Verify that the event was signed by the new key for provided old key
+    /// (signer of the event).
+    pub fn verify(&self, old_key: &VerifyingKey) -> Result<bool, SignatureError> {
+        let payload = Self::payload(old_key, &self.new_key);
+
+        let (is_valid, signer) = self.new_key_signature.verify(payload)?;
+
+        Ok(is_valid && signer == self.new_key)
+    }
+}
+
+impl Event for RotationEvent {
+    type Error = RotationEventError;
+
+    fn to_bytes(&self) -> Box<[u8]> {
+        let mut buf = Vec::with_capacity(VerifyingKey::SIZE + Signature::SIZE);
+
+        buf.extend(self.new_key.to_bytes());
+        buf.extend(self.new_key_signature.to_bytes());
+
+        buf.into_boxed_slice()
+    }
+
+    fn from_bytes(event: &[u8]) -> Result<Self, Self::Error> where Self: Sized {
+        if event.len() < VerifyingKey::SIZE + Signature::SIZE {
+            return Err(RotationEventError::SliceTooShort);
+        }
+
+        let mut new_key = [0; VerifyingKey::SIZE];
+        let mut new_key_signature = [0; Signature::SIZE];
+
+        new_key.copy_from_slice(&event[..VerifyingKey::SIZE]);
+
+        new_key_signature.copy_from_slice(
+            &event[VerifyingKey::SIZE..VerifyingKey::SIZE + Signature::SIZE]
+        );
+
+        let Some(new_key) = VerifyingKey::from_bytes(&new_key) else {
+            return Err(RotationEventError::InvalidVerifyingKey);
+        };
+
+        let Some(new_key_signature) = Signature::from_bytes(&new_key_signature) else {
+            return Err(RotationEventError::InvalidSignature);
+        };
+
+        Ok(Self {
+            new_key,
+            new_key_signature
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(VerifyingKey::SIZE + Signature::SIZE)
+    }
+}
@@ -0,0 +1,256 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Helper functions for the optional `serde` feature. Flowerpot types don't
+//! implement serde traits, so they're serialized manually as base64 strings.
+
+pub mod hash {
+    use alloc::string::String;
+
+    use serde::{Serializer, Deserializer, Deserialize};
+    use serde::de::Error;
+
+    use flowerpot::crypto::hash::Hash;
+
+    pub fn serialize<S: Serializer>(
+        hash: &Hash,
+        serializer: S
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hash.to_base64())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D
+    ) -> Result<Hash, D::Error> {
+        let hash = String::deserialize(deserializer)?;
+
+        Hash::from_base64(&hash)
+            .ok_or_else(|| D::Error::custom("invalid base64 encoded hash"))
+    }
+}
+
+pub mod verifying_key {
+    use alloc::string::String;
+
+    use serde::{Serializer, Deserializer, Deserialize};
+    use serde::de::Error;
+
+    use flowerpot::crypto::sign::VerifyingKey;
+
+    pub fn serialize<S: Serializer>(
+        verifying_key: &VerifyingKey,
+        serializer: S
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&verifying_key.to_base64())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D
+    ) -> Result<VerifyingKey, D::Error> {
+        let verifying_key = String::deserialize(deserializer)?;
+
+        VerifyingKey::from_base64(&verifying_key)
+            .ok_or_else(|| D::Error::custom("invalid base64 encoded verifying key"))
+    }
+}
+
+/// Lists of verifying keys are stored as lists of base64 strings.
+// Used only by the index and archives.
+#[cfg(feature = "std")]
+pub mod verifying_keys {
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    use serde::{Serializer, Deserializer, Deserialize};
+    use serde::de::Error;
+
+    use flowerpot::crypto::sign::VerifyingKey;
+
+    pub fn serialize<S: Serializer>(
+        verifying_keys: &[VerifyingKey],
+        serializer: S
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(verifying_keys.iter().map(VerifyingKey::to_base64))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D
+    ) -> Result<Vec<VerifyingKey>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|verifying_key| {
+                VerifyingKey::from_base64(verifying_key)
+                    .ok_or_else(|| D::Error::custom("invalid base64 encoded verifying key"))
+            })
+            .collect()
+    }
+}
+
+pub mod signature {
+    use alloc::string::String;
+
+    use serde::{Serializer, Deserializer, Deserialize};
+    use serde::de::Error;
+
+    use flowerpot::crypto::base64;
+    use flowerpot::crypto::sign::Signature;
+
+    pub fn serialize<S: Serializer>(
+        signature: &Signature,
+        serializer: S
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&base64::encode(signature.to_bytes()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D
+    ) -> Result<Signature, D::Error> {
+        let signature = String::deserialize(deserializer)?;
+
+        let signature = base64::decode(&signature)
+            .map_err(|_| D::Error::custom("invalid base64 encoded signature"))?;
+
+        let signature = <[u8; Signature::SIZE]>::try_from(signature.as_slice())
+            .map_err(|_| D::Error::custom("invalid signature length"))?;
+
+        Signature::from_bytes(&signature)
+            .ok_or_else(|| D::Error::custom("invalid signature"))
+    }
+}
+
+/// UTC timestamps are stored as unix timestamps (seconds).
+// Used only by the index and archives.
+#[cfg(feature = "std")]
+pub mod timestamp {
+    use serde::{Serializer, Deserializer, Deserialize};
+    use serde::de::Error;
+
+    use time::UtcDateTime;
+
+    pub fn serialize<S: Serializer>(
+        timestamp: &UtcDateTime,
+        serializer: S
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(timestamp.unix_timestamp())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D
+    ) -> Result<UtcDateTime, D::Error> {
+        let timestamp = i64::deserialize(deserializer)?;
+
+        UtcDateTime::from_unix_timestamp(timestamp)
+            .map_err(D::Error::custom)
+    }
+}
+
+/// Optional UTC timestamps are stored as unix timestamps (seconds) or null.
+// Used only by the index and archives.
+#[cfg(feature = "std")]
+pub mod optional_timestamp {
+    use serde::{Serializer, Deserializer, Deserialize};
+    use serde::de::Error;
+
+    use time::UtcDateTime;
+
+    pub fn serialize<S: Serializer>(
+        timestamp: &Option<UtcDateTime>,
+        serializer: S
+    ) -> Result<S::Ok, S::Error> {
+        match timestamp {
+            Some(timestamp) => serializer.serialize_some(&timestamp.unix_timestamp()),
+            None => serializer.serialize_none()
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D
+    ) -> Result<Option<UtcDateTime>, D::Error> {
+        let Some(timestamp) = Option::<i64>::deserialize(deserializer)? else {
+            return Ok(None);
+        };
+
+        UtcDateTime::from_unix_timestamp(timestamp)
+            .map(Some)
+            .map_err(D::Error::custom)
+    }
+}
+
+/// Optional blockchain addresses are stored as base64 strings or null.
+// Used only by the index and archives.
+#[cfg(feature = "std")]
+pub mod optional_address {
+    use alloc::string::String;
+
+    use serde::{Serializer, Deserializer, Deserialize};
+    use serde::de::Error;
+
+    use flowerpot::address::Address;
+
+    pub fn serialize<S: Serializer>(
+        address: &Option<Address>,
+        serializer: S
+    ) -> Result<S::Ok, S::Error> {
+        match address {
+            Some(address) => serializer.serialize_some(&address.to_base64()),
+            None => serializer.serialize_none()
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D
+    ) -> Result<Option<Address>, D::Error> {
+        let Some(address) = Option::<String>::deserialize(deserializer)? else {
+            return Ok(None);
+        };
+
+        Address::from_base64(&address)
+            .map(Some)
+            .ok_or_else(|| D::Error::custom("invalid base64 encoded address"))
+    }
+}
+
+/// Raw bytes (fixed size arrays and boxed slices) are stored as base64 strings.
+pub mod bytes {
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    use serde::{Serializer, Deserializer, Deserialize};
+    use serde::de::Error;
+
+    use flowerpot::crypto::base64;
+
+    pub fn serialize<S: Serializer>(
+        bytes: impl AsRef<[u8]>,
+        serializer: S
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&base64::encode(bytes.as_ref()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: TryFrom<Vec<u8>>>(
+        deserializer: D
+    ) -> Result<T, D::Error> {
+        let bytes = String::deserialize(deserializer)?;
+
+        let bytes = base64::decode(&bytes)
+            .map_err(|_| D::Error::custom("invalid base64 encoded bytes"))?;
+
+        T::try_from(bytes)
+            .map_err(|_| D::Error::custom("invalid bytes length"))
+    }
+}
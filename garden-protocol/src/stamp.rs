@@ -0,0 +1,249 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use sha2::{Sha256, Digest};
+
+#[cfg(feature = "std")]
+use flowerpot::address::Address;
+
+#[cfg(feature = "std")]
+use flowerpot::crypto::sign::VerifyingKey;
+
+use crate::Events;
+
+/// Proof of work stamp of a garden event.
+///
+/// Stamp is a nonce which, hashed together with the blockchain address, the
+/// event author's verifying key and the event bytes, produces a hash with some
+/// amount of leading zero bits - stamp difficulty. Each extra bit of
+/// difficulty doubles the expected amount of work needed to mint the stamp,
+/// which makes flooding the network with events expensive. Since the address
+/// and the author are hashed, a stamp can't be reused by another key or in
+/// another chain.
+///
+/// Stamped events are stored as `[V1_STAMPED][nonce][event]`, where `event`
+/// is the original event binary representation.
+///
+/// Stamps are bound to flowerpot blockchain addresses, so they can be minted
+/// and checked only with the `std` feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Stamp {
+    nonce: u64
+}
+
+impl Stamp {
+    /// Context string hashed together with the stamp nonce and event bytes.
+    pub const CONTEXT: &[u8] = b"garden proof of work stamp";
+
+    /// Size of the stamp in the stamped event's binary representation.
+    pub const SIZE: usize = 8;
+
+    /// Max possible stamp difficulty.
+    pub const MAX_DIFFICULTY: u32 = 256;
+
+    /// Amount of minting attempts between progress callback calls.
+    pub const PROGRESS_INTERVAL: u64 = 4096;
+
+    #[inline(always)]
+    pub const fn new(nonce: u64) -> Self {
+        Self { nonce }
+    }
+
+    #[inline(always)]
+    pub const fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    /// Calculate difficulty of the stamp for provided event bytes signed by
+    /// provided author in the blockchain with provided address.
+    #[cfg(feature = "std")]
+    pub fn difficulty(
+        &self,
+        address: &Address,
+        author: &VerifyingKey,
+        event: &[u8]
+    ) -> u32 {
+        let hash = Self::hasher(address, author, event)
+            .chain_update(self.nonce.to_le_bytes())
+            .finalize();
+
+        leading_zeros(&hash)
+    }
+
+    /// Try to mint a stamp of at least provided difficulty for provided event
+    /// bytes signed by provided author in the blockchain with provided
+    /// address.
+    ///
+    /// Progress callback is called every `PROGRESS_INTERVAL` attempts with
+    /// the amount of already made attempts. Minting is cancelled if it
+    /// returns `false`.
+    ///
+    /// Return `None` if minting was cancelled or difficulty is higher than
+    /// `MAX_DIFFICULTY`.
+    #[cfg(feature = "std")]
+    pub fn mint(
+        address: &Address,
+        author: &VerifyingKey,
+        event: &[u8],
+        difficulty: u32,
+        mut progress: impl FnMut(u64) -> bool
+    ) -> Option<Self> {
+        if difficulty > Self::MAX_DIFFICULTY {
+            return None;
+        }
+
+        let hasher = Self::hasher(address, author, event);
+
+        for nonce in 0..=u64::MAX {
+            if nonce % Self::PROGRESS_INTERVAL == 0 && !progress(nonce) {
+                return None;
+            }
+
+            let hash = hasher.clone()
+                .chain_update(nonce.to_le_bytes())
+                .finalize();
+
+            if leading_zeros(&hash) >= difficulty {
+                return Some(Self { nonce });
+            }
+        }
+
+        None
+    }
+
+    /// Hash the stamp context, blockchain address, author and event bytes.
+    #[cfg(feature = "std")]
+    fn hasher(address: &Address, author: &VerifyingKey, event: &[u8]) -> Sha256 {
+        Sha256::new()
+            .chain_update(Self::CONTEXT)
+            .chain_update(address.to_base64().as_bytes())
+            .chain_update(author.to_bytes())
+            .chain_update(event)
+    }
+
+    /// Wrap provided event bytes into the stamped event.
+    pub fn stamp(&self, event: &[u8]) -> Box<[u8]> {
+        let mut buf = Vec::with_capacity(event.len() + Self::SIZE + 2);
+
+        buf.extend(Events::V1_STAMPED.to_le_bytes());
+        buf.extend(self.nonce.to_le_bytes());
+        buf.extend(event);
+
+        buf.into_boxed_slice()
+    }
+
+    /// Try to split stamped event into the stamp and the original event
+    /// bytes. Return `None` if provided bytes are not a stamped event.
+    pub fn unstamp(event: &[u8]) -> Option<(Self, &[u8])> {
+        if event.len() < Self::SIZE + 2 {
+            return None;
+        }
+
+        if u16::from_le_bytes([event[0], event[1]]) != Events::V1_STAMPED {
+            return None;
+        }
+
+        let mut nonce = [0; Self::SIZE];
+
+        nonce.copy_from_slice(&event[2..Self::SIZE + 2]);
+
+        Some((Self::new(u64::from_le_bytes(nonce)), &event[Self::SIZE + 2..]))
+    }
+}
+
+/// Get difficulty of the stamp of provided event bytes signed by provided
+/// author in the blockchain with provided address. Events without stamp have
+/// zero difficulty.
+#[cfg(feature = "std")]
+pub fn difficulty(address: &Address, author: &VerifyingKey, event: &[u8]) -> u32 {
+    match Stamp::unstamp(event) {
+        Some((stamp, event)) => stamp.difficulty(address, author, event),
+        None => 0
+    }
+}
+
+/// Amount of leading zero bits of provided hash.
+#[cfg(feature = "std")]
+fn leading_zeros(hash: &[u8]) -> u32 {
+    let mut bits = 0;
+
+    for byte in hash {
+        bits += byte.leading_zeros();
+
+        if *byte != 0 {
+            break;
+        }
+    }
+
+    bits
+}
+
+/// Minimal proof of work stamps difficulty required for each event type.
+/// Events with lower stamp difficulty are ignored.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StampPolicy {
+    pub post: u32,
+    pub comment: u32,
+    pub reaction: u32,
+    pub delegation: u32,
+    pub rotation: u32,
+    pub report: u32,
+
+    /// Difficulty of all the group events. Group messages are encrypted so
+    /// posts can't be distinguished from comments.
+    pub group: u32
+}
+
+impl StampPolicy {
+    /// Get minimal stamp difficulty required for provided event.
+    pub const fn required(&self, event: &Events) -> u32 {
+        match event {
+            Events::Post(_)       => self.post,
+            Events::Comment(_)    => self.comment,
+            Events::Reaction(_)   => self.reaction,
+            Events::Delegation(_) => self.delegation,
+            Events::Rotation(_)   => self.rotation,
+            Events::Report(_)     => self.report,
+
+            Events::Group(_) |
+            Events::GroupMember(_) |
+            Events::GroupMessage(_) => self.group
+        }
+    }
+
+    /// Check that provided event bytes signed by provided author in the
+    /// blockchain with provided address have a stamp of required difficulty.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn check(
+        &self,
+        address: &Address,
+        author: &VerifyingKey,
+        event: &Events,
+        bytes: &[u8]
+    ) -> bool {
+        let required = self.required(event);
+
+        required == 0 || difficulty(address, author, bytes) >= required
+    }
+}
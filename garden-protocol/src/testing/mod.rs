@@ -0,0 +1,374 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! In-memory flowerpot blockchain storage and a builder of signed blocks
+//! chains, made to test garden protocol indexing without a real storage and
+//! a synchronized network.
+
+use std::collections::HashMap;
+
+use spin::RwLock;
+
+use flowerpot::crypto::hash::Hash;
+use flowerpot::crypto::sign::{SigningKey, SignatureError};
+use flowerpot::block::Block;
+use flowerpot::message::Message;
+use flowerpot::storage::{Storage, StorageError};
+
+use crate::Events;
+
+mod network;
+mod simulation;
+
+pub use network::{LoopbackNetwork, LoopbackNode, LoopbackError};
+pub use simulation::{Simulation, SimulationConfig, SimulationReport, SimulationError};
+
+/// Create signing key deterministically derived from provided seed.
+pub fn signing_key(seed: u8) -> SigningKey {
+    let mut bytes = [0; SigningKey::SIZE];
+
+    // Zero is not a valid secret key.
+    bytes[0] = 1;
+    bytes[1] = seed;
+
+    SigningKey::from_bytes(&bytes)
+        .expect("failed to create signing key from seed")
+}
+
+/// Flowerpot blockchain storage which keeps all the blocks in memory.
+///
+/// Storage keeps a single chain of blocks. Writing a block which references
+/// a block in the middle of the chain replaces all the following blocks, so
+/// forks can be tested as well.
+#[derive(Default)]
+pub struct MemoryStorage {
+    blocks: RwLock<Vec<Block>>,
+
+    /// Message hash -> hash of the block where the message is stored.
+    messages: RwLock<HashMap<Hash, Hash>>
+}
+
+impl MemoryStorage {
+    /// Create new empty storage.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create new storage from provided chain of blocks.
+    pub fn from_blocks(blocks: impl IntoIterator<Item = Block>) -> Self {
+        let storage = Self::default();
+
+        for block in blocks {
+            storage.write(block);
+        }
+
+        storage
+    }
+
+    /// Get amount of stored blocks.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.blocks.read().len()
+    }
+
+    /// Check if the storage has no blocks.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.blocks.read().is_empty()
+    }
+
+    /// Remove provided amount of blocks from the end of the chain.
+    pub fn truncate(&self, amount: usize) {
+        let mut blocks = self.blocks.write();
+
+        let len = blocks.len().saturating_sub(amount);
+
+        blocks.truncate(len);
+
+        self.reindex_messages(&blocks);
+    }
+
+    /// Get position of the block with provided hash in the chain (root block
+    /// has zero height).
+    pub fn height(&self, hash: &Hash) -> Option<usize> {
+        self.blocks.read()
+            .iter()
+            .position(|block| block.hash() == hash)
+    }
+
+    fn reindex_messages(&self, blocks: &[Block]) {
+        let mut messages = self.messages.write();
+
+        messages.clear();
+
+        for block in blocks {
+            for message in block.inline_messages() {
+                messages.insert(*message.hash(), *block.hash());
+            }
+        }
+    }
+
+    fn write(&self, block: Block) -> bool {
+        let mut blocks = self.blocks.write();
+
+        if blocks.iter().any(|stored| stored.hash() == block.hash()) {
+            return false;
+        }
+
+        let position = if block.prev_hash() == &Hash::ZERO {
+            Some(0)
+        } else {
+            blocks.iter()
+                .position(|stored| stored.hash() == block.prev_hash())
+                .map(|position| position + 1)
+        };
+
+        // Block doesn't belong to the stored chain.
+        let Some(position) = position else {
+            return false;
+        };
+
+        blocks.truncate(position);
+        blocks.push(block);
+
+        self.reindex_messages(&blocks);
+
+        true
+    }
+}
+
+impl std::fmt::Debug for MemoryStorage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemoryStorage")
+            .field("blocks", &self.len())
+            .field("messages", &self.messages.read().len())
+            .finish()
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn root_block(&self) -> Result<Option<Hash>, StorageError> {
+        Ok(self.blocks.read().first().map(|block| *block.hash()))
+    }
+
+    fn tail_block(&self) -> Result<Option<Hash>, StorageError> {
+        Ok(self.blocks.read().last().map(|block| *block.hash()))
+    }
+
+    #[inline]
+    fn has_block(&self, hash: &Hash) -> Result<bool, StorageError> {
+        Ok(self.height(hash).is_some())
+    }
+
+    fn next_block(&self, hash: &Hash) -> Result<Option<Hash>, StorageError> {
+        let blocks = self.blocks.read();
+
+        // Zero hash references the block before the root one.
+        if hash == &Hash::ZERO {
+            return Ok(blocks.first().map(|block| *block.hash()));
+        }
+
+        let next = blocks.iter()
+            .position(|block| block.hash() == hash)
+            .and_then(|position| blocks.get(position + 1))
+            .map(|block| *block.hash());
+
+        Ok(next)
+    }
+
+    fn prev_block(&self, hash: &Hash) -> Result<Option<Hash>, StorageError> {
+        let blocks = self.blocks.read();
+
+        let prev = blocks.iter()
+            .position(|block| block.hash() == hash)
+            .and_then(|position| position.checked_sub(1))
+            .map(|position| *blocks[position].hash());
+
+        Ok(prev)
+    }
+
+    fn read_block(&self, hash: &Hash) -> Result<Option<Block>, StorageError> {
+        let block = self.blocks.read()
+            .iter()
+            .find(|block| block.hash() == hash)
+            .cloned();
+
+        Ok(block)
+    }
+
+    #[inline]
+    fn write_block(&self, block: &Block) -> Result<bool, StorageError> {
+        Ok(self.write(block.clone()))
+    }
+
+    #[inline]
+    fn has_message(&self, hash: &Hash) -> Result<bool, StorageError> {
+        Ok(self.messages.read().contains_key(hash))
+    }
+
+    #[inline]
+    fn find_message(&self, hash: &Hash) -> Result<Option<Hash>, StorageError> {
+        Ok(self.messages.read().get(hash).copied())
+    }
+
+    fn read_message(&self, hash: &Hash) -> Result<Option<Message>, StorageError> {
+        let Some(block_hash) = self.find_message(hash)? else {
+            return Ok(None);
+        };
+
+        let message = self.read_block(&block_hash)?
+            .and_then(|block| {
+                block.inline_messages()
+                    .iter()
+                    .find(|message| message.hash() == hash)
+                    .cloned()
+            });
+
+        Ok(message)
+    }
+}
+
+/// Builder of signed flowerpot blocks chains containing garden events.
+///
+/// ```ignore
+/// let mut chain = ChainBuilder::new(signing_key(0));
+///
+/// let post = chain.event(&author, post_event)?;
+///
+/// chain.block()?;
+///
+/// let storage = chain.storage();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChainBuilder {
+    /// Signing key of the blocks.
+    validator: SigningKey,
+
+    /// Signed blocks.
+    blocks: Vec<Block>,
+
+    /// Messages of the next block.
+    pending: Vec<Message>
+}
+
+impl ChainBuilder {
+    /// Create new empty chain builder which will sign blocks with provided
+    /// key.
+    pub fn new(validator: SigningKey) -> Self {
+        Self {
+            validator,
+            blocks: Vec::new(),
+            pending: Vec::new()
+        }
+    }
+
+    /// Sign provided garden event by the author key and add it to the next
+    /// block. Return hash of the created message.
+    pub fn event(
+        &mut self,
+        author: &SigningKey,
+        event: impl Into<Events>
+    ) -> Result<Hash, SignatureError> {
+        self.data(author, event.into().to_bytes())
+    }
+
+    /// Sign arbitrary data by the author key and add it to the next block.
+    /// Can be used to store stamped events or messages which are not garden
+    /// events at all. Return hash of the created message.
+    pub fn data(
+        &mut self,
+        author: &SigningKey,
+        data: impl Into<Box<[u8]>>
+    ) -> Result<Hash, SignatureError> {
+        let message = Message::create(author, data.into())?;
+        let hash = *message.hash();
+
+        self.pending.push(message);
+
+        Ok(hash)
+    }
+
+    /// Add already signed message to the next block. Return hash of the
+    /// message.
+    pub fn message(&mut self, message: Message) -> Hash {
+        let hash = *message.hash();
+
+        self.pending.push(message);
+
+        hash
+    }
+
+    /// Get amount of messages added to the next block.
+    #[inline]
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Sign a new block with all the added messages. Return hash of the
+    /// created block.
+    pub fn block(&mut self) -> Result<Hash, SignatureError> {
+        let prev_hash = self.blocks.last()
+            .map(|block| *block.hash())
+            .unwrap_or(Hash::ZERO);
+
+        let messages = std::mem::take(&mut self.pending);
+
+        let block = Block::create(&self.validator, prev_hash, messages)?;
+        let hash = *block.hash();
+
+        self.blocks.push(block);
+
+        Ok(hash)
+    }
+
+    /// Get list of signed blocks.
+    #[inline(always)]
+    pub fn blocks(&self) -> &[Block] {
+        &self.blocks
+    }
+
+    /// Remove provided amount of blocks from the end of the chain and return
+    /// them. Can be used to build a fork of the chain.
+    pub fn rollback(&mut self, amount: usize) -> Vec<Block> {
+        let len = self.blocks.len().saturating_sub(amount);
+
+        self.blocks.split_off(len)
+    }
+
+    /// Create new in-memory storage with all the signed blocks. Messages not
+    /// sealed into a block are not stored.
+    #[inline]
+    pub fn storage(&self) -> MemoryStorage {
+        MemoryStorage::from_blocks(self.blocks.iter().cloned())
+    }
+
+    /// Write all the signed blocks which are not stored in provided storage
+    /// yet. Return amount of written blocks.
+    pub fn sync(&self, storage: &dyn Storage) -> Result<usize, StorageError> {
+        let mut written = 0;
+
+        for block in &self.blocks {
+            if storage.write_block(block)? {
+                written += 1;
+            }
+        }
+
+        Ok(written)
+    }
+}
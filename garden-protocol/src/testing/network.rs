@@ -0,0 +1,296 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::HashSet;
+use std::net::{SocketAddr, TcpListener, Ipv4Addr};
+use std::time::{Duration, Instant};
+
+use rand_chacha::ChaCha20Rng;
+use rand_chacha::rand_core::SeedableRng;
+
+use flowerpot::address::Address;
+use flowerpot::crypto::hash::Hash;
+use flowerpot::crypto::key_exchange::SecretKey;
+use flowerpot::crypto::sign::SigningKey;
+use flowerpot::protocol::network::{PacketStreamOptions, PacketStreamEncryption};
+use flowerpot::node::{Node, NodeOptions};
+
+use crate::handler::{Handler, listener};
+use crate::handler::chain::SealBlockError;
+use crate::index::{Index, IndexUpdateError};
+
+use super::{MemoryStorage, signing_key};
+
+#[derive(Debug, thiserror::Error)]
+pub enum LoopbackError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Update(#[from] IndexUpdateError),
+
+    #[error(transparent)]
+    Seal(#[from] SealBlockError),
+
+    #[error("failed to start flowerpot node: {0}")]
+    Node(String)
+}
+
+/// Flowerpot node started by the loopback network.
+#[derive(Debug, Clone)]
+pub struct LoopbackNode {
+    /// Local address of the node's connections listener.
+    address: SocketAddr,
+
+    /// Garden protocol handler of the node.
+    handler: Handler
+}
+
+impl LoopbackNode {
+    #[inline(always)]
+    pub const fn address(&self) -> &SocketAddr {
+        &self.address
+    }
+
+    #[inline(always)]
+    pub const fn handler(&self) -> &Handler {
+        &self.handler
+    }
+}
+
+/// Network of flowerpot nodes connected through the loopback interface.
+///
+/// Nodes are started the same way as in the garden client: every node gets an
+/// in-memory blockchain storage, connects to all the previously started nodes
+/// as to bootstrap nodes, synchronizes the blockchain and starts listening to
+/// incoming connections on an ephemeral port.
+///
+/// Blocks are produced by the network's validator: it seals events sent by
+/// all the nodes' handlers into a block and broadcasts it from the first node,
+/// so other nodes receive the block only through the network.
+#[derive(Debug)]
+pub struct LoopbackNetwork {
+    /// Garden protocol blockchain address.
+    address: Address,
+
+    /// Signing key of the blockchain blocks.
+    validator: SigningKey,
+
+    nodes: Vec<LoopbackNode>
+}
+
+impl LoopbackNetwork {
+    /// Start network of provided amount of nodes.
+    pub fn start(nodes: usize) -> Result<Self, LoopbackError> {
+        let validator = signing_key(0);
+        let address = Address::new(validator.verifying_key(), 0);
+
+        let mut network = Self {
+            address,
+            validator,
+            nodes: Vec::with_capacity(nodes)
+        };
+
+        for i in 0..nodes {
+            let bootstrap = network.nodes.iter()
+                .map(|node| node.address)
+                .collect::<Vec<_>>();
+
+            let node = start_node(&network.address, &bootstrap, i as u64)?;
+
+            network.nodes.push(node);
+        }
+
+        Ok(network)
+    }
+
+    /// Get blockchain address of the network.
+    #[inline(always)]
+    pub const fn address(&self) -> &Address {
+        &self.address
+    }
+
+    /// Get list of started nodes.
+    #[inline(always)]
+    pub fn nodes(&self) -> &[LoopbackNode] {
+        &self.nodes
+    }
+
+    /// Get garden protocol handler of the node with provided index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there's no such node.
+    #[inline]
+    pub fn handler(&self, node: usize) -> &Handler {
+        &self.nodes[node].handler
+    }
+
+    /// Seal all the events sent by the nodes' handlers which are not stored
+    /// in the blockchain yet into a new block and broadcast it to the network.
+    ///
+    /// Return hash of the created block, or `None` if there are no events to
+    /// seal.
+    pub fn seal_block(&self) -> Result<Option<Hash>, LoopbackError> {
+        let Some(producer) = self.nodes.first() else {
+            return Ok(None);
+        };
+
+        let messages = self.nodes[1..].iter()
+            .flat_map(|peer| {
+                peer.handler.outbox()
+                    .entries()
+                    .map(|entry| entry.message().clone())
+                    .collect::<Vec<_>>()
+            });
+
+        Ok(producer.handler.seal_block(&self.validator, messages)?)
+    }
+
+    /// Update garden indexes of all the nodes.
+    pub fn update(&self) -> Result<(), LoopbackError> {
+        for node in &self.nodes {
+            node.handler.update()?;
+        }
+
+        Ok(())
+    }
+
+    /// Check if all the nodes have equal garden indexes.
+    pub fn is_converged(&self) -> bool {
+        let mut snapshots = self.nodes.iter()
+            .map(|node| IndexSnapshot::new(&node.handler.index()));
+
+        let Some(first) = snapshots.next() else {
+            return true;
+        };
+
+        snapshots.all(|snapshot| snapshot == first)
+    }
+
+    /// Update garden indexes of all the nodes until all of them index events
+    /// with provided message hashes and converge, or the timeout is reached.
+    ///
+    /// Return `true` if the indexes converged.
+    pub fn wait_converged(
+        &self,
+        messages: &[Hash],
+        timeout: Duration
+    ) -> Result<bool, LoopbackError> {
+        let started_at = Instant::now();
+
+        loop {
+            self.update()?;
+
+            let indexed = self.nodes.iter().all(|node| {
+                let snapshot = IndexSnapshot::new(&node.handler.index());
+
+                messages.iter().all(|message| snapshot.contains(message))
+            });
+
+            if indexed && self.is_converged() {
+                return Ok(true);
+            }
+
+            if started_at.elapsed() > timeout {
+                return Ok(false);
+            }
+
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+}
+
+/// Message hashes of the indexed events.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct IndexSnapshot {
+    posts: HashSet<Hash>,
+    comments: HashSet<Hash>,
+    reactions: HashSet<Hash>
+}
+
+impl IndexSnapshot {
+    fn new(index: &Index) -> Self {
+        Self {
+            posts: index.posts()
+                .map(|post| *post.message_hash())
+                .collect(),
+
+            comments: index.comments()
+                .map(|comment| *comment.message_hash())
+                .collect(),
+
+            reactions: index.reactions()
+                .map(|reaction| *reaction.message_hash())
+                .collect()
+        }
+    }
+
+    fn contains(&self, message_hash: &Hash) -> bool {
+        self.posts.contains(message_hash)
+            || self.comments.contains(message_hash)
+            || self.reactions.contains(message_hash)
+    }
+}
+
+/// Start flowerpot node with in-memory blockchain storage, connect it to the
+/// bootstrap nodes and start listening to incoming connections on an
+/// ephemeral loopback port.
+fn start_node(
+    address: &Address,
+    bootstrap: &[SocketAddr],
+    seed: u64
+) -> Result<LoopbackNode, LoopbackError> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+    let local_address = listener.local_addr()?;
+
+    let mut node = Node::default()
+        .add_storage(address.clone(), MemoryStorage::new());
+
+    let secret_key = SecretKey::random(&mut ChaCha20Rng::seed_from_u64(seed));
+
+    let options = PacketStreamOptions {
+        encryption_algorithms: vec![
+            PacketStreamEncryption::ChaCha20
+        ],
+
+        force_encryption: true
+    };
+
+    for bootstrap in bootstrap {
+        node.init_stream(&secret_key, &options, bootstrap.to_string())
+            .map_err(|err| LoopbackError::Node(err.to_string()))?;
+    }
+
+    node.sync().map_err(|err| LoopbackError::Node(err.to_string()))?;
+
+    let handler = node.start(NodeOptions {
+        messages_filter: Some(crate::messages_filter),
+
+        ..NodeOptions::default()
+    });
+
+    let handler = handler.map_err(|err| LoopbackError::Node(err.to_string()))?;
+
+    listener::listen(listener, handler.clone(), secret_key, options);
+
+    Ok(LoopbackNode {
+        address: local_address,
+        handler: Handler::new(address.clone(), handler)
+    })
+}
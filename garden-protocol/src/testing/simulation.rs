@@ -0,0 +1,797 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::{BTreeMap, HashSet};
+
+use rand_chacha::ChaCha8Rng;
+use rand_chacha::rand_core::{SeedableRng, RngCore};
+
+use flowerpot::address::Address;
+use flowerpot::crypto::hash::Hash;
+use flowerpot::crypto::sign::{SigningKey, SignatureError};
+use flowerpot::block::Block;
+use flowerpot::storage::{Storage, StorageError};
+use flowerpot::node::{Node, NodeOptions};
+
+use crate::{
+    Events, PostEvent, CommentEvent, ReactionEvent, Reaction, Content, Tag,
+    ValidationError, StampPolicy
+};
+
+use crate::handler::Handler;
+use crate::index::{Index, IndexUpdateError};
+
+use super::{MemoryStorage, ChainBuilder, signing_key};
+
+#[derive(Debug, thiserror::Error)]
+pub enum SimulationError {
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+
+    #[error(transparent)]
+    Update(#[from] IndexUpdateError),
+
+    #[error("failed to sign message or block: {0}")]
+    Signature(#[from] SignatureError),
+
+    #[error("failed to create event: {0}")]
+    Validation(#[from] ValidationError),
+
+    #[error("failed to start flowerpot node: {0}")]
+    Node(String),
+
+    #[error("node {node} has no blockchain storage")]
+    NoStorage {
+        node: usize
+    },
+
+    #[error("blockchain of node {node} has diverged")]
+    ChainDiverged {
+        node: usize
+    },
+
+    #[error("garden index of node {node} has diverged")]
+    IndexDiverged {
+        node: usize
+    },
+
+    #[error("node {node} doesn't follow the latest reaction rule")]
+    ReactionRule {
+        node: usize
+    }
+}
+
+/// Parameters of a network simulation. Probabilities are applied on every
+/// simulation step.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimulationConfig {
+    /// Seed of the simulation randomness.
+    pub seed: u64,
+
+    /// Amount of simulated nodes.
+    pub nodes: usize,
+
+    /// Amount of events authors.
+    pub authors: usize,
+
+    /// Amount of simulation steps.
+    pub steps: usize,
+
+    /// Minimal block delivery delay between two nodes in virtual
+    /// milliseconds.
+    pub min_delay: u64,
+
+    /// Maximal block delivery delay between two nodes in virtual
+    /// milliseconds.
+    pub max_delay: u64,
+
+    /// Probability to seal pending events into a new block.
+    pub block_probability: f64,
+
+    /// Probability to replace the latest blocks by a longer fork.
+    pub reorg_probability: f64,
+
+    /// Maximal amount of replaced blocks.
+    pub max_reorg_depth: usize,
+
+    /// Probability to split the network into two partitions, or to heal
+    /// the existing partition.
+    pub partition_probability: f64,
+
+    /// Probability to publish malformed event instead of a valid one.
+    pub malformed_probability: f64
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            nodes: 24,
+            authors: 8,
+            steps: 300,
+            min_delay: 5,
+            max_delay: 500,
+            block_probability: 0.15,
+            reorg_probability: 0.02,
+            max_reorg_depth: 3,
+            partition_probability: 0.02,
+            malformed_probability: 0.1
+        }
+    }
+}
+
+/// Summary of a finished simulation.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SimulationReport {
+    /// Virtual time spent by the simulation in milliseconds.
+    pub time: u64,
+
+    /// Amount of published valid events.
+    pub events: u64,
+
+    /// Amount of published malformed events.
+    pub malformed: u64,
+
+    /// Amount of sealed blocks, including forks.
+    pub blocks: u64,
+
+    /// Amount of forks replacing the latest blocks.
+    pub reorgs: u64,
+
+    /// Amount of network partitions.
+    pub partitions: u64,
+
+    /// Amount of delivered blocks.
+    pub deliveries: u64
+}
+
+/// Simulated node: garden protocol handler of a flowerpot node with an
+/// in-memory blockchain storage.
+///
+/// The flowerpot node has no connections: blocks are delivered by the
+/// simulation's transport and written directly to the node's storage, and
+/// the handler updates its index after every delivery.
+struct SimulatedNode {
+    handler: Handler,
+
+    /// Received blocks which are not part of the node's chain.
+    side_blocks: Vec<Block>
+}
+
+impl SimulatedNode {
+    fn start(address: &Address) -> Result<Self, SimulationError> {
+        let node = Node::default()
+            .add_storage(address.clone(), MemoryStorage::new())
+            .start(NodeOptions {
+                messages_filter: Some(crate::messages_filter),
+
+                ..NodeOptions::default()
+            })
+            .map_err(|err| SimulationError::Node(err.to_string()))?;
+
+        Ok(Self {
+            handler: Handler::new(address.clone(), node),
+            side_blocks: Vec::new()
+        })
+    }
+
+    /// Execute provided callback over the node's blockchain storage and the
+    /// list of its side blocks.
+    fn with_storage<T>(
+        &mut self,
+        node: usize,
+        callback: impl FnOnce(&dyn Storage, &mut Vec<Block>) -> Result<T, StorageError>
+    ) -> Result<T, SimulationError> {
+        let side_blocks = &mut self.side_blocks;
+
+        let result = self.handler.node()
+            .map_storage(self.handler.address(), |storage| callback(storage, side_blocks))
+            .ok_or(SimulationError::NoStorage { node })?;
+
+        Ok(result?)
+    }
+
+    /// Receive a block: store it, switch to the longest known chain and
+    /// update the garden index. Return `false` if the block is already known.
+    fn receive(&mut self, node: usize, block: Block) -> Result<bool, SimulationError> {
+        let is_new = self.with_storage(node, |storage, side_blocks| {
+            let is_known = storage.has_block(block.hash())?
+                || side_blocks.iter().any(|side_block| side_block.hash() == block.hash());
+
+            if is_known {
+                return Ok(false);
+            }
+
+            side_blocks.push(block);
+
+            apply_longest_chain(storage, side_blocks)?;
+
+            Ok(true)
+        })?;
+
+        if is_new {
+            self.handler.update()?;
+        }
+
+        Ok(is_new)
+    }
+}
+
+/// Get position of the block with provided hash in the chain (root block has
+/// zero height). Return `None` if the storage doesn't have this block.
+fn height(storage: &dyn Storage, hash: &Hash) -> Result<Option<usize>, StorageError> {
+    if !storage.has_block(hash)? {
+        return Ok(None);
+    }
+
+    let mut height = 0;
+    let mut hash = *hash;
+
+    while let Some(prev_hash) = storage.prev_block(&hash)? {
+        height += 1;
+        hash = prev_hash;
+    }
+
+    Ok(Some(height))
+}
+
+/// Find the longest chain of side blocks starting from the one with provided
+/// index.
+fn longest_path(side_blocks: &[Block], from: usize) -> Vec<usize> {
+    let hash = side_blocks[from].hash();
+
+    let mut longest = Vec::new();
+
+    for (i, block) in side_blocks.iter().enumerate() {
+        if block.prev_hash() == hash {
+            let path = longest_path(side_blocks, i);
+
+            if path.len() > longest.len() {
+                longest = path;
+            }
+        }
+    }
+
+    longest.insert(0, from);
+
+    longest
+}
+
+/// Switch to the longest known chain if it's longer than the stored one.
+fn apply_longest_chain(
+    storage: &dyn Storage,
+    side_blocks: &mut Vec<Block>
+) -> Result<(), StorageError> {
+    let mut best_len = match storage.tail_block()? {
+        Some(tail) => height(storage, &tail)?.map_or(0, |height| height + 1),
+        None => 0
+    };
+
+    let mut best_path = None;
+
+    for (i, block) in side_blocks.iter().enumerate() {
+        let base = if block.prev_hash() == &Hash::ZERO {
+            Some(0)
+        } else {
+            height(storage, block.prev_hash())?.map(|height| height + 1)
+        };
+
+        let Some(base) = base else {
+            continue;
+        };
+
+        let path = longest_path(side_blocks, i);
+
+        if base + path.len() > best_len {
+            best_len = base + path.len();
+            best_path = Some(path);
+        }
+    }
+
+    let Some(path) = best_path else {
+        return Ok(());
+    };
+
+    let blocks = path.iter()
+        .map(|i| side_blocks[*i].clone())
+        .collect::<Vec<_>>();
+
+    for block in &blocks {
+        storage.write_block(block)?;
+    }
+
+    side_blocks.retain(|block| {
+        !blocks.iter().any(|applied| applied.hash() == block.hash())
+    });
+
+    Ok(())
+}
+
+/// Block sent from one node to another.
+#[derive(Debug, Clone)]
+struct Envelope {
+    /// Sender node, or `None` if the block is sent by the validator.
+    from: Option<usize>,
+
+    to: usize,
+    block: Block
+}
+
+/// Deterministic in-process simulation of a garden network.
+///
+/// Every simulated node runs a garden protocol handler of a flowerpot node
+/// with an in-memory blockchain storage. Blocks are delivered between the
+/// nodes by an in-memory transport with random delays measured by a virtual
+/// clock, nodes follow the longest known chain and update their indexes after
+/// every delivered block. The simulation randomly publishes valid and
+/// malformed events, seals them into blocks, replaces the latest blocks by
+/// longer forks and splits the network into partitions.
+///
+/// When all the steps are done the network is healed, all the blocks are
+/// delivered and the following invariants are checked:
+///
+/// 1. All the nodes store the validator's chain.
+/// 2. Garden indexes of all the nodes are equal to the index built from
+///    scratch.
+/// 3. Only one reaction of each identity to each target is counted, and it's
+///    the latest one in a hand-built scenario of reactions published in
+///    different blocks.
+///
+/// Simulation schedule is fully defined by the seed. Block timestamps are
+/// taken from the system clock by flowerpot so block hashes differ between
+/// runs.
+pub struct Simulation {
+    config: SimulationConfig,
+    rng: ChaCha8Rng,
+
+    /// Virtual clock in milliseconds.
+    now: u64,
+
+    /// Sequence number of the last sent envelope, used to order envelopes
+    /// delivered at the same time.
+    sequence: u64,
+
+    authors: Vec<SigningKey>,
+
+    /// Address of the simulated blockchain.
+    address: Address,
+
+    /// Validator's chain.
+    chain: ChainBuilder,
+
+    nodes: Vec<SimulatedNode>,
+
+    /// Envelopes in flight ordered by their delivery time.
+    queue: BTreeMap<(u64, u64), Envelope>,
+
+    /// Side of each node while the network is partitioned.
+    partition: Option<Vec<bool>>,
+
+    /// Envelopes which can't be delivered until the partition is healed.
+    held: Vec<Envelope>,
+
+    /// Message hashes of published posts and comments.
+    targets: Vec<Hash>,
+
+    report: SimulationReport
+}
+
+impl Simulation {
+    /// Create new simulation and start its nodes.
+    pub fn new(config: SimulationConfig) -> Result<Self, SimulationError> {
+        let validator = signing_key(0);
+        let address = Address::new(validator.verifying_key(), 0);
+
+        let nodes = (0..config.nodes)
+            .map(|_| SimulatedNode::start(&address))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            config,
+            rng: ChaCha8Rng::seed_from_u64(config.seed),
+            now: 0,
+            sequence: 0,
+
+            authors: (0..config.authors)
+                .map(|i| signing_key(i as u8 + 1))
+                .collect(),
+
+            address,
+            chain: ChainBuilder::new(validator),
+
+            nodes,
+
+            queue: BTreeMap::new(),
+            partition: None,
+            held: Vec::new(),
+            targets: Vec::new(),
+            report: SimulationReport::default()
+        })
+    }
+
+    /// Run the simulation and check its invariants.
+    pub fn run(mut self) -> Result<SimulationReport, SimulationError> {
+        for _ in 0..self.config.steps {
+            self.step()?;
+        }
+
+        self.finish()?;
+
+        Ok(self.report)
+    }
+
+    /// Get random number in `[0, max)` range.
+    fn below(&mut self, max: usize) -> usize {
+        (self.rng.next_u64() % max.max(1) as u64) as usize
+    }
+
+    /// Return `true` with provided probability.
+    fn chance(&mut self, probability: f64) -> bool {
+        (self.rng.next_u64() as f64 / u64::MAX as f64) < probability
+    }
+
+    fn author(&mut self) -> SigningKey {
+        let i = self.below(self.authors.len());
+
+        self.authors[i].clone()
+    }
+
+    fn step(&mut self) -> Result<(), SimulationError> {
+        self.now += 1 + self.below(50) as u64;
+
+        if self.chance(self.config.malformed_probability) {
+            self.publish_malformed()?;
+        } else {
+            self.publish_event()?;
+        }
+
+        if self.chance(self.config.block_probability) {
+            self.seal_block()?;
+        }
+
+        if self.chance(self.config.reorg_probability) {
+            self.reorg()?;
+        }
+
+        if self.chance(self.config.partition_probability) {
+            self.toggle_partition();
+        }
+
+        self.deliver(self.now)
+    }
+
+    fn publish_event(&mut self) -> Result<(), SimulationError> {
+        let author = self.author();
+        let number = self.report.events;
+
+        let kind = if self.targets.is_empty() {
+            0
+        } else {
+            self.below(3)
+        };
+
+        match kind {
+            0 => {
+                const TAGS: &[&str] = &["sport", "news", "nsfw", "spoiler"];
+
+                let tag = Tag::new(TAGS[self.below(TAGS.len())])?;
+
+                let post = PostEvent::new(
+                    Content::new(format!("post #{number}"))?,
+                    [tag]
+                )?;
+
+                let hash = self.chain.event(&author, post)?;
+
+                self.targets.push(hash);
+            }
+
+            1 => {
+                let target = self.targets[self.below(self.targets.len())];
+
+                let comment = CommentEvent::new(
+                    target,
+                    Content::new(format!("comment #{number}"))?
+                );
+
+                let hash = self.chain.event(&author, comment)?;
+
+                self.targets.push(hash);
+            }
+
+            _ => {
+                let target = self.targets[self.below(self.targets.len())];
+
+                let reaction = if self.chance(0.5) {
+                    Reaction::ThumbUp
+                } else {
+                    Reaction::ThumbDown
+                };
+
+                self.chain.event(&author, ReactionEvent::new(target, reaction))?;
+            }
+        }
+
+        self.report.events += 1;
+
+        Ok(())
+    }
+
+    fn publish_malformed(&mut self) -> Result<(), SimulationError> {
+        let author = self.author();
+
+        let post = PostEvent::new(
+            Content::new(format!("malformed #{}", self.report.malformed))?,
+            [Tag::new("malformed")?]
+        )?;
+
+        let mut bytes = Events::from(post).to_bytes().into_vec();
+
+        match self.below(4) {
+            // Random bytes.
+            0 => {
+                bytes = (0..self.below(64))
+                    .map(|_| self.rng.next_u64() as u8)
+                    .collect();
+            }
+
+            // Truncated event.
+            1 => {
+                let len = self.below(bytes.len());
+
+                bytes.truncate(len);
+            }
+
+            // Corrupted event.
+            2 => {
+                let i = self.below(bytes.len());
+
+                bytes[i] ^= 1 << self.below(8);
+            }
+
+            // Random body of a known event.
+            _ => {
+                let id = self.below(Events::V1_GROUP_MESSAGE as usize + 1) as u16;
+
+                bytes = id.to_le_bytes().to_vec();
+
+                bytes.extend((0..self.below(64)).map(|_| self.rng.next_u64() as u8));
+            }
+        }
+
+        // Decoding must never panic.
+        let _ = Events::from_bytes(&bytes);
+
+        self.chain.data(&author, bytes)?;
+
+        self.report.malformed += 1;
+
+        Ok(())
+    }
+
+    /// Seal pending events into a new block and send it to a random node.
+    fn seal_block(&mut self) -> Result<(), SimulationError> {
+        self.chain.block()?;
+
+        let block = self.chain.blocks()[self.chain.blocks().len() - 1].clone();
+        let to = self.below(self.nodes.len());
+
+        self.send(None, to, block);
+
+        self.report.blocks += 1;
+
+        Ok(())
+    }
+
+    /// Replace the latest blocks by a longer fork with the same events.
+    fn reorg(&mut self) -> Result<(), SimulationError> {
+        let depth = 1 + self.below(self.config.max_reorg_depth);
+
+        // Keep the root block.
+        if self.chain.blocks().len() <= depth {
+            return Ok(());
+        }
+
+        let mut messages = self.chain.rollback(depth)
+            .iter()
+            .flat_map(|block| block.inline_messages().to_vec())
+            .collect::<Vec<_>>();
+
+        // Shuffle events of the replaced blocks.
+        for i in (1..messages.len()).rev() {
+            let j = self.below(i + 1);
+
+            messages.swap(i, j);
+        }
+
+        for i in 0..=depth {
+            let amount = if i == depth {
+                messages.len()
+            } else {
+                self.below(messages.len() + 1)
+            };
+
+            for message in messages.drain(..amount) {
+                self.chain.message(message);
+            }
+
+            self.seal_block()?;
+        }
+
+        self.report.reorgs += 1;
+
+        Ok(())
+    }
+
+    /// Split the network into two partitions or heal the existing one.
+    fn toggle_partition(&mut self) {
+        if self.partition.take().is_some() {
+            for envelope in std::mem::take(&mut self.held) {
+                self.send(envelope.from, envelope.to, envelope.block);
+            }
+        } else {
+            let partition = (0..self.nodes.len())
+                .map(|_| self.chance(0.5))
+                .collect();
+
+            self.partition = Some(partition);
+
+            self.report.partitions += 1;
+        }
+    }
+
+    /// Send block to the node with a random delay.
+    fn send(&mut self, from: Option<usize>, to: usize, block: Block) {
+        let delay = self.config.min_delay + self.below(
+            self.config.max_delay.saturating_sub(self.config.min_delay) as usize + 1
+        ) as u64;
+
+        self.sequence += 1;
+
+        self.queue.insert((self.now + delay, self.sequence), Envelope {
+            from,
+            to,
+            block
+        });
+    }
+
+    /// Deliver all the envelopes sent before provided time.
+    fn deliver(&mut self, until: u64) -> Result<(), SimulationError> {
+        while self.queue.first_key_value().is_some_and(|((time, _), _)| *time <= until) {
+            let Some((_, envelope)) = self.queue.pop_first() else {
+                break;
+            };
+
+            // Nodes from different partitions can't reach each other.
+            if let (Some(partition), Some(from)) = (&self.partition, envelope.from)
+                && partition[from] != partition[envelope.to]
+            {
+                self.held.push(envelope);
+
+                continue;
+            }
+
+            self.report.deliveries += 1;
+
+            let is_new = self.nodes[envelope.to]
+                .receive(envelope.to, envelope.block.clone())?;
+
+            if !is_new {
+                continue;
+            }
+
+            // Gossip the new block to all the other nodes.
+            for peer in 0..self.nodes.len() {
+                if peer != envelope.to {
+                    self.send(Some(envelope.to), peer, envelope.block.clone());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Deliver all the blocks and check the simulation invariants.
+    fn finish(&mut self) -> Result<(), SimulationError> {
+        if self.partition.is_some() {
+            self.toggle_partition();
+        }
+
+        if self.chain.pending() > 0 {
+            self.seal_block()?;
+        }
+
+        // Hand-built reaction scenario: a new identity reacts to a new post
+        // twice in different blocks, so only the second reaction must be
+        // counted by all the nodes.
+        let reader = signing_key(u8::MAX);
+        let author = self.author();
+
+        let post = self.chain.event(&author, PostEvent::new(
+            Content::new("reactions scenario")?,
+            [Tag::new("scenario")?]
+        )?)?;
+
+        self.chain.event(&reader, ReactionEvent::new(post, Reaction::ThumbUp))?;
+        self.seal_block()?;
+
+        let latest = self.chain.event(&reader, ReactionEvent::new(post, Reaction::ThumbDown))?;
+
+        self.seal_block()?;
+
+        while let Some(((time, _), _)) = self.queue.first_key_value() {
+            self.now = self.now.max(*time);
+
+            self.deliver(self.now)?;
+        }
+
+        self.report.time = self.now;
+
+        let storage = self.chain.storage();
+
+        let mut reference = Index::with_stamp_policy(
+            self.address.clone(),
+            StampPolicy::default()
+        );
+
+        reference.update(&storage)?;
+
+        for (i, node) in self.nodes.iter_mut().enumerate() {
+            node.handler.update()?;
+
+            let tail_block = node.with_storage(i, |storage, _| storage.tail_block())?;
+
+            if tail_block != storage.tail_block()? {
+                return Err(SimulationError::ChainDiverged { node: i });
+            }
+
+            let index = node.handler.index();
+
+            if *index != reference {
+                return Err(SimulationError::IndexDiverged { node: i });
+            }
+
+            // Only one reaction of each identity to each target is counted.
+            let mut counted = HashSet::new();
+
+            let is_unique = index.reactions().all(|reaction| {
+                counted.insert((reaction.identity().clone(), *reaction.ref_message_hash()))
+            });
+
+            let scenario = index.reactions_of(&post)
+                .map(|reaction| (reaction.identity(), reaction.message_hash()))
+                .collect::<Vec<_>>();
+
+            if !is_unique || scenario != [(&reader.verifying_key(), &latest)] {
+                return Err(SimulationError::ReactionRule { node: i });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for Simulation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Simulation")
+            .field("config", &self.config)
+            .field("now", &self.now)
+            .field("report", &self.report)
+            .finish()
+    }
+}
@@ -0,0 +1,209 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use flowerpot::crypto::hash::Hash;
+
+use garden_protocol::*;
+use garden_protocol::archive::*;
+use garden_protocol::index::Index;
+use garden_protocol::testing::*;
+
+fn post(content: &str) -> PostEvent {
+    PostEvent::new(Content::new(content).unwrap(), []).unwrap()
+}
+
+fn comment(ref_message_hash: Hash, content: &str) -> CommentEvent {
+    CommentEvent::new(ref_message_hash, Content::new(content).unwrap())
+}
+
+fn export() -> Archive {
+    let author = signing_key(1);
+    let reader = signing_key(2);
+    let device = signing_key(3);
+
+    let mut chain = ChainBuilder::new(signing_key(0));
+
+    let post_hash = chain.event(&author, post("hello, world!")).unwrap();
+
+    chain.event(&reader, post("not mine")).unwrap();
+    chain.block().unwrap();
+
+    chain.event(&reader, comment(post_hash, "hi!")).unwrap();
+    chain.event(&author, comment(post_hash, "hello!")).unwrap();
+    chain.event(&author, ReactionEvent::new(post_hash, Reaction::ThumbUp)).unwrap();
+    chain.event(&author, DelegationEvent::authorize(device.verifying_key())).unwrap();
+    chain.event(&author, ReportEvent::new(post_hash, ReportReason::Spam, Content::new("").unwrap())).unwrap();
+    chain.block().unwrap();
+
+    // Events signed by the delegated device key belong to the author.
+    chain.event(&device, DelegationEvent::accept(author.verifying_key())).unwrap();
+    chain.block().unwrap();
+
+    chain.event(&device, post("from device")).unwrap();
+    chain.block().unwrap();
+
+    let storage = chain.storage();
+
+    let mut index = Index::default();
+
+    index.update(&storage).unwrap();
+
+    Archive::export(&storage, &index, &author.verifying_key()).unwrap()
+}
+
+/// Encode archive with a modified manifest.
+fn encode(manifest: &ArchiveManifest, archive: &Archive) -> Archive {
+    let mut bytes = Archive::MAGIC.to_vec();
+
+    bytes.push(Archive::VERSION);
+
+    for data in std::iter::once(serde_json::to_vec(manifest).unwrap())
+        .chain(archive.messages().iter().map(|message| message.to_bytes().to_vec()))
+    {
+        bytes.extend((data.len() as u32).to_le_bytes());
+        bytes.extend(data);
+    }
+
+    Archive::from_bytes(&bytes).unwrap()
+}
+
+#[test]
+fn export_archive() {
+    let archive = export();
+
+    let kinds = archive.manifest().entries.iter()
+        .map(|entry| entry.kind)
+        .collect::<Vec<_>>();
+
+    assert_eq!(kinds, [
+        ArchiveEventKind::Post,
+        ArchiveEventKind::Comment,
+        ArchiveEventKind::Reaction,
+        ArchiveEventKind::Delegation,
+        ArchiveEventKind::Delegation,
+        ArchiveEventKind::Post
+    ]);
+
+    let author = signing_key(1).verifying_key();
+    let device = signing_key(3).verifying_key();
+
+    let signers = archive.manifest().entries.iter()
+        .map(|entry| &entry.signer)
+        .collect::<Vec<_>>();
+
+    assert_eq!(signers, [&author, &author, &author, &author, &device, &device]);
+
+    assert_eq!(archive.messages().len(), 6);
+    assert_eq!(archive.manifest().author, author);
+    assert_eq!(archive.manifest().keys, [author, device]);
+
+    archive.verify().unwrap();
+}
+
+#[test]
+fn verify_archive_signers() {
+    let archive = export();
+
+    let mut manifest = archive.manifest().clone();
+
+    // Device key is not a key of the author's identity anymore.
+    manifest.keys.retain(|key| key != &signing_key(3).verifying_key());
+
+    assert!(matches!(
+        encode(&manifest, &archive).verify(),
+        Err(ArchiveError::InvalidSignature(_))
+    ));
+}
+
+#[test]
+fn verify_archive_foreign_key() {
+    let archive = export();
+
+    let foreign = signing_key(5).verifying_key();
+
+    let mut manifest = archive.manifest().clone();
+
+    // Foreign key is not authorized by any archived identity event.
+    manifest.keys.push(foreign.clone());
+
+    assert!(matches!(
+        encode(&manifest, &archive).verify(),
+        Err(ArchiveError::UnknownKey(key)) if key == foreign
+    ));
+}
+
+#[test]
+fn encode_archive() {
+    let archive = export();
+
+    let bytes = archive.to_bytes().unwrap();
+    let decoded = Archive::from_bytes(&bytes).unwrap();
+
+    assert_eq!(decoded.manifest(), archive.manifest());
+
+    decoded.verify().unwrap();
+
+    assert!(matches!(Archive::from_bytes(&bytes[1..]), Err(ArchiveError::InvalidFormat)));
+    assert!(matches!(Archive::from_bytes(&bytes[..bytes.len() - 1]), Err(ArchiveError::InvalidFormat)));
+
+    // Replace the author key everywhere in the manifest.
+    let author = signing_key(1).verifying_key().to_base64();
+    let another = signing_key(2).verifying_key().to_base64();
+
+    let mut tampered = bytes.clone();
+
+    while let Some(position) = tampered.windows(author.len())
+        .position(|window| window == author.as_bytes())
+    {
+        tampered[position..position + author.len()].copy_from_slice(another.as_bytes());
+    }
+
+    // Device key is not reachable from the replaced author key.
+    assert!(matches!(
+        Archive::from_bytes(&tampered).unwrap().verify(),
+        Err(ArchiveError::UnknownKey(key)) if key == signing_key(3).verifying_key()
+    ));
+}
+
+#[test]
+fn republish_archive() {
+    let archive = export();
+
+    let archive = Archive::from_bytes(archive.to_bytes().unwrap()).unwrap();
+
+    archive.verify().unwrap();
+
+    // Archived messages keep their hashes on another chain.
+    let mut chain = ChainBuilder::new(signing_key(4));
+
+    for message in archive.messages() {
+        chain.message(message.clone());
+    }
+
+    chain.block().unwrap();
+
+    let mut index = Index::default();
+
+    index.update(&chain.storage()).unwrap();
+
+    assert_eq!(index.posts().len(), 2);
+    assert_eq!(index.comments().len(), 1);
+    assert_eq!(index.reactions().count(), 1);
+    assert_eq!(index.delegations().count(), 1);
+    assert_eq!(index.comments_of(index.posts().next().unwrap().message_hash()).count(), 1);
+}
@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use flowerpot::crypto::hash::Hash;
+
+use time::UtcDateTime;
+
+use garden_protocol::*;
+use garden_protocol::handler::cache::InfoCache;
+use garden_protocol::index::post::PostInfo;
+use garden_protocol::testing::*;
+
+fn post(i: u8) -> PostInfo {
+    let author = signing_key(1).verifying_key();
+
+    PostInfo {
+        block_hash: Hash::ZERO,
+        message_hash: Hash::from([i; 32]),
+        author: author.clone(),
+        identity: author,
+        signed_by_revoked_key: false,
+        timestamp: UtcDateTime::UNIX_EPOCH,
+        content: Content::new(format!("post {i}")).unwrap(),
+        tags: Box::new([])
+    }
+}
+
+#[test]
+fn least_recently_used() {
+    let mut cache = InfoCache::new(2);
+
+    cache.insert_post(post(1));
+    cache.insert_post(post(2));
+
+    // Mark the first post as recently used.
+    assert_eq!(cache.post(&Hash::from([1; 32])), Some(&post(1)));
+
+    cache.insert_post(post(3));
+
+    assert_eq!(cache.len(), 2);
+    assert!(cache.post(&Hash::from([1; 32])).is_some());
+    assert!(cache.post(&Hash::from([2; 32])).is_none());
+    assert!(cache.post(&Hash::from([3; 32])).is_some());
+
+    cache.resize(1);
+
+    assert_eq!(cache.capacity(), 1);
+    assert_eq!(cache.len(), 1);
+    assert!(cache.post(&Hash::from([3; 32])).is_some());
+
+    cache.clear();
+
+    assert!(cache.is_empty());
+}
@@ -0,0 +1,161 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use flowerpot::crypto::base64;
+use flowerpot::message::Message;
+use flowerpot::storage::Storage;
+use flowerpot::node::{Node, NodeOptions};
+
+use garden_protocol::*;
+use garden_protocol::index::Index;
+use garden_protocol::handler::Handler;
+use garden_protocol::handler::outbox::DeliveryStatus;
+use garden_protocol::handler::chain::{Invite, CreateChainError};
+use garden_protocol::testing::*;
+
+#[test]
+fn create_chain() {
+    let root_signer = signing_key(1);
+    let storage = MemoryStorage::new();
+
+    let address = Handler::create_chain(&root_signer, 42, &storage).unwrap();
+
+    assert_eq!(storage.len(), 1);
+    assert!(storage.root_block().unwrap().is_some());
+
+    let other = Handler::create_chain(&signing_key(1), 43, &MemoryStorage::new()).unwrap();
+
+    assert_ne!(address, other);
+
+    assert!(matches!(
+        Handler::create_chain(&root_signer, 42, &storage),
+        Err(CreateChainError::StorageNotEmpty)
+    ));
+
+    let mut index = Index::default();
+
+    index.update(&storage).unwrap();
+
+    assert_eq!(index.lag(&storage).unwrap(), 0);
+    assert_eq!(index.statistics().total(), 0);
+}
+
+#[test]
+fn seal_created_chain() {
+    let root_signer = signing_key(1);
+    let storage = MemoryStorage::new();
+
+    let address = Handler::create_chain(&root_signer, 42, &storage).unwrap();
+
+    let node = Node::default()
+        .add_storage(address.clone(), storage)
+        .start(NodeOptions::default())
+        .unwrap();
+
+    let handler = Handler::new(address, node);
+
+    assert_eq!(handler.seal_block(&root_signer, []).unwrap(), None);
+
+    let alice = signing_key(2);
+    let bob = signing_key(3);
+
+    let post = handler.send_post(&alice, PostEvent::new(
+        Content::new("hello, new garden!").unwrap(),
+        []
+    ).unwrap()).wait().unwrap();
+
+    // Event received from another node of the garden.
+    let comment = Message::create(
+        &bob,
+        Events::from(CommentEvent::new(post, Content::new("hi!").unwrap())).to_bytes()
+    ).unwrap();
+
+    let block_hash = handler.seal_block(&root_signer, [comment.clone()])
+        .unwrap()
+        .unwrap();
+
+    // Everything is already sealed.
+    assert_eq!(handler.seal_block(&root_signer, [comment.clone()]).unwrap(), None);
+
+    handler.update().unwrap();
+
+    assert_eq!(
+        handler.outbox().status(&post),
+        Some(&DeliveryStatus::Included { block_hash })
+    );
+
+    assert_eq!(handler.lag().unwrap(), 0);
+
+    let index = handler.index();
+
+    assert_eq!(index.posts().len(), 1);
+    assert_eq!(index.comments_of(&post).count(), 1);
+}
+
+#[test]
+fn invite() {
+    let address = Handler::create_chain(&signing_key(1), 0, &MemoryStorage::new()).unwrap();
+
+    let invite = Invite {
+        name: String::from("Team garden"),
+        address,
+        stamps: StampPolicy {
+            post: 16,
+            comment: 12,
+            ..StampPolicy::default()
+        },
+        bootstrap: vec![
+            String::from("127.0.0.1:13400"),
+            String::from("[::1]:13401")
+        ]
+    };
+
+    let encoded = invite.to_base64();
+
+    assert_eq!(Invite::from_base64(&encoded), Some(invite.clone()));
+    assert_eq!(Invite::from_base64(format!(" {encoded}\n")), Some(invite.clone()));
+
+    let invite = Invite {
+        bootstrap: Vec::new(),
+        ..invite
+    };
+
+    assert_eq!(Invite::from_base64(invite.to_base64()), Some(invite));
+
+    // Too long names are truncated at a character boundary.
+    let invite = Invite {
+        name: "🌷".repeat(u16::MAX as usize / 4 + 1),
+        ..invite
+    };
+
+    let decoded = Invite::from_base64(invite.to_base64()).unwrap();
+
+    assert_eq!(decoded.name, "🌷".repeat(u16::MAX as usize / 4));
+    assert_eq!(decoded.bootstrap, invite.bootstrap);
+
+    // Invites of unknown versions are rejected.
+    let mut future = base64::decode(&encoded).unwrap();
+
+    future[0] = u8::MAX;
+
+    assert_eq!(Invite::from_base64(base64::encode(future)), None);
+
+    assert_eq!(Invite::from_base64(""), None);
+    assert_eq!(Invite::from_base64("not an invite"), None);
+    assert_eq!(Invite::from_base64(&encoded[..encoded.len() / 2]), None);
+}
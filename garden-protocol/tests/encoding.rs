@@ -0,0 +1,126 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use flowerpot::crypto::hash::Hash;
+
+use garden_protocol::*;
+
+/// Encoded post with content "garden " repeated 32 times and "test" tag.
+const POST_VECTOR: &str = "030001ddc5b10d00000404401283584d42740a7e60a3d843754b69ed51fa1616c4e000";
+
+/// Encoded comment referencing `[1; 32]` hash with content "hello, world! "
+/// repeated 16 times.
+const COMMENT_VECTOR: &str = "040001ddc7310900000804406ca2bbb1141c1e1e5cac6f030378db89dc2a01ba0e1b61fa6f0b";
+
+fn from_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn post() -> PostEvent {
+    PostEvent::new(
+        Content::new("garden ".repeat(32)).unwrap(),
+        [Tag::new("test").unwrap()]
+    ).unwrap()
+}
+
+fn comment() -> CommentEvent {
+    CommentEvent::new(
+        Hash::from([1; Hash::SIZE]),
+        Content::new("hello, world! ".repeat(16)).unwrap()
+    )
+}
+
+#[test]
+fn encode_post() {
+    let bytes = Events::from(post()).to_bytes();
+
+    assert_eq!(bytes.as_ref(), from_hex(POST_VECTOR));
+}
+
+#[test]
+fn decode_post() {
+    let Events::Post(event) = Events::from_bytes(from_hex(POST_VECTOR)).unwrap() else {
+        panic!("invalid event type");
+    };
+
+    assert_eq!(event, post());
+}
+
+#[test]
+fn encode_comment() {
+    let bytes = Events::from(comment()).to_bytes();
+
+    assert_eq!(bytes.as_ref(), from_hex(COMMENT_VECTOR));
+}
+
+#[test]
+fn decode_comment() {
+    let Events::Comment(event) = Events::from_bytes(from_hex(COMMENT_VECTOR)).unwrap() else {
+        panic!("invalid event type");
+    };
+
+    assert_eq!(event, comment());
+}
+
+#[test]
+fn small_events_are_plain() {
+    let post = PostEvent::new(Content::new("hi").unwrap(), []).unwrap();
+
+    let bytes = Events::from(post.clone()).to_bytes();
+
+    assert_eq!(bytes.as_ref(), [0, 0, 2, 0, b'h', b'i', 0]);
+    assert_eq!(bytes, Events::from(post).to_bytes_with_encoding(Encoding::Plain));
+}
+
+#[test]
+fn plain_encoded_events() {
+    let mut bytes = vec![3, 0, Encoding::PLAIN];
+
+    bytes.extend(post().to_bytes());
+
+    let Events::Post(event) = Events::from_bytes(bytes).unwrap() else {
+        panic!("invalid event type");
+    };
+
+    assert_eq!(event, post());
+}
+
+#[test]
+fn unknown_encoding() {
+    let result = Events::from_bytes([3, 0, 255, 0, 0, 0]);
+
+    assert!(matches!(
+        result,
+        Err(EventDecodeError::Encoding(EncodingError::UnknownEncoding(255)))
+    ));
+}
+
+#[test]
+fn decompression_bomb() {
+    let mut bytes = vec![3, 0, Encoding::DEFLATE];
+
+    bytes.extend(Encoding::Deflate.encode(&vec![0; 1 << 20]));
+
+    assert!(matches!(
+        Events::from_bytes(bytes),
+        Err(EventDecodeError::Encoding(EncodingError::TooLarge(PostEvent::MAX_SIZE)))
+    ));
+}
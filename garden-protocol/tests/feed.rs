@@ -0,0 +1,176 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use garden_protocol::*;
+use garden_protocol::feed::*;
+use garden_protocol::index::Index;
+use garden_protocol::testing::*;
+
+use time::{UtcDateTime, Date, Month, Time};
+
+#[test]
+fn parse_query() {
+    let query = FeedQuery::parse("tag:sport -tag:nsfw since:2026-01-01 reactions>10 is:reply \"Hello World\"").unwrap();
+
+    assert_eq!(query.terms(), &[
+        Term {
+            negated: false,
+            filter: Filter::Tag(Tag::new("sport").unwrap())
+        },
+        Term {
+            negated: true,
+            filter: Filter::Tag(Tag::new("nsfw").unwrap())
+        },
+        Term {
+            negated: false,
+            filter: Filter::Since(UtcDateTime::new(
+                Date::from_calendar_date(2026, Month::January, 1).unwrap(),
+                Time::MIDNIGHT
+            ))
+        },
+        Term {
+            negated: false,
+            filter: Filter::Count(Relation::Reactions, Comparison::Greater, 10)
+        },
+        Term {
+            negated: false,
+            filter: Filter::Is(Kind::Reply)
+        },
+        Term {
+            negated: false,
+            filter: Filter::Text(String::from("hello world"))
+        }
+    ]);
+}
+
+#[test]
+fn display_query() {
+    let query = "tag:sport -tag:nsfw before:2026-02-03 comments<=5 -has:reactions \"hello world\" rust";
+
+    assert_eq!(
+        FeedQuery::parse(query).unwrap().to_string(),
+        "tag:sport -tag:nsfw before:2026-02-03 comments<=5 -reactions>0 \"hello world\" rust"
+    );
+}
+
+#[test]
+fn plan_query() {
+    let plan = FeedQuery::parse("tag:sport").unwrap().plan();
+
+    assert!(plan.includes_posts());
+    assert!(!plan.includes_replies());
+
+    let plan = FeedQuery::parse("-is:post").unwrap().plan();
+
+    assert!(!plan.includes_posts());
+    assert!(plan.includes_replies());
+}
+
+#[test]
+fn parse_errors() {
+    fn error(query: &str) -> ParseError {
+        FeedQuery::parse(query).unwrap_err()
+    }
+
+    assert_eq!(error("tag:sport color:red"), ParseError {
+        position: 10,
+        kind: ParseErrorKind::UnknownFilter(String::from("color"))
+    });
+
+    assert_eq!(error("since:"), ParseError {
+        position: 0,
+        kind: ParseErrorKind::MissingValue(String::from("since"))
+    });
+
+    assert_eq!(error("since:2026-13-01").kind, ParseErrorKind::InvalidDate(String::from("2026-13-01")));
+    assert_eq!(error("before:yesterday").kind, ParseErrorKind::InvalidDate(String::from("yesterday")));
+    assert_eq!(error("reactions>many").kind, ParseErrorKind::InvalidNumber(String::from("many")));
+    assert_eq!(error("author:invalid").kind, ParseErrorKind::InvalidKey(String::from("invalid")));
+    assert_eq!(error("\"hello").kind, ParseErrorKind::UnterminatedQuote);
+    assert_eq!(error("rust - sport").kind, ParseErrorKind::EmptyNegation);
+
+    assert!(matches!(error("tag:-sport").kind, ParseErrorKind::InvalidTag(_)));
+    assert!(matches!(error("is:video").kind, ParseErrorKind::UnknownValue { .. }));
+
+    assert_eq!(
+        error("rust is:video").to_string(),
+        "unknown value 'video' of filter 'is', expected one of: post, reply (at position 6)"
+    );
+}
+
+#[test]
+fn author_filter() {
+    let old_key = signing_key(1);
+    let new_key = signing_key(2);
+    let device = signing_key(3);
+    let stranger = signing_key(4);
+
+    let mut chain = ChainBuilder::new(signing_key(0));
+
+    let post = |content: &str| PostEvent::new(Content::new(content).unwrap(), []).unwrap();
+
+    chain.event(&old_key, DelegationEvent::authorize(device.verifying_key())).unwrap();
+    chain.event(&device, DelegationEvent::accept(old_key.verifying_key())).unwrap();
+    chain.block().unwrap();
+
+    let old_post = chain.event(&old_key, post("signed by old key")).unwrap();
+    let device_post = chain.event(&device, post("signed by device")).unwrap();
+
+    chain.event(&stranger, post("signed by stranger")).unwrap();
+    chain.block().unwrap();
+
+    chain.event(&old_key, RotationEvent::new(&old_key.verifying_key(), &new_key).unwrap()).unwrap();
+    chain.block().unwrap();
+
+    let new_post = chain.event(&new_key, post("signed by new key")).unwrap();
+
+    chain.block().unwrap();
+
+    let storage = chain.storage();
+
+    let mut index = Index::default();
+
+    index.update(&storage).unwrap();
+
+    // Any key of the identity selects all of its posts.
+    for key in [&old_key, &new_key, &device] {
+        let query = FeedQuery::parse(format!("author:{}", key.verifying_key().to_base64())).unwrap();
+
+        let items = Plan::new(&query)
+            .execute(&index, &storage)
+            .unwrap()
+            .iter()
+            .map(|item| *item.message_hash())
+            .collect::<Vec<_>>();
+
+        assert_eq!(items, [new_post, old_post, device_post]);
+    }
+
+    let query = FeedQuery::parse(format!("-author:{}", new_key.verifying_key().to_base64())).unwrap();
+
+    let items = Plan::new(&query)
+        .execute(&index, &storage)
+        .unwrap();
+
+    assert_eq!(items.len(), 1);
+
+    assert!(matches!(
+        &items[0],
+        FeedItem::Post(post) if post.author == stranger.verifying_key()
+    ));
+}
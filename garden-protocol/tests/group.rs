@@ -0,0 +1,335 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use rand_chacha::ChaCha20Rng;
+use rand_chacha::rand_core::SeedableRng;
+
+use time::Duration;
+
+use flowerpot::crypto::hash::Hash;
+
+use garden_protocol::*;
+use garden_protocol::index::Index;
+use garden_protocol::index::group::{GroupIndex, GroupKeys};
+use garden_protocol::testing::*;
+
+fn post(content: &str) -> PostEvent {
+    PostEvent::new(Content::new(content).unwrap(), []).unwrap()
+}
+
+fn content(message: &index::group::GroupMessageInfo) -> &str {
+    match &message.content {
+        GroupContent::Post(post) => post.content().as_str(),
+        GroupContent::Comment(comment) => comment.content().as_str()
+    }
+}
+
+#[test]
+fn wrap_group_key() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+
+    let member = signing_key(1);
+    let key = GroupKey::random(&mut rng);
+
+    let event = GroupMemberEvent::new(
+        Hash::ZERO,
+        3,
+        member.verifying_key(),
+        &key,
+        &mut rng
+    ).unwrap();
+
+    let Events::GroupMember(decoded) = Events::from_bytes(Events::from(event.clone()).to_bytes()).unwrap() else {
+        panic!("invalid event type");
+    };
+
+    assert_eq!(decoded, event);
+    assert_eq!(decoded.epoch(), 3);
+    assert_eq!(decoded.member(), &member.verifying_key());
+
+    assert_eq!(decoded.unwrap_key(&member), Some(key));
+    assert_eq!(decoded.unwrap_key(&signing_key(2)), None);
+}
+
+#[test]
+fn seal_message() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+
+    let key = GroupKey::random(&mut rng);
+    let event = GroupMessageEvent::seal(Hash::ZERO, 0, &key, post("secret"), &mut rng);
+
+    let Events::GroupMessage(decoded) = Events::from_bytes(Events::from(event.clone()).to_bytes()).unwrap() else {
+        panic!("invalid event type");
+    };
+
+    assert_eq!(decoded, event);
+    assert_eq!(decoded.open(&key).unwrap(), GroupContent::Post(post("secret")));
+
+    assert!(matches!(
+        decoded.open(&GroupKey::random(&mut rng)),
+        Err(GroupOpenError::Decryption)
+    ));
+}
+
+#[test]
+fn private_group() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+
+    let admin = signing_key(1);
+    let member = signing_key(2);
+    let outsider = signing_key(3);
+
+    let mut chain = ChainBuilder::new(signing_key(0));
+
+    let group = chain.event(&admin, GroupEvent::new(&mut rng)).unwrap();
+
+    chain.block().unwrap();
+
+    // Add both admin and member to the first epoch.
+    let key = GroupKey::random(&mut rng);
+
+    for key_owner in [&admin, &member] {
+        let event = GroupMemberEvent::new(group, 0, key_owner.verifying_key(), &key, &mut rng).unwrap();
+
+        chain.event(&admin, event).unwrap();
+    }
+
+    // Only the admin can add members.
+    let event = GroupMemberEvent::new(group, 0, outsider.verifying_key(), &key, &mut rng).unwrap();
+
+    chain.event(&member, event).unwrap();
+    chain.block().unwrap();
+
+    chain.event(&member, GroupMessageEvent::seal(group, 0, &key, post("hello"), &mut rng)).unwrap();
+    chain.event(&outsider, GroupMessageEvent::seal(group, 0, &key, post("spam"), &mut rng)).unwrap();
+    chain.block().unwrap();
+
+    let mut index = Index::default();
+
+    index.update(&chain.storage()).unwrap();
+
+    assert_eq!(index.groups().count(), 1);
+    assert_eq!(index.group(&group).unwrap().admin(), &admin.verifying_key());
+    assert_eq!(index.group(&group).unwrap().members().count(), 2);
+    assert_eq!(index.groups_of(&member.verifying_key()).count(), 1);
+    assert_eq!(index.groups_of(&outsider.verifying_key()).count(), 0);
+    assert_eq!(index.group_messages(&group).count(), 1);
+
+    let member_keys = GroupKeys::unwrap(&index, &chain.storage(), &member).unwrap();
+    let outsider_keys = GroupKeys::unwrap(&index, &chain.storage(), &outsider).unwrap();
+
+    assert_eq!(member_keys.len(), 1);
+    assert!(outsider_keys.is_empty());
+
+    let messages = index.read_group_messages(&group, &chain.storage(), &member_keys).unwrap();
+
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].author, member.verifying_key());
+    assert_eq!(content(&messages[0]), "hello");
+
+    assert!(index.read_group_messages(&group, &chain.storage(), &outsider_keys).unwrap().is_empty());
+
+    // Remove the member by rotating the group key.
+    let rotated_key = GroupKey::random(&mut rng);
+    let event = GroupMemberEvent::new(group, 1, admin.verifying_key(), &rotated_key, &mut rng).unwrap();
+
+    chain.event(&admin, event).unwrap();
+    chain.block().unwrap();
+
+    // Removed member is not a member of the new epoch.
+    let guessed_key = GroupKey::random(&mut rng);
+
+    chain.event(&member, GroupMessageEvent::seal(group, 1, &guessed_key, post("still here"), &mut rng)).unwrap();
+    chain.event(&admin, GroupMessageEvent::seal(group, 1, &rotated_key, post("bye"), &mut rng)).unwrap();
+    chain.block().unwrap();
+
+    index.update(&chain.storage()).unwrap();
+
+    assert_eq!(index.group(&group).unwrap().epoch(), 1);
+    assert_eq!(index.group(&group).unwrap().members().count(), 1);
+    assert_eq!(index.groups_of(&member.verifying_key()).count(), 0);
+    assert_eq!(index.group_messages(&group).count(), 2);
+
+    let admin_keys = GroupKeys::unwrap(&index, &chain.storage(), &admin).unwrap();
+    let member_keys = GroupKeys::unwrap(&index, &chain.storage(), &member).unwrap();
+
+    let messages = index.read_group_messages(&group, &chain.storage(), &admin_keys).unwrap();
+
+    assert_eq!(messages.iter().map(content).collect::<Vec<_>>(), ["hello", "bye"]);
+
+    // Removed member can read only messages of the old epoch.
+    let messages = index.read_group_messages(&group, &chain.storage(), &member_keys).unwrap();
+
+    assert_eq!(messages.iter().map(content).collect::<Vec<_>>(), ["hello"]);
+}
+
+#[test]
+fn group_key_rotation() {
+    let mut rng = ChaCha20Rng::seed_from_u64(1);
+
+    let admin = signing_key(1);
+    let member = signing_key(2);
+    let removed = signing_key(3);
+
+    let mut chain = ChainBuilder::new(signing_key(0));
+
+    let group = chain.event(&admin, GroupEvent::new(&mut rng)).unwrap();
+
+    chain.block().unwrap();
+
+    let keys = [
+        GroupKey::random(&mut rng),
+        GroupKey::random(&mut rng),
+        GroupKey::random(&mut rng)
+    ];
+
+    for key_owner in [&admin, &member, &removed] {
+        let event = GroupMemberEvent::new(group, 0, key_owner.verifying_key(), &keys[0], &mut rng).unwrap();
+
+        chain.event(&admin, event).unwrap();
+    }
+
+    chain.block().unwrap();
+
+    chain.event(&removed, GroupMessageEvent::seal(group, 0, &keys[0], post("first"), &mut rng)).unwrap();
+    chain.block().unwrap();
+
+    // Rotate the group key to remove a member.
+    for key_owner in [&admin, &member] {
+        let event = GroupMemberEvent::new(group, 1, key_owner.verifying_key(), &keys[1], &mut rng).unwrap();
+
+        chain.event(&admin, event).unwrap();
+    }
+
+    chain.block().unwrap();
+
+    // Messages sent before the rotation was stored are sealed after it.
+    chain.event(&removed, GroupMessageEvent::seal(group, 0, &keys[0], post("late"), &mut rng)).unwrap();
+    chain.event(&member, GroupMessageEvent::seal(group, 0, &keys[0], post("late reply"), &mut rng)).unwrap();
+    chain.event(&member, GroupMessageEvent::seal(group, 1, &keys[1], post("rotated"), &mut rng)).unwrap();
+
+    // Removed member is not a member of the new epoch.
+    chain.event(&removed, GroupMessageEvent::seal(group, 1, &keys[1], post("spam"), &mut rng)).unwrap();
+    chain.block().unwrap();
+
+    // Rotate the group key again. Messages of the epoch before the previous
+    // one are not accepted anymore.
+    let event = GroupMemberEvent::new(group, 2, admin.verifying_key(), &keys[2], &mut rng).unwrap();
+
+    chain.event(&admin, event).unwrap();
+    chain.block().unwrap();
+
+    chain.event(&member, GroupMessageEvent::seal(group, 0, &keys[0], post("too old"), &mut rng)).unwrap();
+    chain.event(&member, GroupMessageEvent::seal(group, 1, &keys[1], post("still rotating"), &mut rng)).unwrap();
+    chain.event(&admin, GroupMessageEvent::seal(group, 2, &keys[2], post("alone"), &mut rng)).unwrap();
+    chain.block().unwrap();
+
+    let mut index = Index::default();
+
+    index.update(&chain.storage()).unwrap();
+
+    let admin_keys = GroupKeys::unwrap(&index, &chain.storage(), &admin).unwrap();
+
+    assert_eq!(admin_keys.len(), 3);
+
+    let messages = index.read_group_messages(&group, &chain.storage(), &admin_keys).unwrap();
+
+    assert_eq!(
+        messages.iter().map(content).collect::<Vec<_>>(),
+        ["first", "late", "late reply", "rotated", "still rotating", "alone"]
+    );
+
+    // Removed member still has only the first epoch key.
+    let removed_keys = GroupKeys::unwrap(&index, &chain.storage(), &removed).unwrap();
+
+    assert_eq!(removed_keys.len(), 1);
+
+    let group = index.group(&group).unwrap();
+
+    assert_eq!(group.epoch(), 2);
+    assert!(group.is_member_at(&removed.verifying_key(), 0));
+    assert!(!group.is_member_at(&removed.verifying_key(), 1));
+
+    // Messages of the previous epoch are accepted only within the grace
+    // period after the rotation.
+    let rotated_at = *group.epoch_timestamp(2).unwrap();
+
+    assert!(group.accepts_epoch(2, &(rotated_at + Duration::WEEK)));
+    assert!(group.accepts_epoch(1, &(rotated_at + GroupIndex::EPOCH_GRACE_PERIOD)));
+    assert!(!group.accepts_epoch(1, &(rotated_at + GroupIndex::EPOCH_GRACE_PERIOD + Duration::SECOND)));
+    assert!(!group.accepts_epoch(0, &rotated_at));
+    assert!(!group.accepts_epoch(3, &rotated_at));
+}
+
+#[test]
+fn tampered_group_message() {
+    let mut rng = ChaCha20Rng::seed_from_u64(2);
+
+    let admin = signing_key(1);
+    let member = signing_key(2);
+
+    let mut chain = ChainBuilder::new(signing_key(0));
+
+    let group = chain.event(&admin, GroupEvent::new(&mut rng)).unwrap();
+
+    chain.block().unwrap();
+
+    let key = GroupKey::random(&mut rng);
+
+    for key_owner in [&admin, &member] {
+        let event = GroupMemberEvent::new(group, 0, key_owner.verifying_key(), &key, &mut rng).unwrap();
+
+        chain.event(&admin, event).unwrap();
+    }
+
+    chain.block().unwrap();
+
+    chain.event(&admin, GroupMessageEvent::seal(group, 0, &key, post("before"), &mut rng)).unwrap();
+
+    // Message of the current epoch encrypted with a wrong key.
+    let wrong_key = GroupKey::random(&mut rng);
+
+    chain.event(&member, GroupMessageEvent::seal(group, 0, &wrong_key, post("wrong key"), &mut rng)).unwrap();
+
+    // Message with corrupted ciphertext.
+    let mut corrupted = Events::from(GroupMessageEvent::seal(group, 0, &key, post("corrupted"), &mut rng))
+        .to_bytes()
+        .to_vec();
+
+    let last = corrupted.len() - 1;
+
+    corrupted[last] ^= 0xFF;
+
+    chain.data(&member, corrupted).unwrap();
+
+    chain.event(&member, GroupMessageEvent::seal(group, 0, &key, post("after"), &mut rng)).unwrap();
+    chain.block().unwrap();
+
+    let mut index = Index::default();
+
+    index.update(&chain.storage()).unwrap();
+
+    assert_eq!(index.group_messages(&group).count(), 4);
+
+    let admin_keys = GroupKeys::unwrap(&index, &chain.storage(), &admin).unwrap();
+
+    // Broken messages don't hide valid ones.
+    let messages = index.read_group_messages(&group, &chain.storage(), &admin_keys).unwrap();
+
+    assert_eq!(messages.iter().map(content).collect::<Vec<_>>(), ["before", "after"]);
+}
@@ -0,0 +1,207 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+use flowerpot::node::{Node, NodeOptions};
+
+use rand_chacha::ChaCha20Rng;
+use rand_chacha::rand_core::SeedableRng;
+
+use garden_protocol::*;
+use garden_protocol::handler::Handler;
+use garden_protocol::index::group::GroupKeys;
+use garden_protocol::index::notification::Notification;
+use garden_protocol::testing::*;
+
+#[test]
+fn notifications() {
+    let root_signer = signing_key(0);
+    let storage = MemoryStorage::new();
+
+    let address = Handler::create_chain(&root_signer, 0, &storage).unwrap();
+
+    let node = Node::default()
+        .add_storage(address.clone(), storage)
+        .start(NodeOptions::default())
+        .unwrap();
+
+    let handler = Handler::new(address, node);
+
+    let subscriber = handler.subscribe();
+    let dropped = handler.subscribe();
+
+    drop(dropped);
+
+    assert_eq!(handler.subscribers(), 2);
+
+    // The first update resets the empty index to the chain's root block.
+    handler.update().unwrap();
+
+    assert_eq!(handler.subscribers(), 1);
+    assert!(matches!(subscriber.try_recv(), Ok(Notification::Reset)));
+    assert!(subscriber.try_recv().is_err());
+
+    let post = handler.send_post(&signing_key(1), PostEvent::new(
+        Content::new("hello, subscribers!").unwrap(),
+        []
+    ).unwrap()).wait().unwrap();
+
+    handler.seal_block(&root_signer, []).unwrap().unwrap();
+    handler.update().unwrap();
+
+    let notifications = subscriber.try_iter().collect::<Vec<_>>();
+
+    assert_eq!(notifications.len(), 1);
+
+    assert!(matches!(
+        &notifications[0],
+        Notification::Post(indexed) if indexed.message_hash() == &post
+    ));
+
+    drop(subscriber);
+
+    handler.send_post(&signing_key(1), PostEvent::new(
+        Content::new("anybody here?").unwrap(),
+        []
+    ).unwrap()).wait().unwrap();
+
+    handler.seal_block(&root_signer, []).unwrap().unwrap();
+    handler.update().unwrap();
+
+    assert_eq!(handler.subscribers(), 0);
+}
+
+#[test]
+fn send_group_events() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+
+    let root_signer = signing_key(0);
+    let admin = signing_key(1);
+    let member = signing_key(2);
+
+    let storage = MemoryStorage::new();
+
+    let address = Handler::create_chain(&root_signer, 0, &storage).unwrap();
+
+    let node = Node::default()
+        .add_storage(address.clone(), storage)
+        .start(NodeOptions::default())
+        .unwrap();
+
+    // Group events are indexed only with stamps.
+    let policy = StampPolicy {
+        group: 4,
+        ..StampPolicy::default()
+    };
+
+    let handler = Handler::with_stamp_policy(address, node, policy);
+
+    let group = handler.send_group(&admin, GroupEvent::new(&mut rng))
+        .wait()
+        .unwrap();
+
+    handler.seal_block(&root_signer, []).unwrap().unwrap();
+
+    let key = GroupKey::random(&mut rng);
+
+    for key_owner in [&admin, &member] {
+        let event = GroupMemberEvent::new(group, 0, key_owner.verifying_key(), &key, &mut rng).unwrap();
+
+        handler.add_group_member(&admin, event).wait().unwrap();
+    }
+
+    handler.seal_block(&root_signer, []).unwrap().unwrap();
+
+    let post = PostEvent::new(Content::new("hello, group!").unwrap(), []).unwrap();
+
+    handler.send_group_message(&member, GroupMessageEvent::seal(group, 0, &key, post.clone(), &mut rng))
+        .wait()
+        .unwrap();
+
+    handler.seal_block(&root_signer, []).unwrap().unwrap();
+    handler.update().unwrap();
+
+    let index = handler.index();
+
+    assert_eq!(index.group(&group).unwrap().admin(), &admin.verifying_key());
+    assert_eq!(index.group(&group).unwrap().members().count(), 2);
+
+    let messages = handler.node()
+        .map_storage(handler.address(), |storage| {
+            let keys = GroupKeys::unwrap(&index, storage, &member).unwrap();
+
+            index.read_group_messages(&group, storage, &keys).unwrap()
+        })
+        .unwrap();
+
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].author, member.verifying_key());
+    assert_eq!(messages[0].content, GroupContent::Post(post));
+}
+
+#[test]
+fn import_archive_stamps() {
+    let root_signer = signing_key(0);
+    let author = signing_key(1);
+
+    // Reactions are indexed only with stamps on both chains.
+    let policy = StampPolicy {
+        reaction: 4,
+        ..StampPolicy::default()
+    };
+
+    let handlers = [0, 1].map(|chain_id| {
+        let storage = MemoryStorage::new();
+
+        let address = Handler::create_chain(&root_signer, chain_id, &storage).unwrap();
+
+        let node = Node::default()
+            .add_storage(address.clone(), storage)
+            .start(NodeOptions::default())
+            .unwrap();
+
+        Handler::with_stamp_policy(address, node, policy)
+    });
+
+    let [source, target] = &handlers;
+
+    let post = source.send_post(&author, PostEvent::new(Content::new("hello, world!").unwrap(), []).unwrap())
+        .wait()
+        .unwrap();
+
+    let reaction = source.send_reaction(&author, ReactionEvent::new(post, Reaction::ThumbUp))
+        .wait()
+        .unwrap();
+
+    source.seal_block(&root_signer, []).unwrap().unwrap();
+    source.update().unwrap();
+
+    assert_eq!(source.index().reactions().count(), 1);
+
+    let archive = source.export_archive(&author.verifying_key()).unwrap();
+
+    // Reaction stamp is minted for the source chain so it's not republished.
+    let import = target.import_archive(&archive).unwrap();
+
+    assert_eq!(import.sent, [post]);
+    assert_eq!(import.unstamped, [reaction]);
+
+    target.seal_block(&root_signer, []).unwrap().unwrap();
+    target.update().unwrap();
+
+    assert_eq!(target.index().posts().count(), 1);
+    assert_eq!(target.index().reactions().count(), 0);
+}
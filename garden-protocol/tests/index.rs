@@ -0,0 +1,804 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use time::Duration;
+
+use flowerpot::address::Address;
+use flowerpot::crypto::hash::Hash;
+use flowerpot::crypto::sign::Signature;
+use flowerpot::storage::Storage;
+
+use garden_protocol::*;
+use garden_protocol::index::{Index, PARALLEL_BATCH_SIZE};
+use garden_protocol::index::notification::Notification;
+use garden_protocol::testing::*;
+
+fn post(content: &str, tags: &[&str]) -> PostEvent {
+    PostEvent::new(
+        Content::new(content).unwrap(),
+        tags.iter().map(|tag| Tag::new(tag).unwrap())
+    ).unwrap()
+}
+
+fn comment(ref_message_hash: Hash, content: &str) -> CommentEvent {
+    CommentEvent::new(ref_message_hash, Content::new(content).unwrap())
+}
+
+#[test]
+fn empty_storage() {
+    let mut index = Index::default();
+
+    index.update(&MemoryStorage::new()).unwrap();
+
+    assert_eq!(index.posts().len(), 0);
+    assert_eq!(index.comments().len(), 0);
+    assert_eq!(index.statistics().total(), 0);
+}
+
+#[test]
+fn index_events() {
+    let author = signing_key(1);
+    let reader = signing_key(2);
+
+    let mut chain = ChainBuilder::new(signing_key(0));
+
+    let post_hash = chain.event(&author, post("hello, world!", &["test"])).unwrap();
+
+    chain.block().unwrap();
+
+    let comment_hash = chain.event(&reader, comment(post_hash, "hi!")).unwrap();
+
+    chain.event(&reader, ReactionEvent::new(post_hash, Reaction::ThumbUp)).unwrap();
+    chain.event(&author, ReactionEvent::new(comment_hash, Reaction::ThumbUp)).unwrap();
+
+    chain.block().unwrap();
+
+    let storage = chain.storage();
+
+    let mut index = Index::default();
+
+    index.update(&storage).unwrap();
+
+    assert_eq!(index.posts().len(), 1);
+    assert_eq!(index.comments().len(), 1);
+    assert_eq!(index.reactions().count(), 2);
+
+    assert_eq!(index.comments_of(&post_hash).count(), 1);
+    assert_eq!(index.comments_of(&comment_hash).count(), 0);
+    assert_eq!(index.reactions_of(&post_hash).count(), 1);
+    assert_eq!(index.reactions_of(&comment_hash).count(), 1);
+
+    let post_info = index.posts()
+        .next()
+        .unwrap()
+        .read(&index, &storage)
+        .unwrap();
+
+    assert_eq!(post_info.message_hash, post_hash);
+    assert_eq!(post_info.author, author.verifying_key());
+    assert_eq!(post_info.identity, author.verifying_key());
+    assert_eq!(post_info.content.as_str(), "hello, world!");
+    assert_eq!(post_info.tags.as_ref(), &[Tag::new("test").unwrap()]);
+
+    let comment_info = index.comments()
+        .next()
+        .unwrap()
+        .read(&index, &storage)
+        .unwrap();
+
+    assert_eq!(comment_info.message_hash, comment_hash);
+    assert_eq!(comment_info.ref_message_hash, post_hash);
+    assert_eq!(comment_info.ref_block_hash, post_info.block_hash);
+    assert_eq!(comment_info.author, reader.verifying_key());
+    assert_eq!(comment_info.content.as_str(), "hi!");
+
+    // Block timestamps are stored in the index.
+    let post_block = storage.read_block(&post_info.block_hash).unwrap().unwrap();
+    let comment_block = storage.read_block(&comment_info.block_hash).unwrap().unwrap();
+
+    assert_eq!(index.posts().next().unwrap().timestamp(), post_block.timestamp());
+    assert_eq!(index.comments().next().unwrap().timestamp(), comment_block.timestamp());
+    assert_eq!(&post_info.timestamp, post_block.timestamp());
+    assert_eq!(&comment_info.timestamp, comment_block.timestamp());
+
+    let statistics = index.statistics();
+
+    assert_eq!(statistics.total(), 4);
+    assert_eq!(statistics.unique_authors(), 2);
+    assert_eq!(statistics.rejected(), 0);
+}
+
+#[test]
+fn latest_reaction() {
+    let author = signing_key(1);
+    let reader = signing_key(2);
+
+    let mut chain = ChainBuilder::new(signing_key(0));
+
+    let post_hash = chain.event(&author, post("hello", &[])).unwrap();
+
+    chain.event(&reader, ReactionEvent::new(post_hash, Reaction::ThumbUp)).unwrap();
+    chain.block().unwrap();
+
+    let latest = chain.event(&reader, ReactionEvent::new(post_hash, Reaction::ThumbDown)).unwrap();
+
+    chain.block().unwrap();
+
+    let mut index = Index::default();
+
+    index.update(&chain.storage()).unwrap();
+
+    let reactions = index.reactions_of(&post_hash).collect::<Vec<_>>();
+
+    assert_eq!(reactions.len(), 1);
+    assert_eq!(reactions[0].message_hash(), &latest);
+    assert_eq!(reactions[0].reaction(), &Reaction::ThumbDown);
+    assert_eq!(reactions[0].identity(), &reader.verifying_key());
+
+    // Reactions within the same block: the one with lower hash is counted.
+    let first = chain.event(&author, ReactionEvent::new(post_hash, Reaction::ThumbUp)).unwrap();
+    let second = chain.event(&author, ReactionEvent::new(post_hash, Reaction::ThumbDown)).unwrap();
+
+    chain.block().unwrap();
+
+    index.update(&chain.storage()).unwrap();
+
+    let counted = if first.as_bytes() < second.as_bytes() {
+        first
+    } else {
+        second
+    };
+
+    assert_eq!(index.reactions_of(&post_hash).count(), 2);
+
+    assert!(index.reactions_of(&post_hash).any(|reaction| {
+        reaction.identity() == &author.verifying_key()
+            && reaction.message_hash() == &counted
+    }));
+}
+
+#[test]
+fn deserialized_reactions() {
+    let author = signing_key(1);
+    let readers = [signing_key(2), signing_key(3), signing_key(4)];
+
+    let mut chain = ChainBuilder::new(signing_key(0));
+
+    let posts = [
+        chain.event(&author, post("first", &[])).unwrap(),
+        chain.event(&author, post("second", &[])).unwrap()
+    ];
+
+    for reader in &readers {
+        for post in &posts {
+            chain.event(reader, ReactionEvent::new(*post, Reaction::ThumbUp)).unwrap();
+        }
+    }
+
+    chain.block().unwrap();
+
+    let mut index = Index::default();
+
+    index.update(&chain.storage()).unwrap();
+
+    assert_eq!(index.reactions().count(), 6);
+
+    // Reactions of the deserialized index are still replaced by the latest
+    // ones instead of being duplicated.
+    let mut index = serde_json::from_value::<Index>(serde_json::to_value(&index).unwrap()).unwrap();
+
+    let latest = chain.event(&readers[0], ReactionEvent::new(posts[1], Reaction::ThumbDown)).unwrap();
+
+    chain.block().unwrap();
+
+    index.update(&chain.storage()).unwrap();
+
+    assert_eq!(index.reactions().count(), 6);
+    assert_eq!(index.reactions_of(&posts[0]).count(), 3);
+
+    assert!(index.reactions_of(&posts[1]).any(|reaction| {
+        reaction.identity() == &readers[0].verifying_key()
+            && reaction.message_hash() == &latest
+            && reaction.reaction() == &Reaction::ThumbDown
+    }));
+
+    let mut reference = Index::default();
+
+    reference.update(&chain.storage()).unwrap();
+
+    assert_eq!(index, reference);
+}
+
+#[test]
+fn incremental_update() {
+    let author = signing_key(1);
+
+    let mut chain = ChainBuilder::new(signing_key(0));
+
+    chain.event(&author, post("first", &[])).unwrap();
+    chain.block().unwrap();
+
+    let storage = chain.storage();
+
+    let mut index = Index::default();
+    let mut notifications = Vec::new();
+
+    index.update_with(&storage, |notification| notifications.push(notification)).unwrap();
+
+    assert_eq!(index.posts().len(), 1);
+    assert_eq!(index.lag(&storage).unwrap(), 0);
+    assert!(matches!(notifications.as_slice(), [Notification::Post(_)]));
+
+    let second = chain.event(&author, post("second", &[])).unwrap();
+
+    chain.block().unwrap();
+    chain.block().unwrap();
+
+    assert_eq!(chain.sync(&storage).unwrap(), 2);
+    assert_eq!(index.lag(&storage).unwrap(), 2);
+
+    notifications.clear();
+
+    index.update_with(&storage, |notification| notifications.push(notification)).unwrap();
+
+    assert_eq!(index.posts().len(), 2);
+    assert_eq!(index.lag(&storage).unwrap(), 0);
+
+    let [Notification::Post(post)] = notifications.as_slice() else {
+        panic!("unexpected notifications: {notifications:?}");
+    };
+
+    assert_eq!(post.message_hash(), &second);
+}
+
+#[test]
+fn parallel_update() {
+    let authors = (1..=4).map(signing_key).collect::<Vec<_>>();
+
+    let mut chain = ChainBuilder::new(signing_key(0));
+    let mut posts = Vec::new();
+
+    // Make more blocks than a single parallel batch.
+    for i in 0..PARALLEL_BATCH_SIZE * 2 + 10 {
+        let author = &authors[i % authors.len()];
+
+        posts.push(chain.event(author, post(&format!("post {i}"), &[])).unwrap());
+
+        if i % 3 == 0 {
+            chain.data(author, b"not a garden event".as_slice()).unwrap();
+        }
+
+        if let Some(post_hash) = posts.get(i / 2) {
+            chain.event(author, comment(*post_hash, "comment")).unwrap();
+            chain.event(author, ReactionEvent::new(*post_hash, Reaction::ThumbDown)).unwrap();
+        }
+
+        chain.block().unwrap();
+    }
+
+    let storage = chain.storage();
+
+    let mut serial = Index::default();
+    let mut serial_notifications = Vec::new();
+
+    serial.update_with(&storage, |notification| serial_notifications.push(notification)).unwrap();
+
+    let mut parallel = Index::default();
+    let mut parallel_notifications = Vec::new();
+
+    parallel.update_parallel_with(&storage, |notification| parallel_notifications.push(notification)).unwrap();
+
+    assert_eq!(parallel, serial);
+    assert_eq!(parallel_notifications, serial_notifications);
+
+    // Incremental parallel update.
+    chain.event(&authors[0], post("last", &[])).unwrap();
+    chain.block().unwrap();
+
+    assert_eq!(chain.sync(&storage).unwrap(), 1);
+
+    serial.update(&storage).unwrap();
+    parallel.update_parallel(&storage).unwrap();
+
+    assert_eq!(parallel, serial);
+    assert_eq!(parallel.lag(&storage).unwrap(), 0);
+}
+
+#[test]
+fn reset_on_fork() {
+    let author = signing_key(1);
+
+    let mut chain = ChainBuilder::new(signing_key(0));
+
+    chain.event(&author, post("first", &[])).unwrap();
+    chain.block().unwrap();
+
+    let mut fork = chain.clone();
+
+    chain.event(&author, post("second", &[])).unwrap();
+    chain.block().unwrap();
+
+    let storage = chain.storage();
+
+    let mut index = Index::default();
+
+    index.update(&storage).unwrap();
+
+    assert_eq!(index.posts().len(), 2);
+
+    // Replace the last indexed block.
+    fork.event(&author, post("third", &[])).unwrap();
+    fork.event(&author, post("fourth", &[])).unwrap();
+    fork.block().unwrap();
+
+    assert_eq!(fork.sync(&storage).unwrap(), 1);
+
+    let mut notifications = Vec::new();
+
+    index.update_with(&storage, |notification| notifications.push(notification)).unwrap();
+
+    assert_eq!(index.posts().len(), 3);
+    assert!(matches!(notifications.first(), Some(Notification::Reset)));
+
+    // Different root block.
+    let mut chain = ChainBuilder::new(signing_key(3));
+
+    chain.block().unwrap();
+
+    index.update(&chain.storage()).unwrap();
+
+    assert_eq!(index.posts().len(), 0);
+}
+
+#[test]
+fn reject_invalid_events() {
+    let author = signing_key(1);
+
+    let mut chain = ChainBuilder::new(signing_key(0));
+
+    chain.data(&author, b"not a garden event".as_slice()).unwrap();
+    chain.data(&author, [0xFF, 0xFF].as_slice()).unwrap();
+    chain.event(&author, post("valid", &[])).unwrap();
+    chain.block().unwrap();
+
+    let mut index = Index::default();
+
+    index.update(&chain.storage()).unwrap();
+
+    assert_eq!(index.posts().len(), 1);
+    assert_eq!(index.statistics().total(), 1);
+    assert_eq!(index.statistics().rejected(), 2);
+}
+
+#[test]
+fn stamp_policy() {
+    let author = signing_key(1);
+    let address = Address::new(signing_key(0).verifying_key(), 0);
+
+    let event = Events::from(post("stamped", &[])).to_bytes();
+    let stamp = Stamp::mint(&address, &author.verifying_key(), &event, 4, |_| true).unwrap();
+
+    let mut chain = ChainBuilder::new(signing_key(0));
+
+    let stamped = chain.data(&author, stamp.stamp(&event)).unwrap();
+
+    chain.event(&author, post("not stamped", &[])).unwrap();
+    chain.block().unwrap();
+
+    let mut index = Index::with_stamp_policy(address, StampPolicy {
+        post: 4,
+        ..StampPolicy::default()
+    });
+
+    index.update(&chain.storage()).unwrap();
+
+    assert_eq!(index.posts().len(), 1);
+    assert_eq!(index.posts().next().unwrap().message_hash(), &stamped);
+    assert_eq!(index.statistics().rejected(), 1);
+}
+
+#[test]
+fn replay_stamp() {
+    let author = signing_key(1);
+    let replayer = signing_key(2);
+    let address = Address::new(signing_key(0).verifying_key(), 0);
+
+    let policy = StampPolicy {
+        post: 8,
+        ..StampPolicy::default()
+    };
+
+    let event = Events::from(post("stamped", &[])).to_bytes();
+    let stamp = Stamp::mint(&address, &author.verifying_key(), &event, 8, |_| true).unwrap();
+
+    let mut chain = ChainBuilder::new(signing_key(0));
+
+    let original = chain.data(&author, stamp.stamp(&event)).unwrap();
+
+    // Another key re-signs the same stamped event.
+    chain.data(&replayer, stamp.stamp(&event)).unwrap();
+    chain.block().unwrap();
+
+    let mut index = Index::with_stamp_policy(address, policy);
+
+    index.update(&chain.storage()).unwrap();
+
+    assert_eq!(index.posts().len(), 1);
+    assert_eq!(index.posts().next().unwrap().message_hash(), &original);
+    assert_eq!(index.statistics().rejected(), 1);
+
+    // The same stamp is not valid in another chain.
+    let mut index = Index::with_stamp_policy(
+        Address::new(signing_key(0).verifying_key(), 1),
+        policy
+    );
+
+    index.update(&chain.storage()).unwrap();
+
+    assert_eq!(index.posts().len(), 0);
+    assert_eq!(index.statistics().rejected(), 2);
+}
+
+#[test]
+fn identity_rotation() {
+    let old_key = signing_key(1);
+    let new_key = signing_key(2);
+
+    let mut chain = ChainBuilder::new(signing_key(0));
+
+    chain.event(&old_key, post("before rotation", &[])).unwrap();
+    chain.block().unwrap();
+
+    let rotation = RotationEvent::new(&old_key.verifying_key(), &new_key).unwrap();
+
+    chain.event(&old_key, rotation).unwrap();
+    chain.block().unwrap();
+
+    let storage = chain.storage();
+
+    let mut index = Index::default();
+
+    index.update(&storage).unwrap();
+
+    assert_eq!(index.latest_key(&old_key.verifying_key()), &new_key.verifying_key());
+
+    let post_info = index.posts()
+        .next()
+        .unwrap()
+        .read(&index, &storage)
+        .unwrap();
+
+    assert_eq!(post_info.author, old_key.verifying_key());
+    assert_eq!(post_info.identity, new_key.verifying_key());
+}
+
+#[test]
+fn rotated_reactions() {
+    let author = signing_key(1);
+    let old_key = signing_key(2);
+    let new_key = signing_key(3);
+
+    let mut chain = ChainBuilder::new(signing_key(0));
+
+    let post_hash = chain.event(&author, post("hello", &[])).unwrap();
+
+    chain.event(&old_key, ReactionEvent::new(post_hash, Reaction::ThumbUp)).unwrap();
+    chain.block().unwrap();
+
+    let rotation = RotationEvent::new(&old_key.verifying_key(), &new_key).unwrap();
+
+    chain.event(&old_key, rotation).unwrap();
+    chain.block().unwrap();
+
+    let mut index = Index::default();
+
+    index.update(&chain.storage()).unwrap();
+
+    // Reaction made before the rotation belongs to the new key.
+    let reactions = index.reactions_of(&post_hash).collect::<Vec<_>>();
+
+    assert_eq!(reactions.len(), 1);
+    assert_eq!(reactions[0].identity(), &new_key.verifying_key());
+
+    let latest = chain.event(&new_key, ReactionEvent::new(post_hash, Reaction::ThumbDown)).unwrap();
+
+    chain.block().unwrap();
+
+    index.update(&chain.storage()).unwrap();
+
+    // Reactions made before and after the rotation are counted once.
+    let reactions = index.reactions_of(&post_hash).collect::<Vec<_>>();
+
+    assert_eq!(reactions.len(), 1);
+    assert_eq!(reactions[0].message_hash(), &latest);
+    assert_eq!(reactions[0].reaction(), &Reaction::ThumbDown);
+    assert_eq!(reactions[0].identity(), &new_key.verifying_key());
+}
+
+#[test]
+fn malformed_rotation() {
+    let old_key = signing_key(1);
+    let new_key = signing_key(2);
+
+    let mut chain = ChainBuilder::new(signing_key(0));
+
+    let rotation = Events::from(RotationEvent::new(&old_key.verifying_key(), &new_key).unwrap()).to_bytes();
+
+    // Rotations with corrupted inner signature of the new key.
+    let signature = rotation.len() - Signature::SIZE;
+
+    let corruptions: [fn(&mut [u8]); 4] = [
+        |signature| signature.fill(0),
+        |signature| signature.fill(0xFF),
+        |signature| signature[Signature::SIZE / 2] ^= 0xFF,
+        |signature| signature[Signature::SIZE - 1] ^= 0xFF
+    ];
+
+    for corrupt in corruptions {
+        let mut rotation = rotation.to_vec();
+
+        corrupt(&mut rotation[signature..]);
+
+        chain.data(&old_key, rotation).unwrap();
+        chain.block().unwrap();
+    }
+
+    let post_hash = chain.event(&old_key, post("after rotation", &[])).unwrap();
+
+    chain.block().unwrap();
+
+    let storage = chain.storage();
+
+    let mut index = Index::default();
+
+    index.update(&storage).unwrap();
+
+    assert_eq!(index.rotations().count(), 0);
+    assert_eq!(index.lag(&storage).unwrap(), 0);
+    assert_eq!(index.posts().next().unwrap().message_hash(), &post_hash);
+    assert_eq!(index.latest_key(&old_key.verifying_key()), &old_key.verifying_key());
+}
+
+#[test]
+fn revoked_key() {
+    let old_key = signing_key(1);
+    let new_key = signing_key(2);
+
+    let mut chain = ChainBuilder::new(signing_key(0));
+
+    let rotation = RotationEvent::new(&old_key.verifying_key(), &new_key).unwrap();
+
+    chain.event(&old_key, rotation).unwrap();
+    chain.block().unwrap();
+
+    chain.event(&old_key, post("signed by revoked key", &[])).unwrap();
+    chain.event(&new_key, post("signed by new key", &[])).unwrap();
+    chain.block().unwrap();
+
+    let storage = chain.storage();
+
+    let mut index = Index::default();
+
+    index.update(&storage).unwrap();
+
+    let rotated_at = index.rotations().next().unwrap().timestamp;
+
+    assert!(index.is_revoked_key(&old_key.verifying_key(), &rotated_at));
+    assert!(!index.is_revoked_key(&old_key.verifying_key(), &(rotated_at - Duration::SECOND)));
+    assert!(!index.is_revoked_key(&new_key.verifying_key(), &rotated_at));
+
+    let posts = index.posts()
+        .map(|post| post.read(&index, &storage).unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(posts.len(), 2);
+
+    assert!(posts[0].signed_by_revoked_key);
+    assert_eq!(posts[0].identity, new_key.verifying_key());
+
+    assert!(!posts[1].signed_by_revoked_key);
+    assert_eq!(posts[1].identity, new_key.verifying_key());
+}
+
+#[test]
+fn device_delegation() {
+    let master = signing_key(1);
+    let device = signing_key(2);
+    let stranger = signing_key(3);
+
+    let mut chain = ChainBuilder::new(signing_key(0));
+
+    chain.event(&device, post("before authorization", &[])).unwrap();
+    chain.event(&master, DelegationEvent::authorize(device.verifying_key())).unwrap();
+    chain.block().unwrap();
+
+    chain.event(&device, post("before acceptance", &[])).unwrap();
+    chain.block().unwrap();
+
+    chain.event(&device, DelegationEvent::accept(master.verifying_key())).unwrap();
+    chain.block().unwrap();
+
+    chain.event(&device, post("delegated", &[])).unwrap();
+    chain.block().unwrap();
+
+    // Only the master key can revoke its device.
+    chain.event(&stranger, DelegationEvent::revoke(device.verifying_key())).unwrap();
+    chain.block().unwrap();
+
+    chain.event(&device, post("still delegated", &[])).unwrap();
+    chain.block().unwrap();
+
+    chain.event(&master, DelegationEvent::revoke(device.verifying_key())).unwrap();
+    chain.block().unwrap();
+
+    chain.event(&device, post("after revocation", &[])).unwrap();
+    chain.block().unwrap();
+
+    let storage = chain.storage();
+
+    let mut index = Index::default();
+
+    index.update(&storage).unwrap();
+
+    let delegations = index.delegations().collect::<Vec<_>>();
+
+    assert_eq!(delegations.len(), 1);
+    assert_eq!(delegations[0].master_key, master.verifying_key());
+    assert_eq!(delegations[0].device_key, device.verifying_key());
+    assert!(delegations[0].authorized_at.is_some());
+    assert!(delegations[0].accepted_at.is_some());
+    assert!(delegations[0].revoked_at.is_some());
+    assert!(!delegations[0].is_valid());
+
+    let identities = index.posts()
+        .map(|post| post.read(&index, &storage).unwrap())
+        .map(|post| {
+            assert_eq!(post.author, device.verifying_key());
+
+            post.identity
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(identities, [
+        device.verifying_key(),
+        device.verifying_key(),
+        master.verifying_key(),
+        master.verifying_key(),
+        device.verifying_key()
+    ]);
+
+    // Revoked devices still belong to the identity because their older events
+    // were made on its behalf.
+    let keys = index.identity_keys(&master.verifying_key());
+
+    assert!(keys.contains(&&device.verifying_key()));
+    assert!(!keys.contains(&&stranger.verifying_key()));
+}
+
+#[test]
+fn accept_before_authorize() {
+    let master = signing_key(1);
+    let device = signing_key(2);
+
+    let mut chain = ChainBuilder::new(signing_key(0));
+
+    chain.event(&device, DelegationEvent::accept(master.verifying_key())).unwrap();
+    chain.block().unwrap();
+
+    chain.event(&device, post("accepted only", &[])).unwrap();
+    chain.block().unwrap();
+
+    chain.event(&master, DelegationEvent::authorize(device.verifying_key())).unwrap();
+    chain.block().unwrap();
+
+    chain.event(&device, post("delegated", &[])).unwrap();
+    chain.block().unwrap();
+
+    let storage = chain.storage();
+
+    let mut index = Index::default();
+
+    index.update(&storage).unwrap();
+
+    let delegation = index.delegations().next().unwrap();
+
+    assert!(delegation.is_valid());
+    assert_eq!(delegation.valid_since(), delegation.authorized_at);
+
+    let identities = index.posts()
+        .map(|post| post.read(&index, &storage).unwrap().identity)
+        .collect::<Vec<_>>();
+
+    assert_eq!(identities, [
+        device.verifying_key(),
+        master.verifying_key()
+    ]);
+}
+
+#[test]
+fn nested_delegation() {
+    let master = signing_key(1);
+    let device = signing_key(2);
+    let nested = signing_key(3);
+
+    let mut chain = ChainBuilder::new(signing_key(0));
+
+    chain.event(&master, DelegationEvent::authorize(device.verifying_key())).unwrap();
+    chain.event(&device, DelegationEvent::accept(master.verifying_key())).unwrap();
+    chain.block().unwrap();
+
+    // Device keys cannot delegate other keys.
+    chain.event(&device, DelegationEvent::authorize(nested.verifying_key())).unwrap();
+    chain.event(&nested, DelegationEvent::accept(device.verifying_key())).unwrap();
+    chain.block().unwrap();
+
+    // Device key can be delegated only to one master key at a time.
+    chain.event(&nested, DelegationEvent::authorize(device.verifying_key())).unwrap();
+    chain.event(&device, DelegationEvent::accept(nested.verifying_key())).unwrap();
+    chain.block().unwrap();
+
+    chain.event(&nested, post("not delegated", &[])).unwrap();
+    chain.block().unwrap();
+
+    let storage = chain.storage();
+
+    let mut index = Index::default();
+
+    index.update(&storage).unwrap();
+
+    assert_eq!(index.delegations().count(), 1);
+    assert!(index.delegations().all(|delegation| delegation.device_key == device.verifying_key()));
+
+    let post_info = index.posts()
+        .next()
+        .unwrap()
+        .read(&index, &storage)
+        .unwrap();
+
+    assert_eq!(post_info.identity, nested.verifying_key());
+}
+
+#[test]
+fn memory_storage() {
+    let author = signing_key(1);
+
+    let mut chain = ChainBuilder::new(signing_key(0));
+
+    let message = chain.event(&author, post("hello", &[])).unwrap();
+    let root = chain.block().unwrap();
+    let tail = chain.block().unwrap();
+
+    let storage = chain.storage();
+
+    assert_eq!(storage.len(), 2);
+    assert_eq!(storage.root_block().unwrap(), Some(root));
+    assert_eq!(storage.tail_block().unwrap(), Some(tail));
+    assert_eq!(storage.next_block(&Hash::ZERO).unwrap(), Some(root));
+    assert_eq!(storage.next_block(&root).unwrap(), Some(tail));
+    assert_eq!(storage.prev_block(&tail).unwrap(), Some(root));
+    assert_eq!(storage.find_message(&message).unwrap(), Some(root));
+    assert_eq!(storage.read_message(&message).unwrap().map(|message| *message.hash()), Some(message));
+
+    // Already stored block.
+    assert!(!storage.write_block(&chain.blocks()[0]).unwrap());
+
+    storage.truncate(2);
+
+    assert!(storage.is_empty());
+    assert!(!storage.has_message(&message).unwrap());
+}
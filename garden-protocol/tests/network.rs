@@ -0,0 +1,77 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::time::Duration;
+
+use garden_protocol::*;
+use garden_protocol::testing::*;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[test]
+fn loopback_network_converges() {
+    let network = LoopbackNetwork::start(3).unwrap();
+
+    let alice = signing_key(1);
+    let bob = signing_key(2);
+    let carol = signing_key(3);
+
+    let post = PostEvent::new(
+        Content::new("hello from the first node!").unwrap(),
+        [Tag::new("test").unwrap()]
+    ).unwrap();
+
+    let post = network.handler(0)
+        .send_post(&alice, post)
+        .wait()
+        .unwrap();
+
+    assert!(network.seal_block().unwrap().is_some());
+    assert!(network.wait_converged(&[post], TIMEOUT).unwrap());
+
+    let comment = network.handler(1)
+        .send_comment(&bob, CommentEvent::new(post, Content::new("hi!").unwrap()))
+        .wait()
+        .unwrap();
+
+    let reaction = network.handler(2)
+        .send_reaction(&carol, ReactionEvent::new(post, Reaction::ThumbUp))
+        .wait()
+        .unwrap();
+
+    assert!(network.seal_block().unwrap().is_some());
+    assert!(network.wait_converged(&[post, comment, reaction], TIMEOUT).unwrap());
+
+    // Everything is already sealed.
+    assert!(network.seal_block().unwrap().is_none());
+
+    for node in network.nodes() {
+        let index = node.handler().index();
+
+        assert_eq!(index.posts().len(), 1);
+        assert_eq!(index.comments_of(&post).count(), 1);
+        assert_eq!(index.reactions_of(&post).count(), 1);
+
+        let post = node.handler()
+            .read_post(index.posts().next().unwrap())
+            .unwrap();
+
+        assert_eq!(post.author, alice.verifying_key());
+        assert_eq!(post.content.as_str(), "hello from the first node!");
+    }
+}
@@ -0,0 +1,152 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+use time::{Duration, UtcDateTime};
+
+use flowerpot::crypto::hash::Hash;
+use flowerpot::message::Message;
+
+use garden_protocol::*;
+use garden_protocol::handler::outbox::{Outbox, DeliveryStatus};
+use garden_protocol::testing::*;
+
+fn message(content: &str) -> Message {
+    let post = PostEvent::new(Content::new(content).unwrap(), []).unwrap();
+
+    Message::create(&signing_key(1), Events::from(post).to_bytes()).unwrap()
+}
+
+#[test]
+fn included() {
+    let mut chain = ChainBuilder::new(signing_key(0));
+    let mut outbox = Outbox::default();
+
+    let message = message("hello, world!");
+    let message_hash = *message.hash();
+
+    outbox.push(message.clone());
+    outbox.update(&chain.storage()).unwrap();
+
+    assert_eq!(outbox.status(&message_hash), Some(&DeliveryStatus::Pending));
+
+    chain.message(message);
+
+    let block_hash = chain.block().unwrap();
+
+    outbox.update(&chain.storage()).unwrap();
+
+    assert_eq!(outbox.status(&message_hash), Some(&DeliveryStatus::Included { block_hash }));
+
+    // Included event goes back to pending when its block is removed.
+    chain.rollback(1);
+
+    outbox.update(&chain.storage()).unwrap();
+
+    assert_eq!(outbox.status(&message_hash), Some(&DeliveryStatus::Pending));
+    assert_eq!(outbox.get(&message_hash).unwrap().attempts(), 1);
+}
+
+#[test]
+fn dropped() {
+    let mut chain = ChainBuilder::new(signing_key(0));
+    let mut outbox = Outbox::default();
+
+    let message = message("hello, world!");
+    let message_hash = *message.hash();
+
+    outbox.push(message);
+
+    let sent_at = *outbox.get(&message_hash).unwrap().sent_at();
+
+    outbox.update_at(&chain.storage(), sent_at + Outbox::DROP_TIMEOUT).unwrap();
+
+    assert_eq!(outbox.status(&message_hash), Some(&DeliveryStatus::Pending));
+
+    outbox.update_at(&chain.storage(), sent_at + Outbox::DROP_TIMEOUT + Duration::SECOND).unwrap();
+
+    assert_eq!(outbox.status(&message_hash), Some(&DeliveryStatus::Dropped));
+
+    // Dropped events are still included if they're found in the blockchain.
+    chain.message(outbox.get(&message_hash).unwrap().message().clone());
+
+    let block_hash = chain.block().unwrap();
+
+    outbox.update_at(&chain.storage(), sent_at + Outbox::DROP_TIMEOUT * 2).unwrap();
+
+    assert_eq!(outbox.status(&message_hash), Some(&DeliveryStatus::Included { block_hash }));
+}
+
+#[test]
+fn resubmit() {
+    let chain = ChainBuilder::new(signing_key(0));
+    let mut outbox = Outbox::default();
+
+    let message = message("hello, world!");
+    let message_hash = *message.hash();
+
+    outbox.push(message.clone());
+
+    let sent_at = *outbox.get(&message_hash).unwrap().sent_at();
+
+    outbox.update_at(&chain.storage(), sent_at + Outbox::DROP_TIMEOUT * 2).unwrap();
+
+    assert_eq!(outbox.status(&message_hash), Some(&DeliveryStatus::Dropped));
+
+    let resubmitted = outbox.resubmit(&message_hash).unwrap();
+
+    assert_eq!(resubmitted.hash(), &message_hash);
+
+    let entry = outbox.get(&message_hash).unwrap();
+
+    assert_eq!(entry.status(), &DeliveryStatus::Pending);
+    assert_eq!(entry.attempts(), 2);
+    assert!(entry.sent_at() >= &sent_at);
+
+    // Drop timeout is counted since the last submission.
+    let resubmitted_at = *entry.sent_at();
+
+    outbox.update_at(&chain.storage(), resubmitted_at + Outbox::DROP_TIMEOUT).unwrap();
+
+    assert_eq!(outbox.status(&message_hash), Some(&DeliveryStatus::Pending));
+
+    assert!(outbox.resubmit(&Hash::ZERO).is_none());
+
+    assert_eq!(outbox.remove(&message_hash).unwrap().message().hash(), &message_hash);
+    assert!(outbox.remove(&message_hash).is_none());
+    assert_eq!(outbox.entries().count(), 0);
+}
+
+#[test]
+fn entries_order() {
+    let mut outbox = Outbox::default();
+
+    let messages = [message("first"), message("second"), message("third")];
+
+    for message in &messages {
+        outbox.push(message.clone());
+    }
+
+    let sent = outbox.entries()
+        .map(|entry| *entry.message().hash())
+        .collect::<Vec<_>>();
+
+    assert_eq!(sent, messages.map(|message| *message.hash()));
+
+    let now = UtcDateTime::now();
+
+    assert!(outbox.entries().all(|entry| entry.sent_at() <= &now));
+}
@@ -0,0 +1,200 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+use flowerpot::crypto::base64;
+use flowerpot::crypto::hash::Hash;
+use flowerpot::node::{Node, NodeOptions};
+
+use garden_protocol::*;
+use garden_protocol::handler::Handler;
+use garden_protocol::handler::query::*;
+use garden_protocol::index::post::PostInfo;
+use garden_protocol::testing::*;
+
+/// Start handler of a new chain with provided amount of posts, each stored
+/// in its own block.
+fn handler(posts: usize) -> (Handler, Vec<Hash>) {
+    let root_signer = signing_key(0);
+    let author = signing_key(1);
+    let storage = MemoryStorage::new();
+
+    let address = Handler::create_chain(&root_signer, 0, &storage).unwrap();
+
+    let node = Node::default()
+        .add_storage(address.clone(), storage)
+        .start(NodeOptions::default())
+        .unwrap();
+
+    let handler = Handler::new(address, node);
+
+    let posts = (0..posts)
+        .map(|i| {
+            let post = PostEvent::new(Content::new(format!("post {i}")).unwrap(), []).unwrap();
+            let post = handler.send_post(&author, post).wait().unwrap();
+
+            handler.seal_block(&root_signer, []).unwrap().unwrap();
+
+            post
+        })
+        .collect::<Vec<_>>();
+
+    handler.update().unwrap();
+
+    (handler, posts)
+}
+
+fn hashes(page: &Page<PostInfo>) -> Vec<Hash> {
+    page.items.iter()
+        .map(|post| post.message_hash)
+        .collect()
+}
+
+#[test]
+fn cursor_encoding() {
+    let (handler, posts) = handler(1);
+
+    let page = handler.query_posts(&Query::default()).unwrap();
+    let cursor = page.next.unwrap();
+
+    assert_eq!(page.previous, Some(cursor));
+    assert_eq!(Cursor::from_base64(cursor.to_base64()), Some(cursor));
+
+    let mut bytes = base64::decode(cursor.to_base64()).unwrap();
+
+    assert_eq!(bytes.len(), Hash::SIZE + 1);
+    assert_eq!(&bytes[1..], posts[0].as_bytes());
+
+    // Unknown version.
+    bytes[0] = 1;
+
+    assert_eq!(Cursor::from_base64(base64::encode(&bytes)), None);
+
+    // Truncated hash.
+    bytes[0] = 0;
+
+    assert_eq!(Cursor::from_base64(base64::encode(&bytes[..Hash::SIZE])), None);
+
+    // Malformed strings.
+    assert_eq!(Cursor::from_base64(""), None);
+    assert_eq!(Cursor::from_base64("not a cursor!"), None);
+}
+
+#[test]
+fn invalid_cursor() {
+    let (handler, _) = handler(1);
+
+    let mut bytes = vec![0];
+
+    bytes.extend_from_slice(Hash::ZERO.as_bytes());
+
+    let query = Query {
+        after: Cursor::from_base64(base64::encode(bytes)),
+        ..Query::default()
+    };
+
+    assert!(query.after.is_some());
+
+    assert!(matches!(
+        handler.query_posts(&query),
+        Err(QueryError::InvalidCursor)
+    ));
+}
+
+#[test]
+fn paging() {
+    let (handler, posts) = handler(5);
+
+    for direction in [Direction::Forward, Direction::Backward] {
+        let mut expected = posts.clone();
+
+        if direction == Direction::Backward {
+            expected.reverse();
+        }
+
+        let mut query = Query {
+            direction,
+            limit: 2,
+            ..Query::default()
+        };
+
+        let mut pages = Vec::new();
+
+        loop {
+            let page = handler.query_posts(&query).unwrap();
+
+            query.after = page.next;
+
+            let has_more = page.has_more;
+
+            pages.push(page);
+
+            if !has_more {
+                break;
+            }
+        }
+
+        assert_eq!(pages.iter().map(hashes).collect::<Vec<_>>(), [
+            &expected[0..2],
+            &expected[2..4],
+            &expected[4..5]
+        ]);
+
+        // Next cursor of the last page stays the same so it can be used to
+        // poll for new posts.
+        let page = handler.query_posts(&query).unwrap();
+
+        assert!(page.items.is_empty());
+        assert!(!page.has_more);
+        assert_eq!(page.next, query.after);
+        assert_eq!(page.previous, None);
+
+        // Previous cursor with reversed direction returns the previous page.
+        let page = handler.query_posts(&Query {
+            direction: direction.reverse(),
+            limit: 2,
+            after: pages[2].previous,
+            ..Query::default()
+        }).unwrap();
+
+        assert_eq!(hashes(&page), [expected[3], expected[2]]);
+        assert!(page.has_more);
+
+        let page = handler.query_posts(&Query {
+            direction: direction.reverse(),
+            limit: 2,
+            after: pages[0].previous,
+            ..Query::default()
+        }).unwrap();
+
+        assert!(page.items.is_empty());
+        assert!(!page.has_more);
+    }
+}
+
+#[test]
+fn paging_by_timestamp() {
+    let (handler, posts) = handler(3);
+
+    let page = handler.query_posts(&Query {
+        sort: Sort::Timestamp,
+        direction: Direction::Backward,
+        ..Query::default()
+    }).unwrap();
+
+    assert_eq!(hashes(&page), [posts[2], posts[1], posts[0]]);
+    assert!(!page.has_more);
+}
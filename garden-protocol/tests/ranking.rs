@@ -0,0 +1,95 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use flowerpot::crypto::hash::Hash;
+
+use garden_protocol::feed::ranking::*;
+
+use time::{UtcDateTime, Duration};
+
+fn now() -> UtcDateTime {
+    UtcDateTime::from_unix_timestamp(1_800_000_000).unwrap()
+}
+
+fn item(id: u8, age_hours: i64, reactions: u64, comments: u64) -> (RankItem, u8) {
+    let item = RankItem {
+        message_hash: Hash::from([id; 32]),
+        timestamp: now() - Duration::hours(age_hours),
+        reactions,
+        comments
+    };
+
+    (item, id)
+}
+
+fn items() -> Vec<(RankItem, u8)> {
+    vec![
+        item(0, 1, 0, 0),
+        item(1, 2, 50, 0),
+        item(2, 30, 100, 10),
+        item(3, 500, 1000, 100),
+        item(4, 5, 5, 20),
+        item(5, 2, 50, 0)
+    ]
+}
+
+#[test]
+fn chronological() {
+    assert_eq!(rank(&Chronological, items(), now()), [0, 1, 5, 4, 2, 3]);
+}
+
+#[test]
+fn top() {
+    assert_eq!(rank(&Top::DAY, items(), now()), [1, 5, 4, 0]);
+    assert_eq!(rank(&Top::WEEK, items(), now()), [2, 1, 5, 4, 0]);
+}
+
+#[test]
+fn hot() {
+    let ranked = rank(&Hot::default(), items(), now());
+
+    assert_eq!(ranked, [1, 5, 4, 2, 0, 3]);
+
+    // Fresh item with the same points is hotter than an old one.
+    let ranking = Hot::default();
+
+    assert!(
+        ranking.score(&item(0, 1, 10, 0).0, now()) >
+        ranking.score(&item(0, 10, 10, 0).0, now())
+    );
+}
+
+#[test]
+fn deterministic() {
+    let strategies = [
+        Strategy::Chronological(Chronological),
+        Strategy::Top(Top::default()),
+        Strategy::Hot(Hot::default())
+    ];
+
+    for strategy in strategies {
+        let mut reversed = items();
+
+        reversed.reverse();
+
+        assert_eq!(
+            rank(&strategy, items(), now()),
+            rank(&strategy, reversed, now())
+        );
+    }
+}
@@ -0,0 +1,97 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(feature = "serde")]
+
+use flowerpot::crypto::hash::Hash;
+
+use serde_json::json;
+
+use garden_protocol::*;
+
+#[test]
+fn post_event() {
+    let post = PostEvent::new(
+        Content::new("Hello, World!").unwrap(),
+        [Tag::new("hello").unwrap(), Tag::new("world").unwrap()]
+    ).unwrap();
+
+    let value = serde_json::to_value(&post).unwrap();
+
+    assert_eq!(value, json!({
+        "content": "Hello, World!",
+        "tags": ["hello", "world"]
+    }));
+
+    assert_eq!(serde_json::from_value::<PostEvent>(value).unwrap(), post);
+}
+
+#[test]
+fn invalid_tags() {
+    let invalid_tag = json!({
+        "content": "Hello, World!",
+        "tags": ["-hello"]
+    });
+
+    assert!(serde_json::from_value::<PostEvent>(invalid_tag).is_err());
+
+    let too_many_tags = json!({
+        "content": "Hello, World!",
+        "tags": vec!["tag"; 256]
+    });
+
+    assert!(serde_json::from_value::<PostEvent>(too_many_tags).is_err());
+}
+
+#[test]
+fn invalid_content() {
+    let content = "a".repeat(u16::MAX as usize + 1);
+
+    assert!(serde_json::from_value::<Content>(json!(content)).is_err());
+}
+
+#[test]
+fn events() {
+    let event = Events::from(CommentEvent::new(
+        Hash::from([1; Hash::SIZE]),
+        Content::new("Hello, World!").unwrap()
+    ));
+
+    let value = serde_json::to_value(&event).unwrap();
+
+    assert_eq!(value["type"], "comment");
+    assert_eq!(value["ref_message_hash"], Hash::from([1; Hash::SIZE]).to_base64());
+
+    let Events::Comment(comment) = serde_json::from_value(value).unwrap() else {
+        panic!("invalid event type");
+    };
+
+    assert_eq!(comment.content().as_str(), "Hello, World!");
+}
+
+#[test]
+fn reaction_event() {
+    let event = ReactionEvent::new(Hash::from([1; Hash::SIZE]), Reaction::ThumbUp);
+
+    let value = serde_json::to_value(&event).unwrap();
+
+    assert_eq!(value["reaction"], "thumb_up");
+    assert_eq!(serde_json::from_value::<ReactionEvent>(value).unwrap(), event);
+
+    assert!(serde_json::from_value::<Reaction>(json!("unknown")).is_err());
+}
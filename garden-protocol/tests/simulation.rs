@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use garden_protocol::testing::*;
+
+#[test]
+fn simulate_network() {
+    for seed in 0..4 {
+        let report = Simulation::new(SimulationConfig {
+            seed,
+            ..SimulationConfig::default()
+        }).unwrap().run().unwrap();
+
+        assert!(report.blocks > 0);
+        assert!(report.deliveries > 0);
+    }
+}
+
+#[test]
+fn simulate_unstable_network() {
+    let report = Simulation::new(SimulationConfig {
+        seed: 42,
+        nodes: 12,
+        reorg_probability: 0.1,
+        partition_probability: 0.1,
+        malformed_probability: 0.3,
+        ..SimulationConfig::default()
+    }).unwrap().run().unwrap();
+
+    assert!(report.reorgs > 0);
+    assert!(report.partitions > 0);
+    assert!(report.malformed > 0);
+}
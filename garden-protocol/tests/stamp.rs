@@ -0,0 +1,136 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use flowerpot::address::Address;
+use flowerpot::message::Message;
+use flowerpot::crypto::sign::VerifyingKey;
+
+use garden_protocol::*;
+use garden_protocol::testing::*;
+
+fn address() -> Address {
+    Address::new(signing_key(0).verifying_key(), 0)
+}
+
+fn author() -> VerifyingKey {
+    signing_key(1).verifying_key()
+}
+
+fn post() -> Events {
+    Events::from(PostEvent::new(
+        Content::new("hello, world!").unwrap(),
+        [Tag::new("test").unwrap()]
+    ).unwrap())
+}
+
+#[test]
+fn mint_stamp() {
+    let bytes = post().to_bytes();
+
+    let stamp = Stamp::mint(&address(), &author(), &bytes, 8, |_| true).unwrap();
+
+    assert!(stamp.difficulty(&address(), &author(), &bytes) >= 8);
+}
+
+#[test]
+fn cancel_minting() {
+    let bytes = post().to_bytes();
+
+    assert!(Stamp::mint(&address(), &author(), &bytes, 64, |attempts| attempts < Stamp::PROGRESS_INTERVAL).is_none());
+}
+
+#[test]
+fn decode_stamped() {
+    let bytes = post().to_bytes();
+
+    let stamp = Stamp::mint(&address(), &author(), &bytes, 4, |_| true).unwrap();
+    let stamped = stamp.stamp(&bytes);
+
+    let Events::Post(event) = Events::from_bytes(&stamped).unwrap() else {
+        panic!("invalid event type");
+    };
+
+    let Events::Post(original) = post() else {
+        unreachable!();
+    };
+
+    assert_eq!(event, original);
+    assert_eq!(Stamp::unstamp(&stamped), Some((stamp, bytes.as_ref())));
+}
+
+#[test]
+fn nested_stamp() {
+    let bytes = post().to_bytes();
+
+    let stamp = Stamp::new(0);
+    let stamped = stamp.stamp(&stamp.stamp(&bytes));
+
+    assert!(matches!(
+        Events::from_bytes(&stamped),
+        Err(EventDecodeError::NestedStamp)
+    ));
+}
+
+#[test]
+fn stamp_policy() {
+    let event = post();
+    let bytes = event.to_bytes();
+
+    let policy = StampPolicy {
+        post: 8,
+        ..StampPolicy::default()
+    };
+
+    assert!(!policy.check(&address(), &author(), &event, &bytes));
+    assert!(StampPolicy::default().check(&address(), &author(), &event, &bytes));
+
+    let stamp = Stamp::mint(&address(), &author(), &bytes, 8, |_| true).unwrap();
+
+    assert!(policy.check(&address(), &author(), &event, &stamp.stamp(&bytes)));
+}
+
+#[test]
+fn replay_stamp() {
+    let event = post();
+    let bytes = event.to_bytes();
+
+    let policy = StampPolicy {
+        post: 12,
+        ..StampPolicy::default()
+    };
+
+    let stamp = Stamp::mint(&address(), &author(), &bytes, 12, |_| true).unwrap();
+    let stamped = stamp.stamp(&bytes);
+
+    let other_author = signing_key(2).verifying_key();
+    let other_address = Address::new(signing_key(0).verifying_key(), 1);
+
+    assert!(policy.check(&address(), &author(), &event, &stamped));
+
+    // Stamp can't be reused by another key or in another chain.
+    assert!(!policy.check(&address(), &other_author, &event, &stamped));
+    assert!(!policy.check(&other_address, &author(), &event, &stamped));
+
+    // Messages filter checks stamps against the message author.
+    let original = Message::create(&signing_key(1), stamped.clone()).unwrap();
+    let replayed = Message::create(&signing_key(2), stamped).unwrap();
+
+    assert!(messages_filter_with(&policy, &address(), &original, &author()));
+    assert!(!messages_filter_with(&policy, &address(), &replayed, &other_author));
+    assert!(!messages_filter_with(&policy, &other_address, &original, &author()));
+}
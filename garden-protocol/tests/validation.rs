@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+//
+// garden-protocol
+// Copyright (C) 2025  Nikita Podvirnyi <krypt0nn@vk.com>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use garden_protocol::*;
+
+#[test]
+fn content_too_long() {
+    let content = "a".repeat(Content::MAX_LENGTH + 10);
+
+    let err = Content::new(content).unwrap_err();
+
+    assert_eq!(err, ValidationError::ContentTooLong {
+        length: Content::MAX_LENGTH + 10,
+        max_length: Content::MAX_LENGTH
+    });
+
+    assert_eq!(err.to_string(), "content is too long by 10 bytes");
+}
+
+#[test]
+fn tags() {
+    assert!(Tag::new("sport").is_ok());
+    assert!(Tag::new("formula-1").is_ok());
+
+    assert_eq!(Tag::new("").unwrap_err(), ValidationError::EmptyTag);
+
+    assert_eq!(Tag::new("Sport").unwrap_err(), ValidationError::IllegalTagCharacter {
+        tag: String::from("Sport"),
+        character: 'S',
+        index: 0
+    });
+
+    assert_eq!(
+        Tag::new("spört").unwrap_err().to_string(),
+        "tag 'spört' contains illegal character 'ö' at position 3"
+    );
+
+    assert_eq!(Tag::new("-sport").unwrap_err(), ValidationError::LeadingDash {
+        tag: String::from("-sport")
+    });
+
+    assert_eq!(Tag::new("sport-").unwrap_err(), ValidationError::TrailingDash {
+        tag: String::from("sport-")
+    });
+
+    assert!(matches!(
+        Tag::new("a".repeat(Tag::MAX_LENGTH + 1)),
+        Err(ValidationError::TagTooLong { .. })
+    ));
+}
+
+#[test]
+fn too_many_tags() {
+    let tags = (0..PostEvent::MAX_TAGS + 2)
+        .map(|i| Tag::new(i.to_string()).unwrap());
+
+    let err = PostEvent::new(Content::new("").unwrap(), tags).unwrap_err();
+
+    assert_eq!(err, ValidationError::TooManyTags {
+        amount: PostEvent::MAX_TAGS + 2,
+        max_amount: PostEvent::MAX_TAGS
+    });
+}